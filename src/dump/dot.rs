@@ -0,0 +1,65 @@
+use super::{EdgeDump, NodeDump, RouteKeyDump, TreeDump};
+use std::fmt::Write;
+
+pub(super) fn render(dump: &TreeDump) -> String {
+    let mut out = String::from("digraph router {\n  node [shape=box, fontname=\"monospace\"];\n");
+    let mut next_id = 0;
+    write_node(&mut out, &dump.root, &mut next_id);
+
+    if !dump.static_routes.is_empty() {
+        let mut label = String::new();
+        for route in &dump.static_routes {
+            let _ = write!(
+                label,
+                "{} {} -> {}\\l",
+                route.method.as_str(),
+                escape(&route.path),
+                route.key
+            );
+        }
+        let _ = writeln!(out, "  static_routes [shape=note, label=\"{label}\"];");
+    }
+    out.push_str("}\n");
+    out
+}
+
+// preorder numbering, so ids follow the dump's child ordering
+fn write_node(out: &mut String, node: &NodeDump, next_id: &mut usize) {
+    let id = *next_id;
+    *next_id += 1;
+
+    let mut label = escape(&node.edge.label());
+    push_routes(&mut label, &node.routes, "");
+    push_routes(&mut label, &node.wildcard_routes, " *");
+    let _ = writeln!(out, "  n{id} [label=\"{label}\"];");
+
+    for child in &node.children {
+        let style = match child.edge {
+            EdgeDump::Fused(_) => ", style=bold",
+            EdgeDump::Pattern(_) => ", style=dashed",
+            EdgeDump::Root | EdgeDump::Static(_) => "",
+        };
+        let _ = writeln!(
+            out,
+            "  n{id} -> n{} [label=\"{}\"{style}];",
+            *next_id,
+            escape(&child.edge.label())
+        );
+        write_node(out, child, next_id);
+    }
+}
+
+fn push_routes(label: &mut String, routes: &[RouteKeyDump], suffix: &str) {
+    for route in routes {
+        let _ = write!(
+            label,
+            "\\n{}{suffix} -> {}",
+            route.method.as_str(),
+            route.key
+        );
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
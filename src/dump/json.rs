@@ -0,0 +1,96 @@
+use super::{EdgeDump, NodeDump, PartDump, RouteKeyDump, TreeDump};
+use std::fmt::Write;
+
+pub(super) fn render(dump: &TreeDump) -> String {
+    let mut out = String::from("{\"root\":");
+    write_node(&mut out, &dump.root);
+    out.push_str(",\"static_routes\":[");
+    for (idx, route) in dump.static_routes.iter().enumerate() {
+        if idx > 0 {
+            out.push(',');
+        }
+        let _ = write!(
+            out,
+            "{{\"method\":\"{}\",\"path\":{},\"key\":{}}}",
+            route.method.as_str(),
+            string(&route.path),
+            route.key
+        );
+    }
+    out.push_str("]}");
+    out
+}
+
+fn write_node(out: &mut String, node: &NodeDump) {
+    let _ = write!(
+        out,
+        "{{\"kind\":\"{}\",\"edge\":{}",
+        node.edge.kind(),
+        string(&node.edge.label())
+    );
+    if let EdgeDump::Pattern(parts) = &node.edge {
+        out.push_str(",\"parts\":[");
+        for (idx, part) in parts.iter().enumerate() {
+            if idx > 0 {
+                out.push(',');
+            }
+            match part {
+                PartDump::Literal(text) => {
+                    let _ = write!(out, "{{\"literal\":{}}}", string(text));
+                }
+                PartDump::Param { name, constraint } => {
+                    let constraint = constraint.as_deref().map_or("null".to_string(), string);
+                    let _ = write!(
+                        out,
+                        "{{\"param\":{},\"constraint\":{constraint}}}",
+                        string(name)
+                    );
+                }
+            }
+        }
+        out.push(']');
+    }
+    out.push_str(",\"routes\":");
+    write_routes(out, &node.routes);
+    out.push_str(",\"wildcard_routes\":");
+    write_routes(out, &node.wildcard_routes);
+    out.push_str(",\"children\":[");
+    for (idx, child) in node.children.iter().enumerate() {
+        if idx > 0 {
+            out.push(',');
+        }
+        write_node(out, child);
+    }
+    out.push_str("]}");
+}
+
+fn write_routes(out: &mut String, routes: &[RouteKeyDump]) {
+    out.push('{');
+    for (idx, route) in routes.iter().enumerate() {
+        if idx > 0 {
+            out.push(',');
+        }
+        let _ = write!(out, "\"{}\":{}", route.method.as_str(), route.key);
+    }
+    out.push('}');
+}
+
+fn string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", ch as u32);
+            }
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -0,0 +1,210 @@
+mod dot;
+mod json;
+
+use crate::enums::HttpMethod;
+use crate::pattern::{SegmentPart, SegmentPattern};
+use crate::radix::{HTTP_METHOD_COUNT, RadixTree};
+use crate::readonly::converter::{copy_static_maps, extract_root};
+use crate::readonly::{ReadOnlyNode, RouterReadOnly};
+use hashbrown::HashMap as FastHashMap;
+
+/// Structural view of a route tree for debugging, with deterministic ordering:
+/// the fused child first, then static children by key, then pattern children in
+/// evaluation order. Route keys are decoded (as returned by `find`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeDump {
+    pub root: NodeDump,
+    /// Full-path static lookup table, sorted by method then path.
+    pub static_routes: Vec<StaticRouteDump>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeDump {
+    pub edge: EdgeDump,
+    pub routes: Vec<RouteKeyDump>,
+    pub wildcard_routes: Vec<RouteKeyDump>,
+    pub children: Vec<NodeDump>,
+}
+
+/// How a node is reached from its parent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EdgeDump {
+    Root,
+    Static(String),
+    /// Chain of single-child static segments merged by compression, joined by `/`.
+    Fused(String),
+    Pattern(Vec<PartDump>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartDump {
+    Literal(String),
+    Param {
+        name: String,
+        constraint: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteKeyDump {
+    pub method: HttpMethod,
+    pub key: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticRouteDump {
+    pub method: HttpMethod,
+    pub path: String,
+    pub key: u16,
+}
+
+impl TreeDump {
+    fn build(
+        root: &ReadOnlyNode,
+        static_maps: &[FastHashMap<Box<str>, u16>; HTTP_METHOD_COUNT],
+    ) -> Self {
+        let mut static_routes: Vec<StaticRouteDump> = HttpMethod::ALL
+            .iter()
+            .zip(static_maps.iter())
+            .flat_map(|(method, map)| {
+                map.iter().map(|(path, key)| StaticRouteDump {
+                    method: *method,
+                    path: path.to_string(),
+                    key: *key,
+                })
+            })
+            .collect();
+        static_routes.sort_by(|a, b| (a.method as u8, &a.path).cmp(&(b.method as u8, &b.path)));
+
+        Self {
+            root: NodeDump::build(EdgeDump::Root, root),
+            static_routes,
+        }
+    }
+
+    /// Graphviz rendering; see [`TreeDump`] for the ordering guarantees.
+    pub fn to_dot(&self) -> String {
+        dot::render(self)
+    }
+
+    /// Compact JSON rendering; see [`TreeDump`] for the ordering guarantees.
+    pub fn to_json(&self) -> String {
+        json::render(self)
+    }
+}
+
+impl NodeDump {
+    fn build(edge: EdgeDump, node: &ReadOnlyNode) -> Self {
+        let mut children = Vec::new();
+        if let (Some(edge), Some(child)) = (node.fused_edge.as_deref(), node.fused_child.as_deref())
+        {
+            children.push(NodeDump::build(EdgeDump::Fused(edge.to_string()), child));
+        }
+
+        let mut statics: Vec<_> = node.static_children.iter().collect();
+        statics.sort_by_key(|(key, _)| *key);
+        children.extend(
+            statics
+                .into_iter()
+                .map(|(key, child)| NodeDump::build(EdgeDump::Static(key.to_string()), child)),
+        );
+        children.extend(
+            node.patterns
+                .iter()
+                .map(|(pattern, child)| NodeDump::build(EdgeDump::Pattern(parts(pattern)), child)),
+        );
+
+        Self {
+            edge,
+            routes: route_keys(&node.routes),
+            wildcard_routes: route_keys(&node.wildcard_routes),
+            children,
+        }
+    }
+
+    /// Number of nodes in this subtree, including itself.
+    pub fn node_count(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(NodeDump::node_count)
+            .sum::<usize>()
+    }
+}
+
+impl EdgeDump {
+    /// The edge as it would appear in a route path, e.g. `users`, `:id(\d+)`.
+    pub fn label(&self) -> String {
+        match self {
+            EdgeDump::Root => "/".to_string(),
+            EdgeDump::Static(segment) | EdgeDump::Fused(segment) => segment.clone(),
+            EdgeDump::Pattern(parts) => parts.iter().map(PartDump::label).collect(),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            EdgeDump::Root => "root",
+            EdgeDump::Static(_) => "static",
+            EdgeDump::Fused(_) => "fused",
+            EdgeDump::Pattern(_) => "pattern",
+        }
+    }
+}
+
+impl PartDump {
+    fn label(&self) -> String {
+        match self {
+            PartDump::Literal(text) => text.clone(),
+            PartDump::Param {
+                name,
+                constraint: Some(constraint),
+            } => format!(":{name}({constraint})"),
+            PartDump::Param {
+                name,
+                constraint: None,
+            } => format!(":{name}"),
+        }
+    }
+}
+
+fn parts(pattern: &SegmentPattern) -> Vec<PartDump> {
+    pattern
+        .parts
+        .iter()
+        .map(|part| match part {
+            SegmentPart::Literal(text) => PartDump::Literal(text.clone()),
+            SegmentPart::Param { name, constraint } => PartDump::Param {
+                name: name.clone(),
+                constraint: constraint.as_ref().map(|c| c.raw().to_string()),
+            },
+        })
+        .collect()
+}
+
+// node keys are +1 encoded; zero means no route for the method
+fn route_keys(slots: &[u16; HTTP_METHOD_COUNT]) -> Vec<RouteKeyDump> {
+    HttpMethod::ALL
+        .iter()
+        .zip(slots.iter())
+        .filter(|(_, key)| **key != 0)
+        .map(|(method, key)| RouteKeyDump {
+            method: *method,
+            key: key - 1,
+        })
+        .collect()
+}
+
+impl RadixTree {
+    /// Dumps the tree in its current state; compression and the static table only
+    /// show up once the tree has been sealed.
+    pub fn dump(&self) -> TreeDump {
+        TreeDump::build(&extract_root(&self.root_node), &copy_static_maps(self))
+    }
+}
+
+impl RouterReadOnly {
+    pub fn dump(&self) -> TreeDump {
+        TreeDump::build(&self.root, &self.static_maps)
+    }
+}
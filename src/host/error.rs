@@ -0,0 +1,24 @@
+use crate::radix::RadixError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum HostError {
+    #[error("host pattern is empty")]
+    Empty,
+    #[error("host pattern '{host}' contains an empty label")]
+    EmptyLabel { host: String },
+    #[error("wildcard must be the leftmost label in host pattern '{host}'")]
+    WildcardNotLeftmost { host: String },
+    #[error("host pattern '{host}' contains disallowed character '{character}'")]
+    DisallowedCharacter { host: String, character: char },
+    #[error("parameter '{param}' is declared by both host pattern '{host}' and path '{path}'")]
+    ParamConflict {
+        host: String,
+        path: String,
+        param: String,
+    },
+    #[error(transparent)]
+    Radix(#[from] RadixError),
+}
+
+pub type HostResult<T> = Result<T, HostError>;
@@ -0,0 +1,5 @@
+mod error;
+mod pattern;
+
+pub use error::{HostError, HostResult};
+pub use pattern::{HOST_WILDCARD_PARAM, host_lookup_path, host_pattern_path, restore_host_params};
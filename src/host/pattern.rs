@@ -0,0 +1,168 @@
+use super::{HostError, HostResult};
+use crate::types::RouteParams;
+
+/// Parameter name under which a leftmost `*` host label is exposed, kept apart from
+/// the path wildcard `*`.
+pub const HOST_WILDCARD_PARAM: &str = "*host";
+
+/// Converts a host pattern such as `:tenant.example.com` into the reversed,
+/// slash-separated form `/com/example/:tenant` understood by the radix tree.
+pub fn host_pattern_path(host: &str) -> HostResult<String> {
+    let trimmed = host.trim().trim_end_matches('.');
+    if trimmed.is_empty() {
+        return Err(HostError::Empty);
+    }
+
+    let labels = split_labels(trimmed);
+    let mut reversed: Vec<String> = Vec::with_capacity(labels.len());
+    for (idx, label) in labels.iter().enumerate() {
+        if label.is_empty() {
+            return Err(HostError::EmptyLabel {
+                host: host.to_string(),
+            });
+        }
+        if *label == "*" {
+            if idx != 0 {
+                return Err(HostError::WildcardNotLeftmost {
+                    host: host.to_string(),
+                });
+            }
+            reversed.push("*".to_string());
+            continue;
+        }
+        if label.starts_with(':') {
+            reversed.push(label.to_string());
+            continue;
+        }
+        if let Some(character) = label.chars().find(|c| matches!(c, '/' | ':' | '*')) {
+            return Err(HostError::DisallowedCharacter {
+                host: host.to_string(),
+                character,
+            });
+        }
+        reversed.push(label.to_ascii_lowercase());
+    }
+    reversed.reverse();
+
+    Ok(format!("/{}", reversed.join("/")))
+}
+
+/// Converts a request `Host` value into the reversed lookup form, dropping any
+/// port and trailing dot. Returns `None` when the value cannot name a host.
+pub fn host_lookup_path(host: &str) -> Option<String> {
+    let host = strip_port(host.trim()).trim_end_matches('.');
+    if host.is_empty() || host.contains('/') {
+        return None;
+    }
+
+    let mut out = String::with_capacity(host.len() + 1);
+    for label in host.rsplit('.') {
+        if label.is_empty() {
+            return None;
+        }
+        out.push('/');
+        out.push_str(label);
+    }
+    out.make_ascii_lowercase();
+    Some(out)
+}
+
+/// Rewrites captures taken on the reversed host back into host order.
+pub fn restore_host_params(params: RouteParams) -> RouteParams {
+    params
+        .into_iter()
+        .map(|(name, value)| {
+            if name == "*" {
+                let labels: Vec<&str> = value.rsplit('/').collect();
+                (HOST_WILDCARD_PARAM.to_string(), labels.join("."))
+            } else {
+                (name, value)
+            }
+        })
+        .collect()
+}
+
+fn strip_port(host: &str) -> &str {
+    if host.starts_with('[') {
+        return match host.find(']') {
+            Some(end) => &host[..=end],
+            None => host,
+        };
+    }
+    match host.rfind(':') {
+        Some(idx) if host[idx + 1..].bytes().all(|b| b.is_ascii_digit()) => &host[..idx],
+        _ => host,
+    }
+}
+
+/// Splits on `.` outside of inline parameter constraints, so `:id(\d+\.\d+)`
+/// stays a single label.
+fn split_labels(host: &str) -> Vec<&str> {
+    let mut labels = Vec::new();
+    let mut depth = 0usize;
+    let mut escaped = false;
+    let mut start = 0usize;
+
+    for (idx, ch) in host.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match ch {
+            '\\' => escaped = true,
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            '.' if depth == 0 => {
+                labels.push(&host[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    labels.push(&host[start..]);
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reverses_labels_and_lowercases_literals() {
+        let path = host_pattern_path(":Tenant.Example.COM").unwrap();
+        assert_eq!(path, "/com/example/:Tenant");
+    }
+
+    #[test]
+    fn keeps_dots_inside_constraints() {
+        let path = host_pattern_path(r":v(\d+\.\d+).example.com").unwrap();
+        assert_eq!(path, r"/com/example/:v(\d+\.\d+)");
+    }
+
+    #[test]
+    fn rejects_wildcard_outside_leftmost_label() {
+        let err = host_pattern_path("api.*.com").unwrap_err();
+        assert!(matches!(err, HostError::WildcardNotLeftmost { .. }));
+    }
+
+    #[test]
+    fn lookup_path_drops_port_and_trailing_dot() {
+        assert_eq!(
+            host_lookup_path("API.example.com.:8443").as_deref(),
+            Some("/com/example/api")
+        );
+        assert_eq!(host_lookup_path("[::1]:80").as_deref(), Some("/[::1]"));
+        assert_eq!(host_lookup_path("a..b"), None);
+    }
+
+    #[test]
+    fn restores_wildcard_capture_in_host_order() {
+        let mut params = RouteParams::new();
+        params.insert("*".to_string(), "eu/shop".to_string());
+        let restored = restore_host_params(params);
+        assert_eq!(
+            restored.get(HOST_WILDCARD_PARAM).map(|s| s.as_str()),
+            Some("shop.eu")
+        );
+    }
+}
@@ -1,18 +1,26 @@
+pub mod dump;
 pub mod enums;
+pub mod host;
 pub mod matcher;
+pub mod metrics;
+pub mod openapi;
 pub mod path;
 pub mod pattern;
+pub mod query;
 pub mod radix;
 pub mod readonly;
 pub mod registry;
+pub mod request;
 pub mod router;
 pub mod tools;
 pub mod types;
+pub mod version;
 
 pub use enums::HttpMethod;
 pub use router::{
-    MatchOrder, ParamStyle, RepeatMatchMode, RouteOptions, RouteOptionsBuilder, Router,
-    RouterError, RouterOptions, RouterOptionsBuilder, RouterOptionsError, RouterReadOnly,
-    RouterResult,
+    CacheAdmission, CacheOptions, CacheScope, DefaultCachePolicy, GroupDefinition, ManifestError,
+    MatchOrder, ParamStyle, RepeatMatchMode, RouteCachePolicy, RouteDefinition, RouteGroup,
+    RouteManifest, RouteOptions, RouteOptionsBuilder, RouteOverlay, Router, RouterError,
+    RouterOptions, RouterOptionsBuilder, RouterOptionsError, RouterReadOnly, RouterResult,
 };
-pub use types::{RouteMatch, RouteParams};
+pub use types::{MiddlewareChain, RouteMatch, RouteParams};
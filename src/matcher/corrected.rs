@@ -0,0 +1,178 @@
+use crate::enums::HttpMethod;
+use crate::pattern::match_segment;
+use crate::readonly::ReadOnlyNode;
+use regex::Regex;
+
+/// Walks the snapshot comparing static segments without regard to ASCII case and
+/// rebuilds the path using the registered spelling. Parameter and wildcard values
+/// are copied from the request as sent.
+pub fn find_corrected_path(
+    root: &ReadOnlyNode,
+    method: HttpMethod,
+    normalized: &str,
+    default_param_pattern: &Regex,
+) -> Option<(u16, String)> {
+    let mut out = String::with_capacity(normalized.len());
+    let key = correct_from(root, method, normalized, 0, &mut out, default_param_pattern)?;
+    if out.is_empty() {
+        out.push('/');
+    }
+    Some((key, out))
+}
+
+fn correct_from(
+    node: &ReadOnlyNode,
+    method: HttpMethod,
+    path: &str,
+    index: usize,
+    out: &mut String,
+    default_param_pattern: &Regex,
+) -> Option<u16> {
+    let current_index = skip_slash(path, index);
+    let checkpoint = out.len();
+
+    if let Some(edge) = node.fused_edge.as_deref() {
+        let remainder = &path[current_index..];
+        if !starts_with_ignore_case(remainder, edge) {
+            return None;
+        }
+        let child = node.fused_child.as_deref()?;
+        out.push('/');
+        out.push_str(edge);
+        let found = correct_from(
+            child,
+            method,
+            path,
+            current_index + edge.len(),
+            out,
+            default_param_pattern,
+        );
+        if found.is_none() {
+            out.truncate(checkpoint);
+        }
+        return found;
+    }
+
+    if current_index >= path.len() {
+        if path.as_bytes().last() == Some(&b'/')
+            && let Some(next_node) = node.static_children.get("")
+        {
+            out.push('/');
+            if let Some(found) = correct_from(
+                next_node,
+                method,
+                path,
+                current_index,
+                out,
+                default_param_pattern,
+            ) {
+                return Some(found);
+            }
+            out.truncate(checkpoint);
+        }
+        return terminal_key(node, method);
+    }
+
+    let (segment, next_index) = split_segment(path, current_index);
+
+    for (key, next_node) in static_candidates(node, segment) {
+        out.push('/');
+        out.push_str(key);
+        if let Some(found) = correct_from(
+            next_node,
+            method,
+            path,
+            next_index,
+            out,
+            default_param_pattern,
+        ) {
+            return Some(found);
+        }
+        out.truncate(checkpoint);
+    }
+
+    for (pattern, child) in node.patterns.iter() {
+        if match_segment(segment, pattern, default_param_pattern).is_some() {
+            out.push('/');
+            out.push_str(segment);
+            if let Some(found) =
+                correct_from(child, method, path, next_index, out, default_param_pattern)
+            {
+                return Some(found);
+            }
+            out.truncate(checkpoint);
+        }
+    }
+
+    let wildcard = node.wildcard_routes[method as usize];
+    if wildcard != 0 {
+        out.push('/');
+        out.push_str(&path[current_index..]);
+        return Some(wildcard - 1);
+    }
+
+    None
+}
+
+/// Exact spelling first, then every case-insensitive match in sorted order so the
+/// result is deterministic when several spellings were registered.
+fn static_candidates<'a>(
+    node: &'a ReadOnlyNode,
+    segment: &str,
+) -> Vec<(&'a str, &'a ReadOnlyNode)> {
+    let mut candidates: Vec<(&str, &ReadOnlyNode)> = Vec::new();
+    if let Some((key, child)) = node.static_children.get_key_value(segment) {
+        candidates.push((key.as_ref(), child));
+    }
+
+    let mut folded: Vec<(&str, &ReadOnlyNode)> = node
+        .static_children
+        .iter()
+        .filter(|(key, _)| key.as_ref() != segment && key.eq_ignore_ascii_case(segment))
+        .map(|(key, child)| (key.as_ref(), child))
+        .collect();
+    folded.sort_unstable_by(|a, b| a.0.cmp(b.0));
+    candidates.extend(folded);
+    candidates
+}
+
+fn terminal_key(node: &ReadOnlyNode, method: HttpMethod) -> Option<u16> {
+    let method_index = method as usize;
+    let rk = node.routes[method_index];
+    if rk != 0 {
+        return Some(rk - 1);
+    }
+    let wildcard = node.wildcard_routes[method_index];
+    if wildcard != 0 {
+        return Some(wildcard - 1);
+    }
+    None
+}
+
+fn starts_with_ignore_case(haystack: &str, prefix: &str) -> bool {
+    let len = prefix.len();
+    if haystack.len() < len || !haystack.is_char_boundary(len) {
+        return false;
+    }
+    if !haystack[..len].eq_ignore_ascii_case(prefix) {
+        return false;
+    }
+    haystack.len() == len || haystack.as_bytes()[len] == b'/'
+}
+
+fn skip_slash(s: &str, mut index: usize) -> usize {
+    let bytes = s.as_bytes();
+    if index < bytes.len() && bytes[index] == b'/' {
+        index += 1;
+    }
+    index
+}
+
+fn split_segment(s: &str, start: usize) -> (&str, usize) {
+    let bytes = s.as_bytes();
+    let mut end = start;
+    while end < bytes.len() && bytes[end] != b'/' {
+        end += 1;
+    }
+    (&s[start..end], end)
+}
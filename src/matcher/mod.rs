@@ -1,6 +1,10 @@
+pub mod corrected;
 mod params;
 pub mod resolver;
+mod trace;
 
+pub use corrected::find_corrected_path;
 pub(crate) use params::captures_to_map;
 pub use params::with_param_buffer;
-pub use resolver::find_route;
+pub use resolver::{allowed_methods, find_route};
+pub(crate) use trace::trace_route;
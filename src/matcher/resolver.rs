@@ -153,6 +153,73 @@ fn handle_wildcard(
     Some((wildcard - 1, captures_to_map(path, params.clone())))
 }
 
+/// Methods under which [`find_route`] would resolve `normalized`, as a mask with
+/// bit `method as usize` set for each. One walk visits every branch that any
+/// method's lookup could take, instead of one walk per method.
+pub fn allowed_methods(
+    root: &ReadOnlyNode,
+    normalized: &str,
+    default_param_pattern: &Regex,
+) -> u32 {
+    let mut mask = 0;
+    collect_methods(root, normalized, 0, default_param_pattern, &mut mask);
+    mask
+}
+
+fn collect_methods(
+    node: &ReadOnlyNode,
+    path: &str,
+    index: usize,
+    default_param_pattern: &Regex,
+    mask: &mut u32,
+) {
+    let current_index = skip_slashes(path, index);
+
+    if let Some(edge) = node.fused_edge.as_deref() {
+        if path[current_index..].starts_with(edge)
+            && let Some(child) = node.fused_child.as_deref()
+        {
+            collect_methods(
+                child,
+                path,
+                current_index + edge.len(),
+                default_param_pattern,
+                mask,
+            );
+        }
+        return;
+    }
+
+    if current_index >= path.len() {
+        if path.as_bytes().last() == Some(&b'/')
+            && let Some(next_node) = node.static_children.get("")
+        {
+            collect_methods(next_node, path, current_index, default_param_pattern, mask);
+        }
+        *mask |= method_mask(&node.routes) | method_mask(&node.wildcard_routes);
+        return;
+    }
+
+    let (segment, next_index) = split_segment(path, current_index);
+    if let Some(next_node) = node.static_children.get(segment) {
+        collect_methods(next_node, path, next_index, default_param_pattern, mask);
+    }
+    for (pattern, child) in node.patterns.iter() {
+        if match_segment(segment, pattern, default_param_pattern).is_some() {
+            collect_methods(child, path, next_index, default_param_pattern, mask);
+        }
+    }
+    *mask |= method_mask(&node.wildcard_routes);
+}
+
+fn method_mask(routes: &[u16]) -> u32 {
+    routes
+        .iter()
+        .enumerate()
+        .filter(|(_, route)| **route != 0)
+        .fold(0, |mask, (method, _)| mask | 1 << method)
+}
+
 fn skip_slashes(s: &str, mut index: usize) -> usize {
     let bytes = s.as_bytes();
     if index < bytes.len() && bytes[index] == b'/' {
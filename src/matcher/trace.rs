@@ -0,0 +1,240 @@
+use crate::enums::HttpMethod;
+use crate::matcher::captures_to_map;
+use crate::pattern::{SegmentPart, SegmentPattern, match_segment};
+use crate::readonly::{PatternOutcome, ReadOnlyNode, TraceStep};
+use crate::types::{CapturedParam, RouteMatch};
+use regex::Regex;
+
+/// Mirror of `find_route` that records every attempt in `steps`. Kept separate so
+/// the regular lookup stays free of tracing branches.
+pub(crate) fn trace_route(
+    root: &ReadOnlyNode,
+    method: HttpMethod,
+    normalized: &str,
+    default_param_pattern: &Regex,
+    steps: &mut Vec<TraceStep>,
+) -> Option<RouteMatch> {
+    let mut tracer = Tracer {
+        method,
+        path: normalized,
+        default_param_pattern,
+        params: Vec::new(),
+        steps,
+    };
+    tracer.visit(root, 0, 0)
+}
+
+struct Tracer<'a> {
+    method: HttpMethod,
+    path: &'a str,
+    default_param_pattern: &'a Regex,
+    params: Vec<CapturedParam>,
+    steps: &'a mut Vec<TraceStep>,
+}
+
+impl Tracer<'_> {
+    fn visit(&mut self, node: &ReadOnlyNode, index: usize, depth: usize) -> Option<RouteMatch> {
+        let path = self.path;
+        let current_index = skip_slash(path, index);
+
+        if let Some(edge) = node.fused_edge.as_deref() {
+            let matched = path[current_index..].starts_with(edge);
+            self.steps.push(TraceStep::FusedEdge {
+                depth,
+                edge: edge.to_string(),
+                matched,
+            });
+            if !matched {
+                return None;
+            }
+            let fused_depth = depth + edge.split('/').count();
+            return node
+                .fused_child
+                .as_deref()
+                .and_then(|child| self.visit(child, current_index + edge.len(), fused_depth));
+        }
+
+        if current_index >= path.len() {
+            if path.ends_with('/')
+                && let Some(child) = node.static_children.get("")
+            {
+                self.steps.push(TraceStep::StaticChild {
+                    depth,
+                    segment: String::new(),
+                    found: true,
+                });
+                if let Some(found) = self.visit(child, current_index, depth + 1) {
+                    return Some(found);
+                }
+            }
+            return self.terminal(node, depth);
+        }
+
+        let (segment, next_index) = split_segment(path, current_index);
+
+        let static_child = node.static_children.get(segment);
+        self.steps.push(TraceStep::StaticChild {
+            depth,
+            segment: segment.to_string(),
+            found: static_child.is_some(),
+        });
+        if let Some(child) = static_child {
+            if let Some(found) = self.visit(child, next_index, depth + 1) {
+                return Some(found);
+            }
+            self.steps.push(TraceStep::Backtrack {
+                depth,
+                via: segment.to_string(),
+            });
+        }
+
+        for (pattern, child) in node.patterns.iter() {
+            let label = pattern_label(pattern);
+            let Some(captures) = match_segment(segment, pattern, self.default_param_pattern) else {
+                self.steps.push(TraceStep::Pattern {
+                    depth,
+                    segment: segment.to_string(),
+                    pattern: label,
+                    outcome: self.rejection(segment, pattern),
+                });
+                continue;
+            };
+            self.steps.push(TraceStep::Pattern {
+                depth,
+                segment: segment.to_string(),
+                pattern: label.clone(),
+                outcome: PatternOutcome::Matched,
+            });
+
+            let checkpoint = self.params.len();
+            for (name, (offset, len)) in captures {
+                let start = current_index + offset;
+                if start + len <= path.len() {
+                    self.params.push((name, (start, len)));
+                }
+            }
+            if let Some(found) = self.visit(child, next_index, depth + 1) {
+                return Some(found);
+            }
+            self.params.truncate(checkpoint);
+            self.steps.push(TraceStep::Backtrack { depth, via: label });
+        }
+
+        self.wildcard(node, current_index, depth)
+    }
+
+    fn terminal(&mut self, node: &ReadOnlyNode, depth: usize) -> Option<RouteMatch> {
+        let idx = self.method as usize;
+        let route = [node.routes[idx], node.wildcard_routes[idx]]
+            .into_iter()
+            .find(|key| *key != 0)
+            .map(|key| key - 1);
+        let available = HttpMethod::ALL
+            .into_iter()
+            .filter(|m| node.routes[*m as usize] != 0 || node.wildcard_routes[*m as usize] != 0)
+            .collect();
+        self.steps.push(TraceStep::Terminal {
+            depth,
+            route,
+            available,
+        });
+        route.map(|key| (key, captures_to_map(self.path, self.params.clone())))
+    }
+
+    fn wildcard(
+        &mut self,
+        node: &ReadOnlyNode,
+        start_index: usize,
+        depth: usize,
+    ) -> Option<RouteMatch> {
+        let slot = node.wildcard_routes[self.method as usize];
+        let route = slot.checked_sub(1);
+        self.steps.push(TraceStep::Wildcard { depth, route });
+        let key = route?;
+
+        let capture_start = skip_slash(self.path, start_index);
+        let mut params = self.params.clone();
+        if capture_start < self.path.len() {
+            params.push((
+                "*".to_string(),
+                (capture_start, self.path.len() - capture_start),
+            ));
+        }
+        Some((key, captures_to_map(self.path, params)))
+    }
+
+    /// Tells a constraint failure apart from a shape mismatch by retrying the
+    /// pattern with its constraints removed.
+    fn rejection(&self, segment: &str, pattern: &SegmentPattern) -> PatternOutcome {
+        let relaxed = SegmentPattern {
+            parts: pattern
+                .parts
+                .iter()
+                .map(|part| match part {
+                    SegmentPart::Param { name, .. } => SegmentPart::Param {
+                        name: name.clone(),
+                        constraint: None,
+                    },
+                    literal => literal.clone(),
+                })
+                .collect(),
+        };
+        let Some(captures) = match_segment(segment, &relaxed, self.default_param_pattern) else {
+            return PatternOutcome::Mismatch;
+        };
+
+        for (name, (offset, len)) in captures {
+            let value = &segment[offset..offset + len];
+            let constraint = pattern.parts.iter().find_map(|part| match part {
+                SegmentPart::Param {
+                    name: param,
+                    constraint: Some(constraint),
+                } if *param == name => Some(constraint),
+                _ => None,
+            });
+            if let Some(constraint) = constraint
+                && !constraint.regex().is_some_and(|re| re.is_match(value))
+            {
+                return PatternOutcome::ConstraintRejected {
+                    param: name,
+                    constraint: constraint.raw().to_string(),
+                    value: value.to_string(),
+                };
+            }
+        }
+        PatternOutcome::Mismatch
+    }
+}
+
+fn pattern_label(pattern: &SegmentPattern) -> String {
+    pattern
+        .parts
+        .iter()
+        .map(|part| match part {
+            SegmentPart::Literal(text) => text.clone(),
+            SegmentPart::Param {
+                name,
+                constraint: Some(constraint),
+            } => format!(":{name}({})", constraint.raw()),
+            SegmentPart::Param {
+                name,
+                constraint: None,
+            } => format!(":{name}"),
+        })
+        .collect()
+}
+
+fn skip_slash(path: &str, index: usize) -> usize {
+    if path.as_bytes().get(index) == Some(&b'/') {
+        index + 1
+    } else {
+        index
+    }
+}
+
+fn split_segment(path: &str, start: usize) -> (&str, usize) {
+    let end = path[start..]
+        .find('/')
+        .map_or(path.len(), |offset| start + offset);
+    (&path[start..end], end)
+}
@@ -0,0 +1,126 @@
+mod openmetrics;
+
+use crate::readonly::RouterReadOnly;
+use thiserror::Error;
+
+pub const DEFAULT_METRIC_PREFIX: &str = "bunner_router";
+
+/// Label names the encoder sets itself on some families.
+const RESERVED_LABELS: [&str; 2] = ["result", "reason"];
+
+/// Renders the counters of a [`RouterReadOnly`] in the OpenMetrics text format,
+/// ready to be served to a Prometheus scrape. Every metric name starts with the
+/// prefix and every sample carries the constant labels.
+///
+/// Families: `_routes` and `_snapshot_generation` gauges always; with the cache
+/// enabled, `_cache_hits`, `_cache_negative_hits`, `_cache_misses`,
+/// `_cache_evictions` and `_cache_rejections` counters and a `_cache_entries`
+/// gauge; with lookup tracking on, a `_lookups` counter labelled by `result` and,
+/// for invalid paths, by `reason`.
+#[derive(Debug, Clone)]
+pub struct MetricsEncoder {
+    prefix: String,
+    labels: Vec<(String, String)>,
+}
+
+impl Default for MetricsEncoder {
+    fn default() -> Self {
+        Self {
+            prefix: DEFAULT_METRIC_PREFIX.to_string(),
+            labels: Vec::new(),
+        }
+    }
+}
+
+impl MetricsEncoder {
+    pub fn builder() -> MetricsEncoderBuilder {
+        MetricsEncoderBuilder::default()
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn labels(&self) -> &[(String, String)] {
+        &self.labels
+    }
+
+    /// One complete exposition, terminated by `# EOF`.
+    pub fn encode(&self, snapshot: &RouterReadOnly) -> String {
+        openmetrics::render(self, snapshot)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct MetricsEncoderBuilder {
+    prefix: Option<String>,
+    labels: Vec<(String, String)>,
+}
+
+impl MetricsEncoderBuilder {
+    pub fn prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Adds a constant label to every sample, such as the service or instance name.
+    pub fn label<K: Into<String>, V: Into<String>>(mut self, name: K, value: V) -> Self {
+        self.labels.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn build(self) -> Result<MetricsEncoder, MetricsError> {
+        let prefix = self
+            .prefix
+            .unwrap_or_else(|| DEFAULT_METRIC_PREFIX.to_string());
+        if !is_metric_name(&prefix) {
+            return Err(MetricsError::InvalidPrefix { prefix });
+        }
+        for (idx, (name, _)) in self.labels.iter().enumerate() {
+            if !is_label_name(name) {
+                return Err(MetricsError::InvalidLabelName { name: name.clone() });
+            }
+            if RESERVED_LABELS.contains(&name.as_str()) {
+                return Err(MetricsError::ReservedLabel { name: name.clone() });
+            }
+            if self.labels[..idx].iter().any(|(seen, _)| seen == name) {
+                return Err(MetricsError::DuplicateLabel { name: name.clone() });
+            }
+        }
+        Ok(MetricsEncoder {
+            prefix,
+            labels: self.labels,
+        })
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MetricsError {
+    #[error("metric prefix '{prefix}' is not a valid metric name")]
+    InvalidPrefix { prefix: String },
+    #[error("label name '{name}' is not valid")]
+    InvalidLabelName { name: String },
+    #[error("label name '{name}' is reserved by the encoder")]
+    ReservedLabel { name: String },
+    #[error("label '{name}' is given more than once")]
+    DuplicateLabel { name: String },
+}
+
+/// `[a-zA-Z_:][a-zA-Z0-9_:]*`
+fn is_metric_name(name: &str) -> bool {
+    let mut bytes = name.bytes();
+    bytes
+        .next()
+        .is_some_and(|b| b.is_ascii_alphabetic() || b == b'_' || b == b':')
+        && bytes.all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b':')
+}
+
+/// `[a-zA-Z_][a-zA-Z0-9_]*`, without the `__` prefix kept for internal use.
+fn is_label_name(name: &str) -> bool {
+    let mut bytes = name.bytes();
+    !name.starts_with("__")
+        && bytes
+            .next()
+            .is_some_and(|b| b.is_ascii_alphabetic() || b == b'_')
+        && bytes.all(|b| b.is_ascii_alphanumeric() || b == b'_')
+}
@@ -0,0 +1,144 @@
+use super::MetricsEncoder;
+use crate::readonly::RouterReadOnly;
+use std::fmt::Write;
+
+pub(super) fn render(encoder: &MetricsEncoder, snapshot: &RouterReadOnly) -> String {
+    let mut out = Exposition {
+        out: String::new(),
+        prefix: &encoder.prefix,
+        labels: render_labels(&encoder.labels),
+    };
+
+    out.gauge(
+        "routes",
+        "Route keys in the sealed snapshot.",
+        snapshot.route_count() as u64,
+    );
+    out.gauge(
+        "snapshot_generation",
+        "Sequence number of the live snapshot.",
+        snapshot.generation(),
+    );
+
+    if let Some(cache) = snapshot.cache_counters() {
+        out.counter(
+            "cache_hits",
+            "Lookups answered with a cached match.",
+            &[(&[], cache.hits)],
+        );
+        out.counter(
+            "cache_negative_hits",
+            "Lookups answered with a cached not-found.",
+            &[(&[], cache.negative_hits)],
+        );
+        out.counter(
+            "cache_misses",
+            "Lookups that missed the cache.",
+            &[(&[], cache.misses)],
+        );
+        out.counter(
+            "cache_evictions",
+            "Entries evicted to make room.",
+            &[(&[], cache.evictions)],
+        );
+        out.counter(
+            "cache_rejections",
+            "Results the admission policy declined to cache.",
+            &[(&[], cache.rejected)],
+        );
+    }
+    if let Some(entries) = snapshot.cache_entries() {
+        out.gauge(
+            "cache_entries",
+            "Entries in the route cache.",
+            entries as u64,
+        );
+    }
+
+    if let Some(lookups) = snapshot.lookup_counters() {
+        let mut samples: Vec<(&[(&str, &str)], u64)> = vec![
+            (&[("result", "match")], lookups.matched),
+            (&[("result", "not_found")], lookups.not_found),
+            (
+                &[("result", "method_not_allowed")],
+                lookups.method_not_allowed,
+            ),
+        ];
+        let reasons: Vec<[(&str, &str); 2]> = lookups
+            .invalid_path
+            .iter()
+            .map(|(kind, _)| [("result", "invalid_path"), ("reason", *kind)])
+            .collect();
+        for (labels, (_, count)) in reasons.iter().zip(lookups.invalid_path.iter()) {
+            samples.push((labels, *count));
+        }
+        out.counter("lookups", "Lookups by outcome.", &samples);
+    }
+
+    out.out.push_str("# EOF\n");
+    out.out
+}
+
+struct Exposition<'a> {
+    out: String,
+    prefix: &'a str,
+    /// Constant labels, already escaped and joined.
+    labels: String,
+}
+
+impl Exposition<'_> {
+    fn header(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.out, "# TYPE {}_{name} {kind}", self.prefix);
+        let _ = writeln!(self.out, "# HELP {}_{name} {help}", self.prefix);
+    }
+
+    fn gauge(&mut self, name: &str, help: &str, value: u64) {
+        self.header(name, "gauge", help);
+        self.sample(name, &[], value);
+    }
+
+    fn counter(&mut self, name: &str, help: &str, samples: &[(&[(&str, &str)], u64)]) {
+        self.header(name, "counter", help);
+        for (labels, value) in samples {
+            self.sample(&format!("{name}_total"), labels, *value);
+        }
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: u64) {
+        let _ = write!(self.out, "{}_{name}", self.prefix);
+        let extra = render_labels(labels);
+        let joined = match (self.labels.is_empty(), extra.is_empty()) {
+            (true, _) => extra,
+            (false, true) => self.labels.clone(),
+            (false, false) => format!("{},{extra}", self.labels),
+        };
+        if !joined.is_empty() {
+            let _ = write!(self.out, "{{{joined}}}");
+        }
+        let _ = writeln!(self.out, " {value}");
+    }
+}
+
+fn render_labels<K: AsRef<str>, V: AsRef<str>>(labels: &[(K, V)]) -> String {
+    let mut out = String::new();
+    for (idx, (name, value)) in labels.iter().enumerate() {
+        if idx > 0 {
+            out.push(',');
+        }
+        let _ = write!(out, "{}=\"{}\"", name.as_ref(), escape(value.as_ref()));
+    }
+    out
+}
+
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            ch => out.push(ch),
+        }
+    }
+    out
+}
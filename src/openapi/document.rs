@@ -0,0 +1,147 @@
+use crate::enums::HttpMethod;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// OpenAPI `paths`: templated path to its operations.
+pub type OpenApiPaths = BTreeMap<String, OpenApiPathItem>;
+
+/// Operations of one path, plus parameters shared by all of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenApiPathItem {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<OpenApiParameter>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub get: Option<OpenApiOperation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post: Option<OpenApiOperation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub put: Option<OpenApiOperation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delete: Option<OpenApiOperation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub patch: Option<OpenApiOperation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head: Option<OpenApiOperation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<OpenApiOperation>,
+}
+
+impl OpenApiPathItem {
+    pub fn operation(&self, method: HttpMethod) -> Option<&OpenApiOperation> {
+        match method {
+            HttpMethod::Get => self.get.as_ref(),
+            HttpMethod::Post => self.post.as_ref(),
+            HttpMethod::Put => self.put.as_ref(),
+            HttpMethod::Delete => self.delete.as_ref(),
+            HttpMethod::Patch => self.patch.as_ref(),
+            HttpMethod::Head => self.head.as_ref(),
+            HttpMethod::Options => self.options.as_ref(),
+        }
+    }
+
+    pub fn operation_slot(&mut self, method: HttpMethod) -> &mut Option<OpenApiOperation> {
+        match method {
+            HttpMethod::Get => &mut self.get,
+            HttpMethod::Post => &mut self.post,
+            HttpMethod::Put => &mut self.put,
+            HttpMethod::Delete => &mut self.delete,
+            HttpMethod::Patch => &mut self.patch,
+            HttpMethod::Head => &mut self.head,
+            HttpMethod::Options => &mut self.options,
+        }
+    }
+
+    /// Operations in method order (`GET`, `POST`, ...).
+    pub fn operations(&self) -> impl Iterator<Item = (HttpMethod, &OpenApiOperation)> {
+        HttpMethod::ALL
+            .into_iter()
+            .filter_map(|method| self.operation(method).map(|op| (method, op)))
+    }
+}
+
+/// The subset of an OpenAPI 3 document the router can produce or consume.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenApiDocument {
+    pub openapi: String,
+    pub info: OpenApiInfo,
+    #[serde(default)]
+    pub paths: OpenApiPaths,
+}
+
+impl OpenApiDocument {
+    pub const VERSION: &'static str = "3.0.3";
+
+    pub fn new(info: OpenApiInfo, paths: OpenApiPaths) -> Self {
+        Self {
+            openapi: Self::VERSION.to_string(),
+            info,
+            paths,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenApiInfo {
+    pub title: String,
+    pub version: String,
+}
+
+impl OpenApiInfo {
+    pub fn new<T: Into<String>, V: Into<String>>(title: T, version: V) -> Self {
+        Self {
+            title: title.into(),
+            version: version.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenApiOperation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<OpenApiParameter>,
+}
+
+/// A parameter object. `$ref` parameters are not resolved; they deserialize with
+/// an empty name and location and are ignored on import.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenApiParameter {
+    #[serde(default)]
+    pub name: String,
+    #[serde(rename = "in", default)]
+    pub location: String,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub schema: OpenApiSchema,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenApiSchema {
+    #[serde(rename = "type", default = "default_schema_kind")]
+    pub kind: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+}
+
+impl Default for OpenApiSchema {
+    fn default() -> Self {
+        Self {
+            kind: default_schema_kind(),
+            format: None,
+            pattern: None,
+        }
+    }
+}
+
+fn default_schema_kind() -> String {
+    "string".to_string()
+}
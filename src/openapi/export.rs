@@ -0,0 +1,135 @@
+use super::{
+    OpenApiDocument, OpenApiInfo, OpenApiOperation, OpenApiParameter, OpenApiPaths, OpenApiSchema,
+};
+use crate::pattern::{SegmentPart, parse_segment};
+use crate::registry::RouteRecord;
+use crate::router::{RouteOptions, Router, RouterOptions};
+use crate::version::VersionSource;
+
+/// Name given to a trailing `*` segment, which OpenAPI cannot express as-is.
+pub const WILDCARD_PARAM_NAME: &str = "wildcard";
+
+/// Meta keys read into operation fields. `tags` is a comma-separated list.
+pub(super) const META_OPERATION_ID: &str = "operationId";
+pub(super) const META_SUMMARY: &str = "summary";
+pub(super) const META_TAGS: &str = "tags";
+
+impl Router {
+    /// Exports the registered routes as an OpenAPI 3 document. Available before and
+    /// after sealing.
+    pub fn to_openapi(&self, info: OpenApiInfo) -> OpenApiDocument {
+        let paths =
+            self.with_registry(|registry| openapi_paths(registry.records(), registry.options()));
+        OpenApiDocument::new(info, paths)
+    }
+}
+
+/// Builds OpenAPI `paths` from route records.
+///
+/// Host-scoped routes are left out, since a path item cannot name a host. Versioned
+/// routes are placed under the router's first path-prefix version source, e.g.
+/// `/v2/orders`. When several routes share a path and method (query or header
+/// variants), the first one registered is exported.
+pub fn openapi_paths(records: &[RouteRecord], options: &RouterOptions) -> OpenApiPaths {
+    let version_prefix = options
+        .versioning
+        .sources
+        .iter()
+        .find_map(|source| match source {
+            VersionSource::PathPrefix { prefix } => Some(prefix.as_str()),
+            _ => None,
+        });
+
+    let mut paths = OpenApiPaths::new();
+    for record in records.iter().filter(|record| record.host.is_none()) {
+        let mut path = record.path.clone();
+        if let (Some(version), Some(prefix)) = (record.options.version, version_prefix) {
+            path = format!("/{prefix}{version}{}", if path == "/" { "" } else { &path });
+        }
+        let (template, parameters) = openapi_path(&path, &record.options);
+
+        let item = paths.entry(template).or_default();
+        item.operation_slot(record.method)
+            .get_or_insert_with(|| operation(&record.options, parameters));
+    }
+    paths
+}
+
+/// Converts a route path into an OpenAPI path template plus its path parameters.
+/// `:id` becomes `{id}`, and the parameter's constraint, inline or from
+/// `options.constraints`, becomes an anchored `pattern`.
+pub fn openapi_path(path: &str, options: &RouteOptions) -> (String, Vec<OpenApiParameter>) {
+    let mut parameters = Vec::new();
+    let mut segments = Vec::new();
+
+    for segment in path.split('/') {
+        if segment == "*" {
+            segments.push(format!("{{{WILDCARD_PARAM_NAME}}}"));
+            parameters.push(path_parameter(WILDCARD_PARAM_NAME, Some(".*")));
+            continue;
+        }
+        let Ok(pattern) = parse_segment(segment) else {
+            segments.push(segment.to_string());
+            continue;
+        };
+
+        let mut out = String::new();
+        for part in pattern.parts.iter() {
+            match part {
+                SegmentPart::Literal(value) => out.push_str(value),
+                SegmentPart::Param { name, constraint } => {
+                    out.push('{');
+                    out.push_str(name);
+                    out.push('}');
+                    let raw = constraint
+                        .as_ref()
+                        .map(|c| c.raw())
+                        .or_else(|| options.constraints.get(name).map(String::as_str));
+                    parameters.push(path_parameter(name, raw));
+                }
+            }
+        }
+        segments.push(out);
+    }
+
+    let template = segments.join("/");
+    let template = if template.is_empty() {
+        "/".to_string()
+    } else {
+        template
+    };
+    (template, parameters)
+}
+
+fn path_parameter(name: &str, constraint: Option<&str>) -> OpenApiParameter {
+    OpenApiParameter {
+        name: name.to_string(),
+        location: "path".to_string(),
+        required: true,
+        schema: OpenApiSchema {
+            pattern: constraint.map(|raw| format!("^(?:{raw})$")),
+            ..OpenApiSchema::default()
+        },
+    }
+}
+
+fn operation(options: &RouteOptions, parameters: Vec<OpenApiParameter>) -> OpenApiOperation {
+    let tags = options
+        .meta
+        .get(META_TAGS)
+        .map(|tags| {
+            tags.split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    OpenApiOperation {
+        operation_id: options.meta.get(META_OPERATION_ID).cloned(),
+        summary: options.meta.get(META_SUMMARY).cloned(),
+        tags,
+        parameters,
+    }
+}
@@ -0,0 +1,179 @@
+use super::export::{META_OPERATION_ID, META_SUMMARY, META_TAGS, WILDCARD_PARAM_NAME};
+use super::{OpenApiDocument, OpenApiOperation, OpenApiParameter, OpenApiSchema};
+use crate::pattern::PatternError;
+use crate::radix::RadixError;
+use crate::router::{RouteOptions, Router, RouterError, RouterResult};
+use std::collections::HashMap;
+
+const UUID_PATTERN: &str =
+    "[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}";
+const INTEGER_PATTERN: &str = "-?[0-9]+";
+const NUMBER_PATTERN: &str = "-?[0-9]+(?:\\.[0-9]+)?";
+
+impl Router {
+    /// Parses an OpenAPI 3 JSON document and registers its operations; see
+    /// [`add_from_openapi_document`](Self::add_from_openapi_document).
+    pub fn add_from_openapi(&self, json: &str) -> RouterResult<HashMap<String, u16>> {
+        let document: OpenApiDocument = serde_json::from_str(json)?;
+        self.add_from_openapi_document(&document)
+    }
+
+    /// Registers every operation in `document.paths`, one route per path and method.
+    ///
+    /// `{param}` templates become `:param`, a trailing `{wildcard}` becomes `*`, and
+    /// path parameter schemas (`pattern`, `format: uuid`, `integer`, `number`) become
+    /// constraints. `operationId`, `summary` and `tags` are stored in `meta`, and the
+    /// operationId is also the route alias. Returns operationId to route key.
+    ///
+    /// All operations are checked before any is registered, so an error leaves the
+    /// router unchanged. Segments mixing a parameter with literal text, such as
+    /// `{id}.json`, have no equivalent in this crate and are rejected.
+    pub fn add_from_openapi_document(
+        &self,
+        document: &OpenApiDocument,
+    ) -> RouterResult<HashMap<String, u16>> {
+        let mut operations = Vec::new();
+        let mut routes = Vec::new();
+        for (template, item) in document.paths.iter() {
+            for (method, operation) in item.operations() {
+                let path = route_path(template).map_err(|err| RouterError::OpenApiImport {
+                    path: template.clone(),
+                    method,
+                    source: Box::new(RadixError::from(err).into()),
+                })?;
+                let options = RouteOptions {
+                    methods: vec![method],
+                    constraints: constraints(&item.parameters, &operation.parameters),
+                    meta: meta(operation),
+                    alias: operation.operation_id.clone(),
+                    ..RouteOptions::default()
+                };
+                operations.push((template, method, operation.operation_id.as_ref()));
+                routes.push((path, options));
+            }
+        }
+
+        let keys = self.add_all_with_options(&routes, |idx, err| {
+            let (template, method, _) = operations[idx];
+            RouterError::OpenApiImport {
+                path: template.clone(),
+                method,
+                source: Box::new(err),
+            }
+        })?;
+        Ok(operations
+            .iter()
+            .zip(keys)
+            .filter_map(|((_, _, id), keys)| id.map(|id| (id.clone(), keys[0])))
+            .collect())
+    }
+}
+
+/// `/users/{id}` to `/users/:id`; a whole trailing `{wildcard}` segment becomes `*`.
+/// A segment holding anything besides one whole `{param}` and braces is rejected.
+fn route_path(template: &str) -> Result<String, PatternError> {
+    let segments: Vec<&str> = template.split('/').collect();
+    let last = segments.len() - 1;
+    let mut out = Vec::with_capacity(segments.len());
+    for (idx, segment) in segments.iter().enumerate() {
+        let param = segment
+            .strip_prefix('{')
+            .and_then(|s| s.strip_suffix('}'))
+            .filter(|name| !name.contains(['{', '}']));
+        out.push(match param {
+            Some(WILDCARD_PARAM_NAME) if idx == last => "*".to_string(),
+            Some(name) => format!(":{name}"),
+            None if segment.contains(['{', '}']) => {
+                return Err(PatternError::MixedParameterLiteralSyntax {
+                    segment: segment.to_string(),
+                });
+            }
+            None => segment.to_string(),
+        });
+    }
+    Ok(out.join("/"))
+}
+
+/// Path parameter constraints; operation-level parameters override path-level ones.
+fn constraints(shared: &[OpenApiParameter], own: &[OpenApiParameter]) -> HashMap<String, String> {
+    shared
+        .iter()
+        .chain(own.iter())
+        .filter(|param| param.location == "path" && param.name != WILDCARD_PARAM_NAME)
+        .filter_map(|param| schema_constraint(&param.schema).map(|re| (param.name.clone(), re)))
+        .collect()
+}
+
+fn schema_constraint(schema: &OpenApiSchema) -> Option<String> {
+    if let Some(pattern) = schema.pattern.as_deref() {
+        return Some(unanchor(pattern).to_string());
+    }
+    if schema.format.as_deref() == Some("uuid") {
+        return Some(UUID_PATTERN.to_string());
+    }
+    match schema.kind.as_str() {
+        "integer" => Some(INTEGER_PATTERN.to_string()),
+        "number" => Some(NUMBER_PATTERN.to_string()),
+        _ => None,
+    }
+}
+
+/// Constraints are matched against the whole segment, so surrounding anchors
+/// (including the `^(?:...)$` form written by the exporter) are dropped.
+fn unanchor(pattern: &str) -> &str {
+    let inner = pattern.strip_prefix('^').unwrap_or(pattern);
+    let inner = match inner.strip_suffix('$') {
+        Some(stripped) if !stripped.ends_with('\\') => stripped,
+        _ => inner,
+    };
+    inner
+        .strip_prefix("(?:")
+        .and_then(|s| s.strip_suffix(')'))
+        .filter(|s| !s.contains(['(', ')']))
+        .unwrap_or(inner)
+}
+
+fn meta(operation: &OpenApiOperation) -> HashMap<String, String> {
+    let mut meta = HashMap::new();
+    if let Some(id) = operation.operation_id.as_ref() {
+        meta.insert(META_OPERATION_ID.to_string(), id.clone());
+    }
+    if let Some(summary) = operation.summary.as_ref() {
+        meta.insert(META_SUMMARY.to_string(), summary.clone());
+    }
+    if !operation.tags.is_empty() {
+        meta.insert(META_TAGS.to_string(), operation.tags.join(", "));
+    }
+    meta
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_templates_to_route_syntax() {
+        assert_eq!(route_path("/users/{id}/posts").unwrap(), "/users/:id/posts");
+        assert_eq!(route_path("/files/{wildcard}").unwrap(), "/files/*");
+        assert_eq!(route_path("/{wildcard}/x").unwrap(), "/:wildcard/x");
+        assert_eq!(route_path("/").unwrap(), "/");
+    }
+
+    #[test]
+    fn rejects_params_mixed_with_literals() {
+        for template in ["/files/{id}.json", "/v{major}", "/{a}{b}"] {
+            assert!(matches!(
+                route_path(template),
+                Err(PatternError::MixedParameterLiteralSyntax { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn strips_anchors_from_patterns() {
+        assert_eq!(unanchor("^\\d+$"), "\\d+");
+        assert_eq!(unanchor("^(?:[a-z]+)$"), "[a-z]+");
+        assert_eq!(unanchor("(?:a)|(?:b)"), "(?:a)|(?:b)");
+        assert_eq!(unanchor("a\\$"), "a\\$");
+    }
+}
@@ -0,0 +1,9 @@
+mod document;
+mod export;
+mod import;
+
+pub use document::{
+    OpenApiDocument, OpenApiInfo, OpenApiOperation, OpenApiParameter, OpenApiPathItem,
+    OpenApiPaths, OpenApiSchema,
+};
+pub use export::{WILDCARD_PARAM_NAME, openapi_path, openapi_paths};
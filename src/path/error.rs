@@ -24,4 +24,36 @@ pub enum PathError {
     InvalidAfterNormalization { input: String, normalized: String },
 }
 
+impl PathError {
+    /// Every value of [`kind`](Self::kind), in declaration order.
+    pub const KINDS: [&'static str; 8] = [
+        "non_ascii",
+        "empty",
+        "control_or_whitespace",
+        "disallowed_character",
+        "invalid_parent_traversal",
+        "invalid_percent_encoding",
+        "invalid_utf8_after_decoding",
+        "invalid_after_normalization",
+    ];
+
+    /// Snake-case name of the variant, stable for use as a metric label.
+    pub fn kind(&self) -> &'static str {
+        Self::KINDS[self.kind_index()]
+    }
+
+    pub(crate) fn kind_index(&self) -> usize {
+        match self {
+            PathError::NonAscii { .. } => 0,
+            PathError::Empty => 1,
+            PathError::ControlOrWhitespace { .. } => 2,
+            PathError::DisallowedCharacter { .. } => 3,
+            PathError::InvalidParentTraversal { .. } => 4,
+            PathError::InvalidPercentEncoding { .. } => 5,
+            PathError::InvalidUtf8AfterDecoding { .. } => 6,
+            PathError::InvalidAfterNormalization { .. } => 7,
+        }
+    }
+}
+
 pub type PathResult<T> = Result<T, PathError>;
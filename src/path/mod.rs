@@ -2,4 +2,5 @@ mod error;
 mod normalize;
 
 pub use error::{PathError, PathResult};
+pub(crate) use normalize::decode_hex_pair;
 pub use normalize::{NormalizationOptions, normalize_and_validate_path, normalize_path};
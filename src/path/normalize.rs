@@ -157,7 +157,7 @@ fn finalize_segment(output: &[u8], segment_start: usize, saw_parent_traversal: &
     }
 }
 
-fn decode_hex_pair(hi: u8, lo: u8) -> Option<u8> {
+pub(crate) fn decode_hex_pair(hi: u8, lo: u8) -> Option<u8> {
     fn val(byte: u8) -> Option<u8> {
         match byte {
             b'0'..=b'9' => Some(byte - b'0'),
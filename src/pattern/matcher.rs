@@ -78,26 +78,15 @@ pub fn match_segment(
                 }
 
                 if let Some(constraint) = constraint {
-                    if let Some(regex) = constraint.compiled() {
-                        if !regex.is_match(capture) {
-                            return None;
-                        }
-                    } else {
-                        debug_assert!(
-                            false,
-                            "parameter constraint missing compiled regex; falling back to runtime compile",
-                        );
-                        let pattern = format!("^(?:{})$", constraint.raw());
-                        match Regex::new(&pattern) {
-                            Ok(regex) => {
-                                if !regex.is_match(capture) {
-                                    return None;
-                                }
-                            }
-                            Err(_) => {
+                    match constraint.regex() {
+                        Some(regex) => {
+                            if !regex.is_match(capture) {
                                 return None;
                             }
                         }
+                        None => {
+                            return None;
+                        }
                     }
                 }
 
@@ -22,4 +22,4 @@ pub use lexer::parse_segment;
 pub use matcher::{CaptureList, CapturedParam, ParamOffset, match_segment};
 pub use parser::parse_pattern;
 pub use scoring::{pattern_compatible_policy, pattern_is_pure_static, pattern_score};
-pub use segment::{SegmentPart, SegmentPattern};
+pub use segment::{ParamConstraint, SegmentPart, SegmentPattern};
@@ -43,10 +43,10 @@ pub fn pattern_compatible_policy(a: &SegmentPattern, b: &SegmentPattern) -> bool
     for (pa, pb) in a.parts.iter().zip(b.parts.iter()) {
         match (pa, pb) {
             (SegmentPart::Literal(_), SegmentPart::Literal(_)) => { /* allowed */ }
-            (SegmentPart::Param { name: na, .. }, SegmentPart::Param { name: nb, .. }) => {
-                if na != nb {
-                    return false;
-                }
+            (SegmentPart::Param { name: na, .. }, SegmentPart::Param { name: nb, .. })
+                if na != nb =>
+            {
+                return false;
             }
             _ => { /* literal vs param allowed */ }
         }
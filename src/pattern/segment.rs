@@ -1,17 +1,19 @@
 use regex::Regex;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
+/// Regex constraint of a parameter. The compiled form is filled in at insert time,
+/// or on first use for constraints restored from a snapshot image.
 #[derive(Debug, Clone)]
 pub struct ParamConstraint {
     raw: Box<str>,
-    compiled: Option<Arc<Regex>>,
+    compiled: OnceLock<Option<Arc<Regex>>>,
 }
 
 impl ParamConstraint {
     pub fn new(raw: String) -> Self {
         Self {
             raw: raw.into_boxed_str(),
-            compiled: None,
+            compiled: OnceLock::new(),
         }
     }
 
@@ -20,11 +22,23 @@ impl ParamConstraint {
     }
 
     pub fn compiled(&self) -> Option<&Arc<Regex>> {
-        self.compiled.as_ref()
+        self.compiled.get().and_then(Option::as_ref)
     }
 
     pub fn set_compiled(&mut self, regex: Arc<Regex>) {
-        self.compiled = Some(regex);
+        self.compiled = OnceLock::from(Some(regex));
+    }
+
+    /// Compiled regex, compiling the raw source on first call when it was not set
+    /// up front. `None` when the source does not compile.
+    pub fn regex(&self) -> Option<&Arc<Regex>> {
+        self.compiled
+            .get_or_init(|| {
+                Regex::new(&format!("^(?:{})$", self.raw))
+                    .ok()
+                    .map(Arc::new)
+            })
+            .as_ref()
     }
 }
 
@@ -0,0 +1,82 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::QueryParams;
+
+/// Requirement a route places on the request query string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum QueryCondition {
+    /// The parameter must be present, with any value.
+    Present { name: String },
+    /// The first value of the parameter must equal `value`.
+    Equals { name: String, value: String },
+    /// The first value of the parameter must fully match the regex `pattern`.
+    Matches { name: String, pattern: String },
+}
+
+impl QueryCondition {
+    pub fn present<S: Into<String>>(name: S) -> Self {
+        Self::Present { name: name.into() }
+    }
+
+    pub fn equals<S: Into<String>, V: Into<String>>(name: S, value: V) -> Self {
+        Self::Equals {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn matches<S: Into<String>, P: Into<String>>(name: S, pattern: P) -> Self {
+        Self::Matches {
+            name: name.into(),
+            pattern: pattern.into(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Present { name } | Self::Equals { name, .. } | Self::Matches { name, .. } => name,
+        }
+    }
+
+    /// Weight used to order candidates sharing a path: exact values are the most
+    /// specific, then patterns, then bare presence.
+    pub fn specificity(&self) -> u32 {
+        match self {
+            Self::Equals { .. } => 3,
+            Self::Matches { .. } => 2,
+            Self::Present { .. } => 1,
+        }
+    }
+
+    pub(crate) fn compile(&self) -> Result<CompiledQueryCondition, regex::Error> {
+        Ok(match self {
+            Self::Present { name } => CompiledQueryCondition::Present { name: name.clone() },
+            Self::Equals { name, value } => CompiledQueryCondition::Equals {
+                name: name.clone(),
+                value: value.clone(),
+            },
+            Self::Matches { name, pattern } => CompiledQueryCondition::Matches {
+                name: name.clone(),
+                regex: Regex::new(&format!("^(?:{})$", pattern))?,
+            },
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum CompiledQueryCondition {
+    Present { name: String },
+    Equals { name: String, value: String },
+    Matches { name: String, regex: Regex },
+}
+
+impl CompiledQueryCondition {
+    pub(crate) fn is_satisfied(&self, query: &QueryParams) -> bool {
+        match self {
+            Self::Present { name } => query.contains(name),
+            Self::Equals { name, value } => query.get(name) == Some(value.as_str()),
+            Self::Matches { name, regex } => query.get(name).is_some_and(|v| regex.is_match(v)),
+        }
+    }
+}
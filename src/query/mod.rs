@@ -0,0 +1,6 @@
+mod condition;
+mod params;
+
+pub(crate) use condition::CompiledQueryCondition;
+pub use condition::QueryCondition;
+pub use params::{QueryParams, parse_query};
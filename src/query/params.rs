@@ -0,0 +1,116 @@
+use crate::path::decode_hex_pair;
+
+/// Percent-decoded query parameters in the order they appear. Keys may repeat.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryParams {
+    pairs: Vec<(String, String)>,
+}
+
+impl QueryParams {
+    /// First value for `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Every value for `name`, in order of appearance.
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.pairs
+            .iter()
+            .filter(move |(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.pairs.iter().any(|(key, _)| key == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+}
+
+/// Parses `application/x-www-form-urlencoded` style input (`a=1&b=x+y&a=2`).
+///
+/// Decoding is lenient: `+` becomes a space, malformed escapes are kept verbatim and
+/// invalid UTF-8 is replaced rather than rejected, since a query never decides
+/// whether a path exists.
+pub fn parse_query(raw: &str) -> QueryParams {
+    let pairs = raw
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode_component(key), decode_component(value))
+        })
+        .collect();
+    QueryParams { pairs }
+}
+
+fn decode_component(input: &str) -> String {
+    let bytes = input.as_bytes();
+    if !bytes.iter().any(|b| *b == b'%' || *b == b'+') {
+        return input.to_string();
+    }
+
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut idx = 0usize;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'+' => out.push(b' '),
+            b'%' if idx + 2 < bytes.len() => {
+                match decode_hex_pair(bytes[idx + 1], bytes[idx + 2]) {
+                    Some(value) => {
+                        out.push(value);
+                        idx += 3;
+                        continue;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            byte => out.push(byte),
+        }
+        idx += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_repeated_keys_in_order() {
+        let params = parse_query("tag=a&q=x&tag=b");
+        assert_eq!(params.get("tag"), Some("a"));
+        assert_eq!(params.get_all("tag").collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(params.len(), 3);
+    }
+
+    #[test]
+    fn decodes_percent_escapes_and_plus() {
+        let params = parse_query("q=a%2Fb+c&caf%C3%A9=1");
+        assert_eq!(params.get("q"), Some("a/b c"));
+        assert_eq!(params.get("café"), Some("1"));
+    }
+
+    #[test]
+    fn keeps_malformed_escapes_and_bare_keys() {
+        let params = parse_query("bad=%zz%4&flag&&");
+        assert_eq!(params.get("bad"), Some("%zz%4"));
+        assert!(params.contains("flag"));
+        assert_eq!(params.get("flag"), Some(""));
+    }
+}
@@ -26,6 +26,95 @@ impl RadixTree {
         self.insert_parsed(method, parsed_segments)
     }
 
+    /// Returns the error [`insert`](Self::insert) would report for `path` without
+    /// changing the tree. `Ok` means the route would take a fresh slot; the route
+    /// limit is left to the caller.
+    pub fn check_insert(&self, method: HttpMethod, path: &str) -> RadixResult<()> {
+        if self.root_node.is_sealed() {
+            return Err(RadixError::TreeSealed {
+                operation: "check_insert",
+                path: Some(path.to_string()),
+            });
+        }
+
+        let (_outcome, parsed_segments, _) = preprocess_and_parse(path, &self.preprocessor)?;
+        let total_segments = parsed_segments.len();
+        let mut current = Some(&self.root_node);
+
+        for (i, pat) in parsed_segments.iter().enumerate() {
+            let is_wildcard =
+                matches!(pat.parts.as_slice(), [SegmentPart::Literal(s)] if s.as_str() == "*");
+            if is_wildcard {
+                if i != total_segments - 1 {
+                    return Err(RadixError::WildcardMustBeTerminal {
+                        segment_index: i,
+                        total_segments,
+                    });
+                }
+                let method_idx = method as usize;
+                if let Some(node) = current
+                    && node.wildcard_routes[method_idx] != 0
+                {
+                    return Err(RadixError::DuplicateWildcardRoute {
+                        method,
+                        existing_key: node.wildcard_routes[method_idx] - 1,
+                    });
+                }
+                return Ok(());
+            }
+
+            // Past the last existing node only the shape of the path can fail.
+            let Some(node) = current else {
+                continue;
+            };
+            current = match static_key(pat) {
+                Some(key) => node.static_child(&key),
+                None => {
+                    if !node
+                        .patterns
+                        .iter()
+                        .all(|exist| pattern_compatible_policy(exist, pat))
+                    {
+                        return Err(RadixError::ParamNameConflict {
+                            pattern: format!("{:?}", pat),
+                        });
+                    }
+                    node.patterns
+                        .iter()
+                        .position(|exist| exist == pat)
+                        .map(|idx| node.pattern_nodes[idx].as_ref())
+                }
+            };
+        }
+
+        let method_idx = method as usize;
+        if let Some(node) = current
+            && node.routes[method_idx] != 0
+        {
+            return Err(RadixError::DuplicateRoute {
+                method,
+                existing_key: node.routes[method_idx] - 1,
+            });
+        }
+        Ok(())
+    }
+
+    /// Parameter names declared by `path`, in segment order, after preprocessing.
+    pub fn param_names(&self, path: &str) -> RadixResult<Vec<String>> {
+        let (_outcome, parsed_segments, _) = preprocess_and_parse(path, &self.preprocessor)?;
+        let mut names = Vec::new();
+        for pat in parsed_segments.iter() {
+            for part in pat.parts.iter() {
+                match part {
+                    SegmentPart::Param { name, .. } => names.push(name.clone()),
+                    SegmentPart::Literal(lit) if lit == "*" => names.push("*".to_string()),
+                    SegmentPart::Literal(_) => {}
+                }
+            }
+        }
+        Ok(names)
+    }
+
     pub(super) fn insert_parsed(
         &mut self,
         method: HttpMethod,
@@ -160,6 +249,24 @@ impl RadixTree {
     }
 }
 
+/// The static child key `insert_parsed` descends through for `pat`, if any.
+fn static_key(pat: &SegmentPattern) -> Option<String> {
+    match pat.parts.as_slice() {
+        [SegmentPart::Literal(lit)] => Some(lit.clone()),
+        [_] => None,
+        parts if pattern_is_pure_static(pat, "") => Some(
+            parts
+                .iter()
+                .map(|p| match p {
+                    SegmentPart::Literal(s) => s.as_str(),
+                    _ => "",
+                })
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
 fn sort_static_children(node: &mut RadixTreeNode, interner: &Interner) {
     let len = node.static_keys.len();
     if len == node.static_vals.len() && len > 1 {
@@ -119,6 +119,14 @@ impl RadixTreeNode {
         self.method_mask = mask;
     }
 
+    /// Read-only counterpart of `descend_static_mut_with_alloc` for unsealed nodes.
+    pub(super) fn static_child(&self, key: &str) -> Option<&RadixTreeNode> {
+        match self.static_keys.iter().position(|k| k.as_ref() == key) {
+            Some(pos) => Some(self.static_vals[pos].as_ref()),
+            None => self.static_children.get(key).map(NodeBox::as_ref),
+        }
+    }
+
     /// Same as `descend_static_mut` but uses provided allocator for child creation.
     pub(super) fn descend_static_mut_with_alloc<F>(
         &mut self,
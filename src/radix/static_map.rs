@@ -20,7 +20,8 @@ pub(super) fn collect_static(
             } else {
                 buf.to_owned().into_boxed_str()
             };
-            maps[i].insert(key, rk);
+            // node keys are +1 encoded; the full map stores the decoded key
+            maps[i].insert(key, rk - 1);
         }
     }
     if !n.static_keys.is_empty() && n.static_vals_idx.len() == n.static_keys.len() {
@@ -11,6 +11,7 @@ use crate::router::{Preprocessor, RouterOptions};
 use crate::tools::Interner;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::sync::atomic::AtomicU16;
 
 pub const HTTP_METHOD_COUNT: usize = 7;
 
@@ -44,11 +45,17 @@ pub struct RadixTree {
     pub(crate) constraint_regex_cache: FastHashMap<Box<str>, Arc<Regex>>,
     pub enable_root_level_pruning: bool,
     pub enable_static_route_full_mapping: bool,
-    pub(crate) next_route_key: std::sync::atomic::AtomicU16,
+    pub(crate) next_route_key: Arc<AtomicU16>,
 }
 
 impl RadixTree {
     pub fn new(configuration: RouterOptions) -> Self {
+        Self::with_route_key_counter(configuration, Arc::new(AtomicU16::new(0)))
+    }
+
+    /// Creates a tree that draws route keys from `counter`, so several trees can
+    /// hand out keys from one shared key space.
+    pub fn with_route_key_counter(configuration: RouterOptions, counter: Arc<AtomicU16>) -> Self {
         let enable_root_level_pruning = false;
         let enable_static_route_full_mapping = false;
         let preprocessor = Preprocessor::new(configuration.clone());
@@ -69,7 +76,7 @@ impl RadixTree {
             constraint_regex_cache: FastHashMap::new(),
             enable_root_level_pruning,
             enable_static_route_full_mapping,
-            next_route_key: std::sync::atomic::AtomicU16::new(0),
+            next_route_key: counter,
         }
     }
 
@@ -115,6 +122,10 @@ impl RadixTree {
         }
     }
 
+    pub fn route_key_counter(&self) -> Arc<AtomicU16> {
+        self.next_route_key.clone()
+    }
+
     pub fn finalize(&mut self) {
         super::builder::finalize(self);
     }
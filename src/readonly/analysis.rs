@@ -0,0 +1,346 @@
+use super::snapshot::{ReadOnlyNode, RouterReadOnly};
+use crate::enums::HttpMethod;
+use crate::matcher::{find_route, with_param_buffer};
+use crate::pattern::{SegmentPart, SegmentPattern, parse_segment, pattern_score};
+use crate::radix::HTTP_METHOD_COUNT;
+use crate::registry::RouteRecord;
+use hashbrown::{HashMap as FastHashMap, HashSet as FastHashSet};
+use regex::Regex;
+use std::fmt;
+
+/// Parameter values tried when building example paths; each parameter uses the
+/// ones its constraint accepts.
+const SAMPLE_VALUES: &[&str] = &[
+    "1",
+    "42",
+    "2024",
+    "x",
+    "abc",
+    "a1",
+    "a-b",
+    "a_b",
+    "a.b",
+    "-1",
+    "3.14",
+    "ABC",
+    "00000000-0000-0000-0000-000000000000",
+];
+const WILDCARD_SAMPLES: &[&str] = &["x", "x/y"];
+const SAMPLES_PER_ROUTE: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FindingKind {
+    /// Every example path of the route resolves to another route.
+    Unreachable,
+    /// Some example paths resolve to another route that is exactly as specific, so
+    /// only registration order decides between them.
+    Ambiguous,
+    /// Some example paths resolve to a more specific route; the route still
+    /// matches elsewhere.
+    Overlapping,
+}
+
+/// A route that loses some or all of its paths to another route of the same
+/// method and scope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteFinding {
+    pub kind: FindingKind,
+    pub method: HttpMethod,
+    pub route: u16,
+    pub path: String,
+    pub shadowed_by: u16,
+    pub shadowed_by_path: String,
+    /// Normalized request path, within the route's host or version scope, that
+    /// resolves to `shadowed_by`.
+    pub example: String,
+}
+
+impl RouteFinding {
+    /// Unreachable and ambiguous routes are conflicts; overlaps are advisory.
+    pub fn is_conflict(&self) -> bool {
+        !matches!(self.kind, FindingKind::Overlapping)
+    }
+}
+
+impl fmt::Display for RouteFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            FindingKind::Unreachable => "unreachable",
+            FindingKind::Ambiguous => "ambiguous",
+            FindingKind::Overlapping => "overlapping",
+        };
+        write!(
+            f,
+            "{} '{}' (key {}) is {kind}: '{}' resolves to '{}' (key {})",
+            self.method.as_str(),
+            self.path,
+            self.route,
+            self.example,
+            self.shadowed_by_path,
+            self.shadowed_by
+        )
+    }
+}
+
+/// Outcome of [`RouterReadOnly::analyze`], ordered by route key.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RouteAnalysis {
+    findings: Vec<RouteFinding>,
+}
+
+impl RouteAnalysis {
+    pub fn findings(&self) -> &[RouteFinding] {
+        &self.findings
+    }
+
+    pub fn conflicts(&self) -> impl Iterator<Item = &RouteFinding> {
+        self.findings.iter().filter(|finding| finding.is_conflict())
+    }
+
+    pub fn has_conflicts(&self) -> bool {
+        self.conflicts().next().is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    pub(crate) fn remap(mut self, keys: &FastHashMap<u16, u16>) -> Self {
+        for finding in &mut self.findings {
+            finding.route = keys.get(&finding.route).copied().unwrap_or(finding.route);
+            finding.shadowed_by = keys
+                .get(&finding.shadowed_by)
+                .copied()
+                .unwrap_or(finding.shadowed_by);
+        }
+        self
+    }
+}
+
+struct ScopeTree<'a> {
+    static_maps: &'a [FastHashMap<Box<str>, u16>; HTTP_METHOD_COUNT],
+    root: &'a ReadOnlyNode,
+}
+
+impl RouterReadOnly {
+    /// Looks for routes shadowed by others by resolving example paths built from
+    /// each route's pattern. Only routes registered through this snapshot's route
+    /// table are checked, so snapshots loaded from an image report nothing.
+    pub fn analyze(&self) -> RouteAnalysis {
+        let slots = self.variant_slots();
+        let by_key: FastHashMap<u16, &RouteRecord> = self
+            .routes
+            .iter()
+            .map(|record| (record.key, record))
+            .collect();
+
+        let mut seen = FastHashSet::new();
+        let mut findings = Vec::new();
+        for record in self.routes.iter() {
+            let slot = slots.get(&record.key).copied().unwrap_or(record.key);
+            let Some(scope) = self.scope_tree(record) else {
+                continue;
+            };
+            let scope_id = (
+                record.host.clone(),
+                record.options.version,
+                record.method,
+                slot,
+            );
+            if !seen.insert(scope_id) {
+                continue;
+            }
+
+            let mut reached = false;
+            let mut lost = None;
+            for example in self.example_paths(&record.normalized) {
+                match self.lookup(&scope, record.method, &example) {
+                    Some(key) if key == slot => reached = true,
+                    Some(key) => {
+                        lost.get_or_insert((key, example));
+                    }
+                    None => {}
+                }
+            }
+
+            let Some((winner, example)) = lost else {
+                continue;
+            };
+            let winner_path = by_key
+                .get(&winner)
+                .map(|other| other.normalized.as_str())
+                .unwrap_or_default();
+            let kind = if !reached {
+                FindingKind::Unreachable
+            } else if equally_specific(&record.normalized, winner_path) {
+                FindingKind::Ambiguous
+            } else {
+                FindingKind::Overlapping
+            };
+            findings.push(RouteFinding {
+                kind,
+                method: record.method,
+                route: slot,
+                path: record.path.clone(),
+                shadowed_by: winner,
+                shadowed_by_path: by_key
+                    .get(&winner)
+                    .map(|other| other.path.clone())
+                    .unwrap_or_default(),
+                example,
+            });
+        }
+        findings.sort_by_key(|finding| finding.route);
+        RouteAnalysis { findings }
+    }
+
+    fn variant_slots(&self) -> FastHashMap<u16, u16> {
+        let mut slots = FastHashMap::new();
+        if let Some(variants) = self.variants.as_deref() {
+            for (slot, group) in variants.groups() {
+                for variant in group {
+                    slots.insert(variant.source().key, slot);
+                }
+            }
+        }
+        slots
+    }
+
+    fn scope_tree(&self, record: &RouteRecord) -> Option<ScopeTree<'_>> {
+        if let Some(host) = record.host.as_deref() {
+            let scope = self
+                .hosts
+                .as_deref()?
+                .scopes()
+                .iter()
+                .find(|scope| scope.host() == host)?;
+            return Some(ScopeTree {
+                static_maps: &scope.static_maps,
+                root: &scope.root,
+            });
+        }
+        match record.options.version {
+            Some(version) => {
+                let set = self
+                    .versions
+                    .as_deref()?
+                    .sets
+                    .iter()
+                    .find(|set| set.version() == version)?;
+                Some(ScopeTree {
+                    static_maps: &set.static_maps,
+                    root: &set.root,
+                })
+            }
+            None => Some(ScopeTree {
+                static_maps: &self.static_maps,
+                root: &self.root,
+            }),
+        }
+    }
+
+    fn lookup(&self, scope: &ScopeTree<'_>, method: HttpMethod, path: &str) -> Option<u16> {
+        if let Some(&key) = scope.static_maps[method as usize].get(path) {
+            return Some(key);
+        }
+        with_param_buffer(|buf| {
+            find_route(scope.root, method, path, buf, &self.param_pattern_default)
+        })
+        .map(|(key, _)| key)
+    }
+
+    /// Up to [`SAMPLES_PER_ROUTE`] distinct paths the pattern should accept. Empty
+    /// when some parameter accepts none of the sample values.
+    fn example_paths(&self, normalized: &str) -> Vec<String> {
+        let mut choices: Vec<Vec<String>> = Vec::new();
+        for segment in normalized.split('/').filter(|segment| !segment.is_empty()) {
+            if segment == "*" {
+                choices.push(WILDCARD_SAMPLES.iter().map(|s| s.to_string()).collect());
+                continue;
+            }
+            let Ok(pattern) = parse_segment(segment) else {
+                return Vec::new();
+            };
+            let values = segment_samples(&pattern, &self.param_pattern_default);
+            if values.is_empty() {
+                return Vec::new();
+            }
+            choices.push(values);
+        }
+
+        let mut out: Vec<String> = Vec::new();
+        for round in 0..SAMPLES_PER_ROUTE {
+            let mut path = String::new();
+            for values in &choices {
+                path.push('/');
+                path.push_str(&values[round % values.len()]);
+            }
+            if path.is_empty() {
+                path.push('/');
+            }
+            if !out.contains(&path) {
+                out.push(path);
+            }
+        }
+        out
+    }
+}
+
+fn segment_samples(pattern: &SegmentPattern, default_param_pattern: &Regex) -> Vec<String> {
+    let mut per_part: Vec<Vec<&str>> = Vec::with_capacity(pattern.parts.len());
+    for part in &pattern.parts {
+        match part {
+            SegmentPart::Literal(text) => per_part.push(vec![text.as_str()]),
+            SegmentPart::Param { constraint, .. } => {
+                let regex = constraint
+                    .as_ref()
+                    .and_then(|constraint| constraint.regex().map(|re| re.as_ref()))
+                    .unwrap_or(default_param_pattern);
+                let accepted: Vec<&str> = SAMPLE_VALUES
+                    .iter()
+                    .copied()
+                    .filter(|value| regex.is_match(value))
+                    .collect();
+                if accepted.is_empty() {
+                    return Vec::new();
+                }
+                per_part.push(accepted);
+            }
+        }
+    }
+
+    let rounds = per_part.iter().map(Vec::len).max().unwrap_or(1);
+    (0..rounds)
+        .map(|round| {
+            per_part
+                .iter()
+                .map(|values| values[round % values.len()])
+                .collect()
+        })
+        .collect()
+}
+
+/// True when the two patterns first differ at a segment where both are parameter
+/// patterns of equal score, leaving registration order as the only tie-breaker.
+fn equally_specific(a: &str, b: &str) -> bool {
+    let diverging = a
+        .split('/')
+        .zip(b.split('/'))
+        .find(|(left, right)| left != right);
+    let Some((left, right)) = diverging else {
+        return false;
+    };
+    match (parse_segment(left), parse_segment(right)) {
+        (Ok(left), Ok(right)) => {
+            has_param(&left) && has_param(&right) && pattern_score(&left) == pattern_score(&right)
+        }
+        _ => false,
+    }
+}
+
+fn has_param(pattern: &SegmentPattern) -> bool {
+    pattern
+        .parts
+        .iter()
+        .any(|part| matches!(part, SegmentPart::Param { .. }))
+}
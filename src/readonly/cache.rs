@@ -1,102 +1,730 @@
 use crate::enums::HttpMethod;
+use crate::pattern::{SegmentPart, parse_segment};
+use crate::registry::RouteRecord;
+use crate::router::{
+    CacheAdmission, CacheOptions, CacheScope, DefaultCachePolicy, RouteCachePolicy,
+};
 use crate::types::RouteMatch;
-use hashbrown::HashMap as FastHashMap;
-use std::collections::VecDeque;
-use std::sync::atomic::{AtomicU64, Ordering};
-
-pub const DEFAULT_CACHE_CAPACITY: usize = 256;
+use hashbrown::hash_map::DefaultHashBuilder;
+use hashbrown::{Equivalent, HashMap as FastHashMap, HashSet as FastHashSet};
+use parking_lot::{Mutex, RwLock};
+use std::cell::RefCell;
+use std::hash::{BuildHasher, Hash};
+use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
 
+/// Slot lookups keyed by method and normalized path, sharded by key hash. Each
+/// shard evicts with CLOCK: a hit only sets a reference bit under the shard's read
+/// lock, and inserts sweep the hand past referenced entries. Both are O(1)
+/// amortized.
 #[derive(Debug)]
 pub struct RouteCache {
-    capacity: usize,
-    map: FastHashMap<RouteCacheKey, RouteCacheValue>,
-    order: VecDeque<RouteCacheKey>,
+    shards: Box<[RwLock<CacheShard>]>,
+    hasher: DefaultHashBuilder,
+    negative: bool,
+    admission: CacheAdmission,
+}
+
+/// Method and normalized path of cached matches.
+type HotKeys = Vec<(HttpMethod, String)>;
+
+/// What [`RouteCache::insert`] did with an entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheInsert {
+    /// Stored in a free slot, or over the entry's previous value.
+    Stored,
+    /// Stored in place of an evicted entry.
+    Evicted,
+    /// Turned away by the admission policy.
+    Rejected,
+    /// Not cacheable under the cache's settings.
+    Skipped,
+}
+
+/// A cached lookup: the matched slot, or a path known to match no route.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CachedLookup {
+    Found(Arc<RouteMatch>),
+    /// `allowed` masks the methods the path resolves under, one bit per
+    /// `method as usize`, so a repeated miss can be told apart from a 405 without
+    /// another walk. Zero unless the snapshot tracks lookups.
+    NotFound {
+        allowed: u32,
+    },
 }
 
 impl RouteCache {
-    pub fn new(capacity: usize) -> Self {
-        let cap = capacity.max(1);
+    /// Expects validated options; the capacity is rounded up to fill every shard.
+    pub fn new(options: &CacheOptions) -> Self {
+        let shard_count = options.shards.max(1);
+        let per_shard = options.capacity.div_ceil(shard_count).max(1);
+        let sketched = options.admission == CacheAdmission::Frequency;
         Self {
-            capacity: cap,
-            map: FastHashMap::with_capacity(cap),
-            order: VecDeque::with_capacity(cap),
+            shards: (0..shard_count)
+                .map(|_| RwLock::new(CacheShard::new(per_shard, sketched)))
+                .collect(),
+            hasher: DefaultHashBuilder::default(),
+            negative: options.negative,
+            admission: options.admission,
         }
     }
 
-    pub fn peek(&self, key: &RouteCacheKey) -> Option<RouteMatch> {
-        self.map.get(key).map(|value| value.result.clone())
+    /// Cached result for the key, marking it recently used. Under frequency
+    /// admission every call also counts towards the key's popularity, hit or not.
+    pub fn get(&self, method: HttpMethod, path: &str) -> Option<CachedLookup> {
+        let query = RouteCacheQuery { method, path };
+        let hash = self.hasher.hash_one(&query);
+        let shard = self.shard(hash).read();
+        if let Some(sketch) = &shard.sketch {
+            sketch.increment(hash);
+        }
+        let slot = shard.slot(&query)?;
+        slot.referenced.store(true, Ordering::Relaxed);
+        Some(slot.value.clone())
+    }
+
+    /// Cached result for the key, leaving its eviction state untouched.
+    pub fn peek(&self, method: HttpMethod, path: &str) -> Option<CachedLookup> {
+        let query = RouteCacheQuery { method, path };
+        let hash = self.hasher.hash_one(&query);
+        let shard = self.shard(hash).read();
+        shard.slot(&query).map(|slot| slot.value.clone())
     }
 
-    pub fn touch(&mut self, key: &RouteCacheKey) {
-        if self.map.contains_key(key) {
-            self.promote(key);
+    /// Stores a lookup result. `NotFound` is skipped unless negative caching is on.
+    pub fn insert(&self, method: HttpMethod, path: &str, value: CachedLookup) -> CacheInsert {
+        if matches!(value, CachedLookup::NotFound { .. }) && !self.negative {
+            return CacheInsert::Skipped;
         }
+        let query = RouteCacheQuery { method, path };
+        let hash = self.hasher.hash_one(&query);
+        self.shard(hash)
+            .write()
+            .insert(&query, hash, value, self.admission)
     }
 
-    pub fn insert(&mut self, key: RouteCacheKey, result: RouteMatch) {
-        if self.map.contains_key(&key) {
-            if let Some(entry) = self.map.get_mut(&key) {
-                entry.result = result;
+    /// [`get`](Self::get) for a batch of keys, taking each shard's read lock once.
+    /// Results are in input order.
+    pub fn get_many(&self, keys: &[(HttpMethod, &str)]) -> Vec<Option<CachedLookup>> {
+        let mut out = vec![None; keys.len()];
+        self.for_each_shard(keys, |shard, batch| {
+            let shard = shard.read();
+            for (idx, hash, query) in batch {
+                if let Some(sketch) = &shard.sketch {
+                    sketch.increment(hash);
+                }
+                if let Some(slot) = shard.slot(&query) {
+                    slot.referenced.store(true, Ordering::Relaxed);
+                    out[idx] = Some(slot.value.clone());
+                }
             }
-            self.promote(&key);
-            return;
+        });
+        out
+    }
+
+    /// [`insert`](Self::insert) for a batch of entries, taking each shard's write
+    /// lock once. Outcomes are in input order.
+    pub fn insert_many(&self, entries: Vec<(HttpMethod, &str, CachedLookup)>) -> Vec<CacheInsert> {
+        let mut out = vec![CacheInsert::Skipped; entries.len()];
+        let mut keys = Vec::with_capacity(entries.len());
+        let mut values = Vec::with_capacity(entries.len());
+        for (idx, (method, path, value)) in entries.into_iter().enumerate() {
+            if matches!(value, CachedLookup::NotFound { .. }) && !self.negative {
+                continue;
+            }
+            keys.push((method, path));
+            values.push((idx, Some(value)));
+        }
+        self.for_each_shard(&keys, |shard, batch| {
+            let mut shard = shard.write();
+            for (key, hash, query) in batch {
+                let (idx, value) = &mut values[key];
+                if let Some(value) = value.take() {
+                    out[*idx] = shard.insert(&query, hash, value, self.admission);
+                }
+            }
+        });
+        out
+    }
+
+    /// Keys of cached matches, recently used ones first; cached not-found results
+    /// are left out. Paths are in their normalized form.
+    pub fn hot_keys(&self) -> Vec<(HttpMethod, String)> {
+        let (mut recent, mut idle) = self.hot_partition();
+        recent.append(&mut idle);
+        recent
+    }
+
+    /// Keys of cached matches, split into recently used and idle ones.
+    fn hot_partition(&self) -> (HotKeys, HotKeys) {
+        let mut recent = Vec::new();
+        let mut idle = Vec::new();
+        for shard in self.shards.iter() {
+            let shard = shard.read();
+            for slot in shard.slots.iter() {
+                if matches!(slot.value, CachedLookup::NotFound { .. }) {
+                    continue;
+                }
+                let key = (slot.key.method, slot.key.path.clone());
+                if slot.referenced.load(Ordering::Relaxed) {
+                    recent.push(key);
+                } else {
+                    idle.push(key);
+                }
+            }
+        }
+        (recent, idle)
+    }
+
+    /// Number of cached entries across all shards.
+    pub fn entries(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.read().slots.len())
+            .sum()
+    }
+
+    fn shard(&self, hash: u64) -> &RwLock<CacheShard> {
+        &self.shards[self.shard_index(hash)]
+    }
+
+    fn shard_index(&self, hash: u64) -> usize {
+        hash as usize % self.shards.len()
+    }
+
+    /// Groups `keys` by shard and calls `visit` once per shard that owns any of
+    /// them, with `(input index, hash, query)` for each of its keys.
+    fn for_each_shard<'k>(
+        &self,
+        keys: &[(HttpMethod, &'k str)],
+        mut visit: impl FnMut(&RwLock<CacheShard>, Vec<(usize, u64, RouteCacheQuery<'k>)>),
+    ) {
+        let mut queries: Vec<(usize, u64, RouteCacheQuery<'k>)> = keys
+            .iter()
+            .enumerate()
+            .map(|(idx, &(method, path))| {
+                let query = RouteCacheQuery { method, path };
+                (idx, self.hasher.hash_one(&query), query)
+            })
+            .collect();
+        queries.sort_by_key(|(_, hash, _)| self.shard_index(*hash));
+        while let Some(&(_, hash, _)) = queries.first() {
+            let shard = self.shard_index(hash);
+            let split = queries
+                .iter()
+                .position(|(_, hash, _)| self.shard_index(*hash) != shard)
+                .unwrap_or(queries.len());
+            let rest = queries.split_off(split);
+            visit(&self.shards[shard], std::mem::replace(&mut queries, rest));
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CacheShard {
+    capacity: usize,
+    index: FastHashMap<RouteCacheKey, usize>,
+    slots: Vec<CacheSlot>,
+    hand: usize,
+    sketch: Option<FrequencySketch>,
+}
+
+#[derive(Debug)]
+struct CacheSlot {
+    key: RouteCacheKey,
+    hash: u64,
+    value: CachedLookup,
+    referenced: AtomicBool,
+}
+
+impl CacheShard {
+    fn new(capacity: usize, sketched: bool) -> Self {
+        Self {
+            capacity,
+            index: FastHashMap::with_capacity(capacity),
+            slots: Vec::with_capacity(capacity),
+            hand: 0,
+            sketch: sketched.then(|| FrequencySketch::new(capacity)),
+        }
+    }
+
+    fn slot(&self, query: &RouteCacheQuery<'_>) -> Option<&CacheSlot> {
+        self.index.get(query).map(|&idx| &self.slots[idx])
+    }
+
+    fn insert(
+        &mut self,
+        query: &RouteCacheQuery<'_>,
+        hash: u64,
+        value: CachedLookup,
+        admission: CacheAdmission,
+    ) -> CacheInsert {
+        if let Some(sketch) = &mut self.sketch {
+            sketch.age_if_due();
+        }
+        if let Some(&idx) = self.index.get(query) {
+            self.slots[idx].value = value;
+            return CacheInsert::Stored;
+        }
+
+        let key = query.to_key();
+        let slot = CacheSlot {
+            key: key.clone(),
+            hash,
+            value,
+            referenced: AtomicBool::new(false),
+        };
+        if self.slots.len() < self.capacity {
+            self.index.insert(key, self.slots.len());
+            self.slots.push(slot);
+            return CacheInsert::Stored;
         }
 
-        if self.order.len() == self.capacity
-            && let Some(oldest) = self.order.pop_back()
+        let victim = self.sweep();
+        if let (CacheAdmission::Frequency, Some(sketch)) = (admission, &self.sketch)
+            && sketch.estimate(hash) <= sketch.estimate(self.slots[victim].hash)
         {
-            self.map.remove(&oldest);
+            return CacheInsert::Rejected;
         }
+        self.index.remove(&self.slots[victim].key);
+        self.index.insert(key, victim);
+        self.slots[victim] = slot;
+        CacheInsert::Evicted
+    }
 
-        self.order.push_front(key.clone());
-        self.map.insert(key, RouteCacheValue { result });
+    /// Advances the hand to the first unreferenced slot, clearing reference bits
+    /// on the way. Terminates within two laps.
+    fn sweep(&mut self) -> usize {
+        loop {
+            let idx = self.hand;
+            self.hand = (self.hand + 1) % self.slots.len();
+            if !std::mem::take(self.slots[idx].referenced.get_mut()) {
+                return idx;
+            }
+        }
     }
+}
+
+const SKETCH_DEPTH: usize = 4;
+const SKETCH_SEEDS: [u64; SKETCH_DEPTH] = [
+    0x9E37_79B9_7F4A_7C15,
+    0xC2B2_AE3D_27D4_EB4F,
+    0x1656_67B1_9E37_79F9,
+    0x85EB_CA77_C2B2_AE63,
+];
+const SKETCH_COUNTER_MAX: u8 = 15;
 
-    fn promote(&mut self, key: &RouteCacheKey) {
-        if let Some(pos) = self.order.iter().position(|existing| existing == key) {
-            self.order.remove(pos);
+/// Count-min sketch of lookup frequencies with 4-bit saturating counters. Counts
+/// are halved every `sample` increments so popularity decays over time.
+#[derive(Debug)]
+struct FrequencySketch {
+    counters: Box<[AtomicU8]>,
+    mask: usize,
+    additions: AtomicUsize,
+    sample: usize,
+}
+
+impl FrequencySketch {
+    fn new(capacity: usize) -> Self {
+        let width = capacity.next_power_of_two().max(64);
+        Self {
+            counters: (0..width * SKETCH_DEPTH)
+                .map(|_| AtomicU8::new(0))
+                .collect(),
+            mask: width - 1,
+            additions: AtomicUsize::new(0),
+            sample: capacity.saturating_mul(10).max(64),
         }
-        self.order.push_front(key.clone());
     }
+
+    fn counter(&self, hash: u64, row: usize) -> &AtomicU8 {
+        let mixed = (hash ^ SKETCH_SEEDS[row]).wrapping_mul(SKETCH_SEEDS[0]);
+        let column = (mixed >> 32) as usize & self.mask;
+        &self.counters[row * (self.mask + 1) + column]
+    }
+
+    fn increment(&self, hash: u64) {
+        for row in 0..SKETCH_DEPTH {
+            let _ = self.counter(hash, row).fetch_update(
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+                |count| (count < SKETCH_COUNTER_MAX).then_some(count + 1),
+            );
+        }
+        self.additions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn estimate(&self, hash: u64) -> u8 {
+        (0..SKETCH_DEPTH)
+            .map(|row| self.counter(hash, row).load(Ordering::Relaxed))
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn age_if_due(&mut self) {
+        let additions = self.additions.get_mut();
+        if *additions < self.sample {
+            return;
+        }
+        *additions /= 2;
+        for counter in self.counters.iter_mut() {
+            *counter.get_mut() /= 2;
+        }
+    }
+}
+
+/// The cache a snapshot reads through: one shared cache, or a private one per
+/// thread. Clones of a snapshot share the store and so the same per-thread caches.
+#[derive(Debug, Clone)]
+pub(crate) enum CacheStore {
+    Shared(Arc<RouteCache>),
+    PerThread(Arc<ThreadCaches>),
+}
+
+impl CacheStore {
+    pub(crate) fn new(options: &CacheOptions) -> Self {
+        match options.scope {
+            CacheScope::Shared => Self::Shared(Arc::new(RouteCache::new(options))),
+            CacheScope::PerThread => Self::PerThread(Arc::new(ThreadCaches::new(CacheOptions {
+                shards: 1,
+                ..*options
+            }))),
+        }
+    }
+
+    /// The cache the calling thread should use.
+    pub(crate) fn local(&self) -> CacheRef<'_> {
+        match self {
+            Self::Shared(cache) => CacheRef::Shared(cache),
+            Self::PerThread(owner) => CacheRef::Local(ThreadCaches::local(owner)),
+        }
+    }
+
+    /// Number of cached entries, summed over every live thread's cache.
+    pub(crate) fn entries(&self) -> usize {
+        match self {
+            Self::Shared(cache) => cache.entries(),
+            Self::PerThread(owner) => owner.live().iter().map(|cache| cache.entries()).sum(),
+        }
+    }
+
+    /// Keys of cached matches across every live thread's cache, recently used ones
+    /// first and each key once.
+    pub(crate) fn hot_keys(&self) -> Vec<(HttpMethod, String)> {
+        match self {
+            Self::Shared(cache) => cache.hot_keys(),
+            Self::PerThread(owner) => {
+                let mut recent = Vec::new();
+                let mut idle = Vec::new();
+                for cache in owner.live() {
+                    let (mut hot, mut cold) = cache.hot_partition();
+                    recent.append(&mut hot);
+                    idle.append(&mut cold);
+                }
+                let mut seen = FastHashSet::new();
+                recent.append(&mut idle);
+                recent.retain(|key| seen.insert(key.clone()));
+                recent
+            }
+        }
+    }
+}
+
+pub(crate) enum CacheRef<'a> {
+    Shared(&'a RouteCache),
+    Local(Arc<RouteCache>),
+}
+
+impl Deref for CacheRef<'_> {
+    type Target = RouteCache;
+
+    fn deref(&self) -> &RouteCache {
+        match self {
+            Self::Shared(cache) => cache,
+            Self::Local(cache) => cache,
+        }
+    }
+}
+
+/// The per-thread caches of one snapshot. Each thread owns its cache through
+/// `LOCAL_CACHES`; the owner only keeps weak handles, so a thread's cache is freed
+/// when the thread exits and its handle is pruned when the next thread registers
+/// or the caches are read.
+#[derive(Debug)]
+pub(crate) struct ThreadCaches {
+    options: CacheOptions,
+    threads: Mutex<Vec<Weak<RouteCache>>>,
+}
+
+/// A thread's cache for one [`ThreadCaches`]. The weak handle keeps the owner's
+/// allocation, and so its address, reserved until the entry is pruned.
+struct LocalCache {
+    owner: Weak<ThreadCaches>,
+    cache: Arc<RouteCache>,
+}
+
+thread_local! {
+    static LOCAL_CACHES: RefCell<Vec<LocalCache>> = const { RefCell::new(Vec::new()) };
+}
+
+impl ThreadCaches {
+    pub(crate) fn new(options: CacheOptions) -> Self {
+        Self {
+            options,
+            threads: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns this thread's cache for `owner`, creating it on first use. Every
+    /// call releases this thread's caches of dropped snapshots, and registering a
+    /// new cache prunes the handles of exited threads.
+    fn local(owner: &Arc<ThreadCaches>) -> Arc<RouteCache> {
+        LOCAL_CACHES.with(|cell| {
+            let mut caches = cell.borrow_mut();
+            caches.retain(|local| local.owner.strong_count() > 0);
+            if let Some(local) = caches
+                .iter()
+                .find(|local| std::ptr::eq(local.owner.as_ptr(), Arc::as_ptr(owner)))
+            {
+                return local.cache.clone();
+            }
+            let cache = Arc::new(RouteCache::new(&owner.options));
+            let mut threads = owner.threads.lock();
+            threads.retain(|cache| cache.strong_count() > 0);
+            threads.push(Arc::downgrade(&cache));
+            drop(threads);
+            caches.push(LocalCache {
+                owner: Arc::downgrade(owner),
+                cache: cache.clone(),
+            });
+            cache
+        })
+    }
+
+    /// Caches of the threads still running, pruning those of exited threads.
+    fn live(&self) -> Vec<Arc<RouteCache>> {
+        let mut threads = self.threads.lock();
+        let mut live = Vec::with_capacity(threads.len());
+        threads.retain(|cache| match cache.upgrade() {
+            Some(cache) => {
+                live.push(cache);
+                true
+            }
+            None => false,
+        });
+        live
+    }
+}
+
+/// Marks the route keys whose lookups must not be cached, from each route's
+/// [`RouteCachePolicy`] and the router's default. `None` when every route may be.
+pub(crate) fn uncached_routes(
+    records: &[RouteRecord],
+    default: DefaultCachePolicy,
+) -> Option<Arc<[bool]>> {
+    let len = records.iter().map(|record| record.key as usize + 1).max()?;
+    let mut uncached = vec![false; len];
+    for record in records.iter() {
+        uncached[record.key as usize] = match record.options.cache {
+            RouteCachePolicy::Always => false,
+            RouteCachePolicy::Never => true,
+            RouteCachePolicy::Default => match default {
+                DefaultCachePolicy::All => false,
+                DefaultCachePolicy::LowCardinality => !is_low_cardinality(&record.normalized),
+            },
+        };
+    }
+    uncached.contains(&true).then(|| uncached.into())
+}
+
+/// Static paths, and paths whose parameters only accept a list of literals.
+fn is_low_cardinality(normalized: &str) -> bool {
+    normalized.split('/').all(|segment| {
+        if segment.starts_with('*') {
+            return false;
+        }
+        if !segment.starts_with(':') {
+            return true;
+        }
+        parse_segment(segment).is_ok_and(|pattern| {
+            pattern.parts.iter().all(|part| match part {
+                SegmentPart::Literal(_) => true,
+                SegmentPart::Param { constraint, .. } => constraint
+                    .as_ref()
+                    .is_some_and(|constraint| is_enumeration(constraint.raw())),
+            })
+        })
+    })
+}
+
+/// `en|de|fr`, optionally wrapped in one group.
+fn is_enumeration(raw: &str) -> bool {
+    let inner = raw
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+        .unwrap_or(raw);
+    inner.split('|').all(|alt| {
+        !alt.is_empty()
+            && alt
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+    })
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct RouteCacheKey {
+struct RouteCacheKey {
     method: HttpMethod,
     path: String,
 }
 
-impl RouteCacheKey {
-    pub fn new(method: HttpMethod, path: String) -> Self {
-        Self { method, path }
+/// Borrowed form of [`RouteCacheKey`]; hashes identically so lookups do not
+/// allocate.
+#[derive(Hash)]
+struct RouteCacheQuery<'a> {
+    method: HttpMethod,
+    path: &'a str,
+}
+
+impl RouteCacheQuery<'_> {
+    fn to_key(&self) -> RouteCacheKey {
+        RouteCacheKey {
+            method: self.method,
+            path: self.path.to_string(),
+        }
     }
 }
 
-#[derive(Debug, Clone)]
-struct RouteCacheValue {
-    result: RouteMatch,
+impl Equivalent<RouteCacheKey> for RouteCacheQuery<'_> {
+    fn equivalent(&self, key: &RouteCacheKey) -> bool {
+        self.method == key.method && self.path == key.path
+    }
+}
+
+/// Point-in-time cache counters. `hits` are lookups answered with a cached match,
+/// `negative_hits` lookups answered with a cached not-found, and `misses` lookups
+/// that walked the tree. `evictions` counts entries pushed out to make room and
+/// `rejected` results the admission policy declined to store.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheCounters {
+    pub hits: u64,
+    pub negative_hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub rejected: u64,
+}
+
+const MAX_COUNTER_STRIPES: usize = 64;
+
+static NEXT_STRIPE: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static STRIPE: usize = NEXT_STRIPE.fetch_add(1, Ordering::Relaxed);
 }
 
+/// One thread group's counters, padded to its own cache line pair so stripes
+/// never share a line.
 #[derive(Debug, Default)]
-pub struct CacheStats {
+#[repr(align(128))]
+struct CounterStripe {
     hits: AtomicU64,
+    negative_hits: AtomicU64,
     misses: AtomicU64,
+    evictions: AtomicU64,
+    rejected: AtomicU64,
+}
+
+/// Cache counters striped across threads: each thread bumps its own stripe and
+/// reads sum all of them, so lookups on different cores do not contend.
+#[derive(Debug)]
+pub struct CacheStats {
+    stripes: Box<[CounterStripe]>,
+}
+
+/// Number of counter stripes: the core count rounded up to a power of two.
+pub(crate) fn stripe_count() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .next_power_of_two()
+        .min(MAX_COUNTER_STRIPES)
+}
+
+/// The calling thread's stripe among `count`, a power of two.
+pub(crate) fn stripe_index(count: usize) -> usize {
+    STRIPE.with(|stripe| *stripe) & (count - 1)
+}
+
+impl Default for CacheStats {
+    fn default() -> Self {
+        Self {
+            stripes: (0..stripe_count())
+                .map(|_| CounterStripe::default())
+                .collect(),
+        }
+    }
 }
 
 impl CacheStats {
+    fn stripe(&self) -> &CounterStripe {
+        &self.stripes[stripe_index(self.stripes.len())]
+    }
+
     pub fn record_hit(&self) {
-        self.hits.fetch_add(1, Ordering::Relaxed);
+        self.stripe().hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_negative_hit(&self) {
+        self.stripe().negative_hits.fetch_add(1, Ordering::Relaxed);
     }
 
     pub fn record_miss(&self) {
-        self.misses.fetch_add(1, Ordering::Relaxed);
+        self.stripe().misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_rejected(&self) {
+        self.stripe().rejected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_eviction(&self) {
+        self.stripe().evictions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records the outcome of a [`RouteCache::insert`].
+    pub fn record_insert(&self, insert: CacheInsert) {
+        match insert {
+            CacheInsert::Evicted => self.record_eviction(),
+            CacheInsert::Rejected => self.record_rejected(),
+            CacheInsert::Stored | CacheInsert::Skipped => {}
+        }
     }
 
+    /// Records the outcome of a [`RouteCache::get`].
+    pub fn record_lookup(&self, lookup: Option<&CachedLookup>) {
+        match lookup {
+            Some(CachedLookup::Found(_)) => self.record_hit(),
+            Some(CachedLookup::NotFound { .. }) => self.record_negative_hit(),
+            None => self.record_miss(),
+        }
+    }
+
+    /// `(hits, misses)`; negative hits are not counted in either.
     pub fn snapshot(&self) -> (u64, u64) {
-        (
-            self.hits.load(Ordering::Relaxed),
-            self.misses.load(Ordering::Relaxed),
-        )
+        let counters = self.counters();
+        (counters.hits, counters.misses)
+    }
+
+    pub fn counters(&self) -> CacheCounters {
+        self.stripes
+            .iter()
+            .fold(CacheCounters::default(), |mut acc, stripe| {
+                acc.hits += stripe.hits.load(Ordering::Relaxed);
+                acc.negative_hits += stripe.negative_hits.load(Ordering::Relaxed);
+                acc.misses += stripe.misses.load(Ordering::Relaxed);
+                acc.evictions += stripe.evictions.load(Ordering::Relaxed);
+                acc.rejected += stripe.rejected.load(Ordering::Relaxed);
+                acc
+            })
     }
 }
 
@@ -105,35 +733,186 @@ mod tests {
     use super::*;
     use crate::types::RouteParams;
 
-    fn sample_match() -> RouteMatch {
-        (42, RouteParams::new())
+    fn single_shard(capacity: usize) -> RouteCache {
+        RouteCache::new(&CacheOptions {
+            capacity,
+            shards: 1,
+            ..CacheOptions::default()
+        })
+    }
+
+    fn sample_match(key: u16) -> CachedLookup {
+        CachedLookup::Found(Arc::new((key, RouteParams::new())))
+    }
+
+    #[test]
+    fn peek_returns_value_without_protecting_it_from_eviction() {
+        let cache = single_shard(2);
+        cache.insert(HttpMethod::Get, "/peek", sample_match(1));
+        cache.insert(HttpMethod::Get, "/other", sample_match(2));
+
+        assert_eq!(cache.peek(HttpMethod::Get, "/peek"), Some(sample_match(1)));
+        cache.insert(HttpMethod::Get, "/new", sample_match(3));
+
+        assert_eq!(cache.peek(HttpMethod::Get, "/peek"), None);
+        assert_eq!(cache.entries(), 2);
     }
 
     #[test]
-    fn peek_returns_value_without_changing_order() {
-        let mut cache = RouteCache::new(4);
-        let key = RouteCacheKey::new(HttpMethod::Get, "/peek".to_string());
-        cache.insert(key.clone(), sample_match());
+    fn get_keeps_entry_across_the_next_eviction() {
+        let cache = single_shard(2);
+        cache.insert(HttpMethod::Get, "/first", sample_match(1));
+        cache.insert(HttpMethod::Get, "/second", sample_match(2));
 
-        let front_before = cache.order.front().cloned();
-        let result = cache.peek(&key);
-        let front_after = cache.order.front().cloned();
+        assert!(cache.get(HttpMethod::Get, "/first").is_some());
+        cache.insert(HttpMethod::Get, "/third", sample_match(3));
 
-        assert_eq!(result, Some(sample_match()));
-        assert_eq!(front_before, front_after);
-        assert_eq!(front_after, Some(key));
+        assert!(cache.peek(HttpMethod::Get, "/first").is_some());
+        assert!(cache.peek(HttpMethod::Get, "/second").is_none());
+        assert!(cache.peek(HttpMethod::Post, "/first").is_none());
     }
 
     #[test]
-    fn touch_promotes_entry_to_front() {
-        let mut cache = RouteCache::new(4);
-        let first = RouteCacheKey::new(HttpMethod::Get, "/first".to_string());
-        let second = RouteCacheKey::new(HttpMethod::Get, "/second".to_string());
-        cache.insert(first.clone(), sample_match());
-        cache.insert(second.clone(), sample_match());
-
-        assert_eq!(cache.order.front(), Some(&second));
-        cache.touch(&first);
-        assert_eq!(cache.order.front(), Some(&first));
+    fn hits_share_the_cached_allocation() {
+        let cache = RouteCache::new(&CacheOptions::default());
+        cache.insert(HttpMethod::Get, "/shared", sample_match(7));
+
+        let (Some(CachedLookup::Found(first)), Some(CachedLookup::Found(second))) = (
+            cache.get(HttpMethod::Get, "/shared"),
+            cache.get(HttpMethod::Get, "/shared"),
+        ) else {
+            panic!("entry should be cached");
+        };
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn batch_calls_keep_input_order_across_shards() {
+        let cache = RouteCache::new(&CacheOptions {
+            shards: 4,
+            ..CacheOptions::default()
+        });
+        let paths: Vec<String> = (0..16).map(|idx| format!("/batch/{idx}")).collect();
+        let inserts = cache.insert_many(
+            paths
+                .iter()
+                .enumerate()
+                .map(|(idx, path)| (HttpMethod::Get, path.as_str(), sample_match(idx as u16)))
+                .chain([(
+                    HttpMethod::Get,
+                    "/missing",
+                    CachedLookup::NotFound { allowed: 0 },
+                )])
+                .collect(),
+        );
+        assert!(
+            inserts[..16]
+                .iter()
+                .all(|insert| *insert == CacheInsert::Stored)
+        );
+        assert_eq!(inserts[16], CacheInsert::Skipped);
+
+        let keys: Vec<(HttpMethod, &str)> = paths
+            .iter()
+            .rev()
+            .map(|path| (HttpMethod::Get, path.as_str()))
+            .chain([(HttpMethod::Post, "/batch/0")])
+            .collect();
+        let hits = cache.get_many(&keys);
+        for (idx, hit) in hits[..16].iter().enumerate() {
+            assert_eq!(*hit, Some(sample_match(15 - idx as u16)));
+        }
+        assert_eq!(hits[16], None);
+    }
+
+    #[test]
+    fn thread_caches_are_pruned_without_reads() {
+        let options = CacheOptions {
+            shards: 1,
+            ..CacheOptions::default()
+        };
+        let owner = Arc::new(ThreadCaches::new(options));
+        for _ in 0..8 {
+            std::thread::spawn({
+                let owner = owner.clone();
+                move || drop(ThreadCaches::local(&owner))
+            })
+            .join()
+            .expect("thread should finish");
+        }
+        assert!(owner.threads.lock().len() <= 1);
+
+        let dropped = Arc::new(ThreadCaches::new(options));
+        drop(ThreadCaches::local(&dropped));
+        let cache = dropped.threads.lock()[0].clone();
+        drop(dropped);
+        drop(ThreadCaches::local(&owner));
+        assert_eq!(cache.strong_count(), 0);
+    }
+
+    #[test]
+    fn not_found_is_only_stored_with_negative_caching() {
+        let plain = single_shard(4);
+        assert_eq!(
+            plain.insert(
+                HttpMethod::Get,
+                "/missing",
+                CachedLookup::NotFound { allowed: 0 }
+            ),
+            CacheInsert::Skipped
+        );
+        assert_eq!(plain.peek(HttpMethod::Get, "/missing"), None);
+
+        let negative = RouteCache::new(&CacheOptions {
+            negative: true,
+            ..CacheOptions::default()
+        });
+        negative.insert(
+            HttpMethod::Get,
+            "/missing",
+            CachedLookup::NotFound { allowed: 0 },
+        );
+        assert_eq!(
+            negative.get(HttpMethod::Get, "/missing"),
+            Some(CachedLookup::NotFound { allowed: 0 })
+        );
+    }
+
+    #[test]
+    fn frequency_admission_rejects_one_off_keys() {
+        let cache = RouteCache::new(&CacheOptions {
+            capacity: 2,
+            shards: 1,
+            admission: CacheAdmission::Frequency,
+            ..CacheOptions::default()
+        });
+        for path in ["/hot", "/warm"] {
+            for _ in 0..3 {
+                cache.get(HttpMethod::Get, path);
+            }
+            assert_eq!(
+                cache.insert(HttpMethod::Get, path, sample_match(1)),
+                CacheInsert::Stored
+            );
+        }
+
+        for idx in 0..20 {
+            let path = format!("/scan/{idx}");
+            assert_eq!(cache.get(HttpMethod::Get, &path), None);
+            assert_eq!(
+                cache.insert(HttpMethod::Get, &path, sample_match(2)),
+                CacheInsert::Rejected
+            );
+        }
+        assert!(cache.peek(HttpMethod::Get, "/hot").is_some());
+        assert!(cache.peek(HttpMethod::Get, "/warm").is_some());
+
+        for _ in 0..5 {
+            cache.get(HttpMethod::Get, "/rising");
+        }
+        assert_eq!(
+            cache.insert(HttpMethod::Get, "/rising", sample_match(3)),
+            CacheInsert::Evicted
+        );
     }
 }
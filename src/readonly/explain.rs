@@ -0,0 +1,212 @@
+use crate::enums::HttpMethod;
+use crate::types::RouteParams;
+use std::fmt;
+
+/// Structured account of how a lookup was resolved, produced by
+/// [`RouterReadOnly::explain`](super::RouterReadOnly::explain). It follows the
+/// same order as `find`, but never updates the route cache or its counters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchTrace {
+    pub method: HttpMethod,
+    pub target: String,
+    pub query: Option<String>,
+    /// Path after each enabled normalization stage, starting with the raw path.
+    pub normalization: Vec<NormalizationStep>,
+    pub steps: Vec<TraceStep>,
+    pub decision: Decision,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizationStep {
+    pub stage: &'static str,
+    pub output: String,
+}
+
+/// One thing the lookup tried. `depth` is the index of the path segment being
+/// matched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceStep {
+    Cache {
+        hit: Option<u16>,
+    },
+    /// The cache remembered that this path matches no route.
+    NegativeCache,
+    StaticMap {
+        hit: Option<u16>,
+    },
+    FusedEdge {
+        depth: usize,
+        edge: String,
+        matched: bool,
+    },
+    StaticChild {
+        depth: usize,
+        segment: String,
+        found: bool,
+    },
+    Pattern {
+        depth: usize,
+        segment: String,
+        pattern: String,
+        outcome: PatternOutcome,
+    },
+    /// The subtree entered through `via` had no route; the lookup backtracks.
+    Backtrack {
+        depth: usize,
+        via: String,
+    },
+    /// End of the path reached; `available` lists the methods the node serves.
+    Terminal {
+        depth: usize,
+        route: Option<u16>,
+        available: Vec<HttpMethod>,
+    },
+    Wildcard {
+        depth: usize,
+        route: Option<u16>,
+    },
+    /// Request conditions chose among the routes sharing the matched slot.
+    Variant {
+        slot: u16,
+        selected: Option<u16>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternOutcome {
+    Matched,
+    /// The segment has the right shape but `value` fails the constraint of `param`.
+    ConstraintRejected {
+        param: String,
+        constraint: String,
+        value: String,
+    },
+    /// Literal parts or the default parameter pattern do not fit the segment.
+    Mismatch,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchSource {
+    Cache,
+    StaticMap,
+    Tree,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decision {
+    Matched {
+        key: u16,
+        params: RouteParams,
+        source: MatchSource,
+    },
+    NotFound,
+    /// The path was rejected before lookup.
+    InvalidPath {
+        reason: String,
+    },
+}
+
+impl MatchTrace {
+    pub fn key(&self) -> Option<u16> {
+        match self.decision {
+            Decision::Matched { key, .. } => Some(key),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for MatchTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} {}", self.method.as_str(), self.target)?;
+        for step in &self.normalization {
+            writeln!(f, "  {:<16} {}", step.stage, step.output)?;
+        }
+        for step in &self.steps {
+            writeln!(f, "  {step}")?;
+        }
+        match &self.decision {
+            Decision::Matched { key, source, .. } => {
+                write!(f, "  => route {key} (from {source:?})")
+            }
+            Decision::NotFound => write!(f, "  => not found"),
+            Decision::InvalidPath { reason } => write!(f, "  => invalid path: {reason}"),
+        }
+    }
+}
+
+impl fmt::Display for TraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let indent = |depth: &usize| "  ".repeat(*depth);
+        match self {
+            TraceStep::Cache { hit } => write!(f, "cache: {}", found(hit)),
+            TraceStep::NegativeCache => write!(f, "cache: known not found"),
+            TraceStep::StaticMap { hit } => write!(f, "static map: {}", found(hit)),
+            TraceStep::FusedEdge {
+                depth,
+                edge,
+                matched,
+            } => write!(
+                f,
+                "{}fused '{edge}': {}",
+                indent(depth),
+                if *matched { "matched" } else { "rejected" }
+            ),
+            TraceStep::StaticChild {
+                depth,
+                segment,
+                found,
+            } => write!(
+                f,
+                "{}static '{segment}': {}",
+                indent(depth),
+                if *found { "entered" } else { "no child" }
+            ),
+            TraceStep::Pattern {
+                depth,
+                segment,
+                pattern,
+                outcome,
+            } => {
+                write!(f, "{}pattern '{pattern}' on '{segment}': ", indent(depth))?;
+                match outcome {
+                    PatternOutcome::Matched => write!(f, "matched"),
+                    PatternOutcome::ConstraintRejected {
+                        param,
+                        constraint,
+                        value,
+                    } => write!(f, "'{value}' fails {param}({constraint})"),
+                    PatternOutcome::Mismatch => write!(f, "shape mismatch"),
+                }
+            }
+            TraceStep::Backtrack { depth, via } => {
+                write!(f, "{}backtrack: nothing under '{via}'", indent(depth))
+            }
+            TraceStep::Terminal {
+                depth,
+                route,
+                available,
+            } => {
+                write!(f, "{}end of path: {}", indent(depth), found(route))?;
+                if route.is_none() && !available.is_empty() {
+                    let methods: Vec<_> = available.iter().map(HttpMethod::as_str).collect();
+                    write!(f, " (node serves {})", methods.join(", "))?;
+                }
+                Ok(())
+            }
+            TraceStep::Wildcard { depth, route } => {
+                write!(f, "{}wildcard: {}", indent(depth), found(route))
+            }
+            TraceStep::Variant { slot, selected } => match selected {
+                Some(key) => write!(f, "variant of slot {slot}: route {key}"),
+                None => write!(f, "variant of slot {slot}: no conditions satisfied"),
+            },
+        }
+    }
+}
+
+fn found(route: &Option<u16>) -> String {
+    match route {
+        Some(key) => format!("route {key}"),
+        None => "miss".to_string(),
+    }
+}
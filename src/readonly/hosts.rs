@@ -0,0 +1,152 @@
+use super::converter::{copy_static_maps, extract_root};
+use super::snapshot::ReadOnlyNode;
+use crate::enums::HttpMethod;
+use crate::host::{host_lookup_path, host_pattern_path, restore_host_params};
+use crate::matcher::{find_route, with_param_buffer};
+use crate::radix::{HTTP_METHOD_COUNT, RadixTree};
+use crate::registry::HostTable;
+use crate::types::{RouteMatch, RouteParams};
+use hashbrown::HashMap as FastHashMap;
+use regex::Regex;
+
+/// Sealed form of [`HostTable`]: the reversed-label host tree plus one path tree
+/// per host pattern.
+#[derive(Debug, Clone, Default)]
+pub struct HostSnapshot {
+    pub(crate) patterns: ReadOnlyNode,
+    pub(crate) scopes: Vec<HostScopeSnapshot>,
+    /// Scope indices from most to least specific pattern.
+    order: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HostScopeSnapshot {
+    pub(crate) host: Box<str>,
+    pub(crate) pattern: ReadOnlyNode,
+    pub(crate) static_maps: [FastHashMap<Box<str>, u16>; HTTP_METHOD_COUNT],
+    pub(crate) root: ReadOnlyNode,
+}
+
+impl HostScopeSnapshot {
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    fn find(
+        &self,
+        method: HttpMethod,
+        normalized: &str,
+        default_param_pattern: &Regex,
+    ) -> Option<RouteMatch> {
+        if let Some(&route_key) = self.static_maps[method as usize].get(normalized) {
+            return Some((route_key, RouteParams::new()));
+        }
+        with_param_buffer(|buf| {
+            find_route(&self.root, method, normalized, buf, default_param_pattern)
+        })
+    }
+}
+
+impl HostSnapshot {
+    pub fn scopes(&self) -> &[HostScopeSnapshot] {
+        &self.scopes
+    }
+
+    pub(crate) fn new(patterns: ReadOnlyNode, scopes: Vec<HostScopeSnapshot>) -> Self {
+        let mut order: Vec<usize> = (0..scopes.len()).collect();
+        order.sort_by_cached_key(|&idx| specificity(&scopes[idx].host));
+        Self {
+            patterns,
+            scopes,
+            order,
+        }
+    }
+
+    pub(crate) fn from_table(table: &HostTable) -> Option<Self> {
+        if table.is_empty() {
+            return None;
+        }
+
+        let scopes = table
+            .scopes()
+            .iter()
+            .map(|scope| HostScopeSnapshot {
+                host: scope.host.clone().into_boxed_str(),
+                pattern: extract_root(&scope.pattern.root_node),
+                static_maps: copy_static_maps(&scope.tree),
+                root: extract_root(&scope.tree.root_node),
+            })
+            .collect();
+
+        Some(Self::new(extract_root(&table.patterns().root_node), scopes))
+    }
+
+    /// Matches `normalized` inside the scopes whose pattern accepts `host`, most
+    /// specific first: when `:tenant.example.com` has no route for the path,
+    /// `*.example.com` is tried next. Host captures of the scope that matched are
+    /// merged into the returned parameters.
+    pub(crate) fn find(
+        &self,
+        method: HttpMethod,
+        host: &str,
+        normalized: &str,
+        default_param_pattern: &Regex,
+    ) -> Option<RouteMatch> {
+        let lookup = host_lookup_path(host)?;
+        let match_host = |node: &ReadOnlyNode| {
+            with_param_buffer(|buf| {
+                find_route(node, HttpMethod::Get, &lookup, buf, default_param_pattern)
+            })
+        };
+
+        // The combined tree yields the best scope in one walk; the rest are only
+        // consulted when it has no route for the path.
+        let (best, host_params) = match_host(&self.patterns)?;
+        let best = best as usize;
+        if let Some(found) = self.scopes.get(best).and_then(|scope| {
+            scope
+                .find(method, normalized, default_param_pattern)
+                .map(|found| merge_host_params(host_params, found))
+        }) {
+            return Some(found);
+        }
+
+        self.order
+            .iter()
+            .filter(|&&idx| idx != best)
+            .find_map(|&idx| {
+                let scope = &self.scopes[idx];
+                let (_, host_params) = match_host(&scope.pattern)?;
+                let found = scope.find(method, normalized, default_param_pattern)?;
+                Some(merge_host_params(host_params, found))
+            })
+    }
+}
+
+/// Seals a single-pattern host tree, as rebuilt when loading an image.
+pub(crate) fn pattern_root(mut tree: RadixTree) -> ReadOnlyNode {
+    tree.finalize();
+    extract_root(&tree.root_node)
+}
+
+fn merge_host_params(host_params: RouteParams, (route_key, params): RouteMatch) -> RouteMatch {
+    let mut merged = restore_host_params(host_params);
+    merged.extend(params);
+    (route_key, merged)
+}
+
+/// Ranks each label from the top-level domain down: literal before parameter
+/// before wildcard, matching the order the host tree prefers.
+fn specificity(host: &str) -> Vec<u8> {
+    let Ok(path) = host_pattern_path(host) else {
+        return Vec::new();
+    };
+    path.split('/')
+        .filter(|label| !label.is_empty())
+        .map(|label| match label.as_bytes()[0] {
+            b'*' => 2,
+            b':' => 1,
+            _ => 0,
+        })
+        .collect()
+}
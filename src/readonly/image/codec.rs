@@ -0,0 +1,161 @@
+use super::error::{ImageError, ImageResult};
+
+/// Appends little-endian primitives and length-prefixed strings to a buffer.
+#[derive(Debug, Default)]
+pub(crate) struct ImageWriter {
+    buf: Vec<u8>,
+}
+
+impl ImageWriter {
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.buf
+    }
+
+    pub(crate) fn u8(&mut self, value: u8) {
+        self.buf.push(value);
+    }
+
+    pub(crate) fn bool(&mut self, value: bool) {
+        self.u8(value as u8);
+    }
+
+    pub(crate) fn u16(&mut self, value: u16) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub(crate) fn u32(&mut self, value: u32) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub(crate) fn i32(&mut self, value: i32) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub(crate) fn len(&mut self, value: usize) {
+        self.u32(u32::try_from(value).expect("snapshot collections fit in u32"));
+    }
+
+    pub(crate) fn str(&mut self, value: &str) {
+        self.len(value.len());
+        self.buf.extend_from_slice(value.as_bytes());
+    }
+
+    pub(crate) fn opt<T>(&mut self, value: Option<T>, write: impl FnOnce(&mut Self, T)) {
+        match value {
+            Some(value) => {
+                self.u8(1);
+                write(self, value);
+            }
+            None => self.u8(0),
+        }
+    }
+}
+
+/// Reads what [`ImageWriter`] wrote, failing on truncation or malformed data.
+#[derive(Debug)]
+pub(crate) struct ImageReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ImageReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    pub(crate) fn finish(self) -> ImageResult<()> {
+        match self.bytes.len() - self.pos {
+            0 => Ok(()),
+            count => Err(ImageError::TrailingBytes { count }),
+        }
+    }
+
+    pub(crate) fn malformed(&self, reason: impl Into<String>) -> ImageError {
+        ImageError::Malformed {
+            offset: self.pos,
+            reason: reason.into(),
+        }
+    }
+
+    pub(crate) fn take(&mut self, n: usize) -> ImageResult<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(ImageError::Truncated { offset: self.pos })?;
+        let out = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(out)
+    }
+
+    fn array<const N: usize>(&mut self) -> ImageResult<[u8; N]> {
+        let bytes = self.take(N)?;
+        Ok(bytes.try_into().expect("take returns exactly N bytes"))
+    }
+
+    pub(crate) fn u8(&mut self) -> ImageResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(crate) fn bool(&mut self) -> ImageResult<bool> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(self.malformed(format!("invalid bool {other}"))),
+        }
+    }
+
+    pub(crate) fn u16(&mut self) -> ImageResult<u16> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    pub(crate) fn u32(&mut self) -> ImageResult<u32> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    pub(crate) fn i32(&mut self) -> ImageResult<i32> {
+        Ok(i32::from_le_bytes(self.array()?))
+    }
+
+    pub(crate) fn u64(&mut self) -> ImageResult<u64> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    /// Collection length; rejected when it could not possibly fit in the rest of
+    /// the image, so corrupt lengths do not trigger huge allocations.
+    pub(crate) fn len(&mut self) -> ImageResult<usize> {
+        let len = self.u32()? as usize;
+        if len > self.bytes.len() - self.pos {
+            return Err(ImageError::Truncated { offset: self.pos });
+        }
+        Ok(len)
+    }
+
+    pub(crate) fn str(&mut self) -> ImageResult<&'a str> {
+        let len = self.len()?;
+        let bytes = self.take(len)?;
+        std::str::from_utf8(bytes).map_err(|_| self.malformed("invalid utf-8 string"))
+    }
+
+    pub(crate) fn string(&mut self) -> ImageResult<String> {
+        self.str().map(str::to_string)
+    }
+
+    pub(crate) fn opt<T>(
+        &mut self,
+        read: impl FnOnce(&mut Self) -> ImageResult<T>,
+    ) -> ImageResult<Option<T>> {
+        if self.bool()? {
+            read(self).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// 64-bit FNV-1a over `bytes`.
+pub(crate) fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ImageError {
+    #[error("snapshot image does not start with the expected magic bytes")]
+    BadMagic,
+    #[error("snapshot image format version {found} is not supported (expected {expected})")]
+    UnsupportedVersion { found: u16, expected: u16 },
+    #[error("snapshot image checksum mismatch (stored {stored:#018x}, computed {computed:#018x})")]
+    ChecksumMismatch { stored: u64, computed: u64 },
+    #[error("snapshot image is truncated at byte {offset}")]
+    Truncated { offset: usize },
+    #[error("snapshot image has {count} unexpected trailing bytes")]
+    TrailingBytes { count: usize },
+    #[error("snapshot image is malformed at byte {offset}: {reason}")]
+    Malformed { offset: usize, reason: String },
+}
+
+pub type ImageResult<T> = Result<T, ImageError>;
@@ -0,0 +1,88 @@
+//! Binary image of a sealed [`RouterReadOnly`].
+//!
+//! Layout: 8 magic bytes, the format version (`u16`), an FNV-1a checksum of the
+//! payload (`u64`), then the payload. All integers are little-endian. Bump
+//! [`IMAGE_FORMAT_VERSION`] whenever the payload layout changes.
+
+mod codec;
+mod error;
+mod sections;
+
+pub use error::{ImageError, ImageResult};
+
+use super::RouterReadOnly;
+use codec::{ImageReader, ImageWriter, checksum};
+use sections::{
+    read_hosts, read_middleware, read_node, read_options, read_routes, read_static_maps,
+    read_uncached, read_variants, read_versions, write_hosts, write_middleware, write_node,
+    write_options, write_routes, write_static_maps, write_uncached, write_variants, write_versions,
+};
+use std::sync::Arc;
+
+pub const IMAGE_MAGIC: &[u8; 8] = b"BNRSNAP\0";
+pub const IMAGE_FORMAT_VERSION: u16 = 8;
+
+const HEADER_LEN: usize = IMAGE_MAGIC.len() + 2 + 8;
+
+impl RouterReadOnly {
+    /// Writes the sealed structure as a binary image that [`from_bytes`](Self::from_bytes)
+    /// can load without re-registering routes. The route cache, its counters and the
+    /// per-route usage counters are not included.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = ImageWriter::default();
+        write_options(&mut w, self.preprocessor.config());
+        write_static_maps(&mut w, &self.static_maps);
+        write_node(&mut w, &self.root);
+        w.opt(self.hosts.as_deref(), write_hosts);
+        w.opt(self.versions.as_deref(), write_versions);
+        write_middleware(&mut w, &self.middleware);
+        w.opt(self.variants.as_deref(), write_variants);
+        w.opt(self.uncached.as_deref(), write_uncached);
+        write_routes(&mut w, &self.routes);
+        let payload = w.into_bytes();
+
+        let mut out = Vec::with_capacity(HEADER_LEN + payload.len());
+        out.extend_from_slice(IMAGE_MAGIC);
+        out.extend_from_slice(&IMAGE_FORMAT_VERSION.to_le_bytes());
+        out.extend_from_slice(&checksum(&payload).to_le_bytes());
+        out.extend_from_slice(&payload);
+        out
+    }
+
+    /// Loads an image written by [`to_bytes`](Self::to_bytes). Parameter
+    /// constraints are recompiled on first use rather than up front.
+    pub fn from_bytes(bytes: &[u8]) -> ImageResult<Self> {
+        let mut header = ImageReader::new(bytes);
+        if header.take(IMAGE_MAGIC.len()).ok() != Some(IMAGE_MAGIC.as_slice()) {
+            return Err(ImageError::BadMagic);
+        }
+        let version = header.u16()?;
+        if version != IMAGE_FORMAT_VERSION {
+            return Err(ImageError::UnsupportedVersion {
+                found: version,
+                expected: IMAGE_FORMAT_VERSION,
+            });
+        }
+        let stored = header.u64()?;
+        let payload = &bytes[HEADER_LEN..];
+        let computed = checksum(payload);
+        if stored != computed {
+            return Err(ImageError::ChecksumMismatch { stored, computed });
+        }
+
+        let mut r = ImageReader::new(payload);
+        let options = read_options(&mut r)?;
+        let mut snapshot = RouterReadOnly::with_options(&options);
+        snapshot.static_maps = read_static_maps(&mut r)?;
+        snapshot.root = read_node(&mut r)?;
+        snapshot.hosts = r.opt(read_hosts)?.map(Arc::new);
+        snapshot.versions = r.opt(read_versions)?.map(Arc::new);
+        snapshot.middleware = read_middleware(&mut r)?;
+        snapshot.enable_usage(&options, snapshot.middleware.len());
+        snapshot.variants = r.opt(read_variants)?.map(Arc::new);
+        snapshot.uncached = r.opt(read_uncached)?;
+        snapshot.routes = read_routes(&mut r)?;
+        r.finish()?;
+        Ok(snapshot)
+    }
+}
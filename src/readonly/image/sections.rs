@@ -0,0 +1,649 @@
+use super::codec::{ImageReader, ImageWriter};
+use super::error::ImageResult;
+use crate::enums::HttpMethod;
+use crate::host::host_pattern_path;
+use crate::pattern::{ParamConstraint, SegmentPart, SegmentPattern};
+use crate::query::QueryCondition;
+use crate::radix::HTTP_METHOD_COUNT;
+use crate::readonly::hosts::{HostScopeSnapshot, HostSnapshot, pattern_root};
+use crate::readonly::routes::RouteTable;
+use crate::readonly::snapshot::ReadOnlyNode;
+use crate::readonly::variants::{CompiledVariant, VariantSnapshot};
+use crate::readonly::versions::{VersionSetSnapshot, VersionSnapshot};
+use crate::registry::{RouteRecord, RouteVariant, host_pattern_tree};
+use crate::request::HeaderCondition;
+use crate::router::{
+    CacheAdmission, CacheOptions, CacheScope, DefaultCachePolicy, MatchOrder, RepeatMatchMode,
+    RouteCachePolicy, RouteOptions, RouterOptions,
+};
+use crate::types::MiddlewareChain;
+use crate::version::{VersionSource, VersioningOptions};
+use hashbrown::HashMap as FastHashMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub(crate) type StaticMaps = [FastHashMap<Box<str>, u16>; HTTP_METHOD_COUNT];
+
+/// Options that affect lookups. Route defaults and strict route checks only matter
+/// before sealing and are not part of the image.
+pub(crate) fn write_options(w: &mut ImageWriter, options: &RouterOptions) {
+    w.bool(options.case_sensitive);
+    w.bool(options.strict_trailing_slash);
+    w.bool(options.decode_uri);
+    w.bool(options.normalize_path);
+    w.bool(options.allow_duplicate_slash);
+    w.u8(match options.match_order {
+        MatchOrder::SpecificFirst => 0,
+        MatchOrder::DefinedFirst => 1,
+    });
+    w.u8(match options.repeat_match_mode {
+        RepeatMatchMode::Greedy => 0,
+        RepeatMatchMode::Lazy => 1,
+    });
+    w.len(options.max_param_depth);
+    w.bool(options.debug);
+    w.bool(options.track_usage);
+    w.bool(options.track_lookups);
+    w.bool(options.cache.enabled);
+    w.len(options.cache.capacity);
+    w.len(options.cache.shards);
+    w.bool(options.cache.negative);
+    w.u8(match options.cache.admission {
+        CacheAdmission::Always => 0,
+        CacheAdmission::Frequency => 1,
+    });
+    w.u8(match options.cache.scope {
+        CacheScope::Shared => 0,
+        CacheScope::PerThread => 1,
+    });
+    w.u8(match options.cache.default_policy {
+        DefaultCachePolicy::All => 0,
+        DefaultCachePolicy::LowCardinality => 1,
+    });
+    write_versioning(w, &options.versioning);
+}
+
+pub(crate) fn read_options(r: &mut ImageReader<'_>) -> ImageResult<RouterOptions> {
+    let case_sensitive = r.bool()?;
+    let strict_trailing_slash = r.bool()?;
+    let decode_uri = r.bool()?;
+    let normalize_path = r.bool()?;
+    let allow_duplicate_slash = r.bool()?;
+    let match_order = match r.u8()? {
+        0 => MatchOrder::SpecificFirst,
+        1 => MatchOrder::DefinedFirst,
+        other => return Err(r.malformed(format!("unknown match order {other}"))),
+    };
+    let repeat_match_mode = match r.u8()? {
+        0 => RepeatMatchMode::Greedy,
+        1 => RepeatMatchMode::Lazy,
+        other => return Err(r.malformed(format!("unknown repeat mode {other}"))),
+    };
+    let max_param_depth = r.u32()? as usize;
+    let debug = r.bool()?;
+    let track_usage = r.bool()?;
+    let track_lookups = r.bool()?;
+    let cache = CacheOptions {
+        enabled: r.bool()?,
+        capacity: r.u32()? as usize,
+        shards: r.u32()? as usize,
+        negative: r.bool()?,
+        admission: match r.u8()? {
+            0 => CacheAdmission::Always,
+            1 => CacheAdmission::Frequency,
+            other => return Err(r.malformed(format!("unknown cache admission {other}"))),
+        },
+        scope: match r.u8()? {
+            0 => CacheScope::Shared,
+            1 => CacheScope::PerThread,
+            other => return Err(r.malformed(format!("unknown cache scope {other}"))),
+        },
+        default_policy: match r.u8()? {
+            0 => DefaultCachePolicy::All,
+            1 => DefaultCachePolicy::LowCardinality,
+            other => return Err(r.malformed(format!("unknown cache policy {other}"))),
+        },
+    };
+    if let Err(err) = cache.validate() {
+        return Err(r.malformed(err.to_string()));
+    }
+    let versioning = read_versioning(r)?;
+
+    Ok(RouterOptions {
+        case_sensitive,
+        strict_trailing_slash,
+        decode_uri,
+        normalize_path,
+        allow_duplicate_slash,
+        match_order,
+        repeat_match_mode,
+        max_param_depth,
+        debug,
+        track_usage,
+        track_lookups,
+        cache,
+        versioning,
+        ..RouterOptions::default()
+    })
+}
+
+fn write_versioning(w: &mut ImageWriter, versioning: &VersioningOptions) {
+    w.len(versioning.sources.len());
+    for source in versioning.sources.iter() {
+        let (tag, value) = match source {
+            VersionSource::PathPrefix { prefix } => (0, prefix),
+            VersionSource::Header { name } => (1, name),
+            VersionSource::MediaTypeParam { name } => (2, name),
+        };
+        w.u8(tag);
+        w.str(value);
+    }
+    w.opt(versioning.default_version, ImageWriter::u32);
+}
+
+fn read_versioning(r: &mut ImageReader<'_>) -> ImageResult<VersioningOptions> {
+    let count = r.len()?;
+    let mut sources = Vec::with_capacity(count);
+    for _ in 0..count {
+        let tag = r.u8()?;
+        let value = r.string()?;
+        sources.push(match tag {
+            0 => VersionSource::PathPrefix { prefix: value },
+            1 => VersionSource::Header { name: value },
+            2 => VersionSource::MediaTypeParam { name: value },
+            other => return Err(r.malformed(format!("unknown version source {other}"))),
+        });
+    }
+    let default_version = r.opt(ImageReader::u32)?;
+    Ok(VersioningOptions {
+        sources,
+        default_version,
+    })
+}
+
+pub(crate) fn write_static_maps(w: &mut ImageWriter, maps: &StaticMaps) {
+    for map in maps.iter() {
+        w.len(map.len());
+        for (path, key) in map.iter() {
+            w.str(path);
+            w.u16(*key);
+        }
+    }
+}
+
+pub(crate) fn read_static_maps(r: &mut ImageReader<'_>) -> ImageResult<StaticMaps> {
+    let mut maps: StaticMaps = Default::default();
+    for map in maps.iter_mut() {
+        let count = r.len()?;
+        map.reserve(count);
+        for _ in 0..count {
+            let path = r.str()?;
+            let key = r.u16()?;
+            map.insert(path.into(), key);
+        }
+    }
+    Ok(maps)
+}
+
+pub(crate) fn write_node(w: &mut ImageWriter, node: &ReadOnlyNode) {
+    w.opt(node.fused_edge.as_deref(), ImageWriter::str);
+    w.opt(node.fused_child.as_deref(), write_node);
+    for key in node.routes.iter().chain(node.wildcard_routes.iter()) {
+        w.u16(*key);
+    }
+    w.len(node.static_children.len());
+    for (segment, child) in node.static_children.iter() {
+        w.str(segment);
+        write_node(w, child);
+    }
+    w.len(node.patterns.len());
+    for (pattern, child) in node.patterns.iter() {
+        write_pattern(w, pattern);
+        write_node(w, child);
+    }
+}
+
+pub(crate) fn read_node(r: &mut ImageReader<'_>) -> ImageResult<ReadOnlyNode> {
+    let fused_edge = r.opt(|r| r.str().map(Box::from))?;
+    let fused_child = r.opt(|r| read_node(r).map(Box::new))?;
+    let mut routes = [0u16; HTTP_METHOD_COUNT];
+    for key in routes.iter_mut() {
+        *key = r.u16()?;
+    }
+    let mut wildcard_routes = [0u16; HTTP_METHOD_COUNT];
+    for key in wildcard_routes.iter_mut() {
+        *key = r.u16()?;
+    }
+
+    let count = r.len()?;
+    let mut static_children = FastHashMap::with_capacity(count);
+    for _ in 0..count {
+        let segment: Box<str> = r.str()?.into();
+        static_children.insert(segment, read_node(r)?);
+    }
+    let count = r.len()?;
+    let mut patterns = Vec::with_capacity(count);
+    for _ in 0..count {
+        let pattern = read_pattern(r)?;
+        patterns.push((pattern, read_node(r)?));
+    }
+
+    Ok(ReadOnlyNode {
+        fused_edge,
+        fused_child,
+        routes,
+        wildcard_routes,
+        static_children,
+        patterns,
+    })
+}
+
+/// Constraints are stored as their source; the regex is compiled on first use.
+fn write_pattern(w: &mut ImageWriter, pattern: &SegmentPattern) {
+    w.len(pattern.parts.len());
+    for part in pattern.parts.iter() {
+        match part {
+            SegmentPart::Literal(value) => {
+                w.u8(0);
+                w.str(value);
+            }
+            SegmentPart::Param { name, constraint } => {
+                w.u8(1);
+                w.str(name);
+                w.opt(
+                    constraint.as_ref().map(ParamConstraint::raw),
+                    ImageWriter::str,
+                );
+            }
+        }
+    }
+}
+
+fn read_pattern(r: &mut ImageReader<'_>) -> ImageResult<SegmentPattern> {
+    let count = r.len()?;
+    let mut parts = Vec::with_capacity(count);
+    for _ in 0..count {
+        parts.push(match r.u8()? {
+            0 => SegmentPart::Literal(r.string()?),
+            1 => SegmentPart::Param {
+                name: r.string()?,
+                constraint: r.opt(|r| r.string().map(ParamConstraint::new))?,
+            },
+            other => return Err(r.malformed(format!("unknown segment part {other}"))),
+        });
+    }
+    Ok(SegmentPattern { parts })
+}
+
+pub(crate) fn write_hosts(w: &mut ImageWriter, hosts: &HostSnapshot) {
+    write_node(w, &hosts.patterns);
+    w.len(hosts.scopes.len());
+    for scope in hosts.scopes.iter() {
+        w.str(&scope.host);
+        write_static_maps(w, &scope.static_maps);
+        write_node(w, &scope.root);
+    }
+}
+
+pub(crate) fn read_hosts(r: &mut ImageReader<'_>) -> ImageResult<HostSnapshot> {
+    let patterns = read_node(r)?;
+    let count = r.len()?;
+    let mut scopes = Vec::with_capacity(count);
+    for _ in 0..count {
+        let host: Box<str> = r.str()?.into();
+        let pattern = host_pattern_path(&host)
+            .and_then(|path| host_pattern_tree(&path))
+            .map_err(|err| r.malformed(format!("host pattern '{host}': {err}")))?;
+        scopes.push(HostScopeSnapshot {
+            pattern: pattern_root(pattern),
+            host,
+            static_maps: read_static_maps(r)?,
+            root: read_node(r)?,
+        });
+    }
+    Ok(HostSnapshot::new(patterns, scopes))
+}
+
+pub(crate) fn write_versions(w: &mut ImageWriter, versions: &VersionSnapshot) {
+    w.len(versions.sets.len());
+    for set in versions.sets.iter() {
+        w.u32(set.version);
+        write_static_maps(w, &set.static_maps);
+        write_node(w, &set.root);
+    }
+}
+
+pub(crate) fn read_versions(r: &mut ImageReader<'_>) -> ImageResult<VersionSnapshot> {
+    let count = r.len()?;
+    let mut sets = Vec::with_capacity(count);
+    for _ in 0..count {
+        sets.push(VersionSetSnapshot {
+            version: r.u32()?,
+            static_maps: read_static_maps(r)?,
+            root: read_node(r)?,
+        });
+    }
+    Ok(VersionSnapshot { sets })
+}
+
+/// Written as the list of keys kept out of the cache.
+pub(crate) fn write_uncached(w: &mut ImageWriter, uncached: &[bool]) {
+    let keys: Vec<usize> = (0..uncached.len()).filter(|&key| uncached[key]).collect();
+    w.len(uncached.len());
+    w.len(keys.len());
+    for key in keys {
+        w.len(key);
+    }
+}
+
+pub(crate) fn read_uncached(r: &mut ImageReader<'_>) -> ImageResult<Arc<[bool]>> {
+    let len = r.u32()? as usize;
+    if len > usize::from(u16::MAX) + 1 {
+        return Err(r.malformed(format!("uncached table of {len} routes")));
+    }
+    let mut uncached = vec![false; len];
+    for _ in 0..r.len()? {
+        let key = r.u32()? as usize;
+        match uncached.get_mut(key) {
+            Some(slot) => *slot = true,
+            None => return Err(r.malformed(format!("uncached route {key} out of range"))),
+        }
+    }
+    Ok(uncached.into())
+}
+
+/// Chains are written once each and referenced by index, mirroring the interning
+/// done when they are composed.
+pub(crate) fn write_middleware(w: &mut ImageWriter, chains: &[MiddlewareChain]) {
+    let mut distinct: Vec<&MiddlewareChain> = Vec::new();
+    let mut indices = Vec::with_capacity(chains.len());
+    for chain in chains.iter() {
+        let idx = match distinct.iter().position(|known| Arc::ptr_eq(known, chain)) {
+            Some(idx) => idx,
+            None => {
+                distinct.push(chain);
+                distinct.len() - 1
+            }
+        };
+        indices.push(idx);
+    }
+
+    w.len(distinct.len());
+    for chain in distinct {
+        w.len(chain.len());
+        for id in chain.iter() {
+            w.str(id);
+        }
+    }
+    w.len(indices.len());
+    for idx in indices {
+        w.len(idx);
+    }
+}
+
+pub(crate) fn read_middleware(r: &mut ImageReader<'_>) -> ImageResult<Vec<MiddlewareChain>> {
+    let count = r.len()?;
+    let mut distinct: Vec<MiddlewareChain> = Vec::with_capacity(count);
+    for _ in 0..count {
+        let len = r.len()?;
+        let mut chain = Vec::with_capacity(len);
+        for _ in 0..len {
+            chain.push(r.string()?);
+        }
+        distinct.push(Arc::from(chain));
+    }
+
+    let count = r.len()?;
+    let mut chains = Vec::with_capacity(count);
+    for _ in 0..count {
+        let idx = r.u32()? as usize;
+        let chain = distinct
+            .get(idx)
+            .cloned()
+            .ok_or_else(|| r.malformed(format!("middleware chain index {idx} out of range")))?;
+        chains.push(chain);
+    }
+    Ok(chains)
+}
+
+pub(crate) fn write_variants(w: &mut ImageWriter, variants: &VariantSnapshot) {
+    let groups: Vec<_> = variants.groups().collect();
+    w.len(groups.len());
+    for (slot, group) in groups {
+        w.u16(slot);
+        w.len(group.len());
+        for variant in group.iter().map(CompiledVariant::source) {
+            w.u16(variant.key);
+            w.i32(variant.priority);
+            write_query_conditions(w, &variant.query);
+            write_header_conditions(w, &variant.headers);
+        }
+    }
+}
+
+fn write_condition(w: &mut ImageWriter, tag: u8, name: &str, arg: Option<&String>) {
+    w.u8(tag);
+    w.str(name);
+    if let Some(arg) = arg {
+        w.str(arg);
+    }
+}
+
+fn write_query_conditions(w: &mut ImageWriter, conditions: &[QueryCondition]) {
+    w.len(conditions.len());
+    for condition in conditions.iter() {
+        let (tag, name, arg) = match condition {
+            QueryCondition::Present { name } => (0, name, None),
+            QueryCondition::Equals { name, value } => (1, name, Some(value)),
+            QueryCondition::Matches { name, pattern } => (2, name, Some(pattern)),
+        };
+        write_condition(w, tag, name, arg);
+    }
+}
+
+fn write_header_conditions(w: &mut ImageWriter, conditions: &[HeaderCondition]) {
+    w.len(conditions.len());
+    for condition in conditions.iter() {
+        let (tag, name, arg) = match condition {
+            HeaderCondition::Present { name } => (0, name, None),
+            HeaderCondition::Equals { name, value } => (1, name, Some(value)),
+            HeaderCondition::Matches { name, pattern } => (2, name, Some(pattern)),
+        };
+        write_condition(w, tag, name, arg);
+    }
+}
+
+fn read_query_conditions(r: &mut ImageReader<'_>) -> ImageResult<Vec<QueryCondition>> {
+    let count = r.len()?;
+    let mut query = Vec::with_capacity(count);
+    for _ in 0..count {
+        query.push(match r.u8()? {
+            0 => QueryCondition::Present { name: r.string()? },
+            1 => QueryCondition::Equals {
+                name: r.string()?,
+                value: r.string()?,
+            },
+            2 => QueryCondition::Matches {
+                name: r.string()?,
+                pattern: r.string()?,
+            },
+            other => return Err(r.malformed(format!("unknown query condition {other}"))),
+        });
+    }
+    Ok(query)
+}
+
+fn read_header_conditions(r: &mut ImageReader<'_>) -> ImageResult<Vec<HeaderCondition>> {
+    let count = r.len()?;
+    let mut headers = Vec::with_capacity(count);
+    for _ in 0..count {
+        headers.push(match r.u8()? {
+            0 => HeaderCondition::Present { name: r.string()? },
+            1 => HeaderCondition::Equals {
+                name: r.string()?,
+                value: r.string()?,
+            },
+            2 => HeaderCondition::Matches {
+                name: r.string()?,
+                pattern: r.string()?,
+            },
+            other => return Err(r.malformed(format!("unknown header condition {other}"))),
+        });
+    }
+    Ok(headers)
+}
+
+pub(crate) fn read_variants(r: &mut ImageReader<'_>) -> ImageResult<VariantSnapshot> {
+    let count = r.len()?;
+    let mut groups = FastHashMap::with_capacity(count);
+    for _ in 0..count {
+        let slot = r.u16()?;
+        let len = r.len()?;
+        let mut group = Vec::with_capacity(len);
+        for _ in 0..len {
+            let key = r.u16()?;
+            let priority = r.i32()?;
+
+            let query = read_query_conditions(r)?;
+            let headers = read_header_conditions(r)?;
+
+            let variant = CompiledVariant::compile(RouteVariant {
+                key,
+                query,
+                headers,
+                priority,
+            })
+            .map_err(|err| r.malformed(format!("invalid route condition: {err}")))?;
+            group.push(variant);
+        }
+        groups.insert(slot, group);
+    }
+    Ok(VariantSnapshot::from_ordered(groups))
+}
+
+/// Registered routes with their effective options, so route listings and usage
+/// reports work on a loaded snapshot.
+pub(crate) fn write_routes(w: &mut ImageWriter, routes: &RouteTable) {
+    w.len(routes.len());
+    for record in routes.iter() {
+        w.u16(record.key);
+        w.u8(record.method as u8);
+        w.str(&record.path);
+        w.str(&record.normalized);
+        w.opt(record.host.as_deref(), ImageWriter::str);
+        write_route_options(w, &record.options);
+    }
+}
+
+pub(crate) fn read_routes(r: &mut ImageReader<'_>) -> ImageResult<RouteTable> {
+    let count = r.len()?;
+    let mut records = Vec::with_capacity(count);
+    for _ in 0..count {
+        records.push(RouteRecord {
+            key: r.u16()?,
+            method: read_method(r)?,
+            path: r.string()?,
+            normalized: r.string()?,
+            host: r.opt(ImageReader::string)?,
+            options: read_route_options(r)?,
+        });
+    }
+    Ok(RouteTable::new(records))
+}
+
+fn write_route_options(w: &mut ImageWriter, options: &RouteOptions) {
+    w.opt(options.pattern.as_deref(), ImageWriter::str);
+    w.len(options.methods.len());
+    for method in options.methods.iter() {
+        w.u8(*method as u8);
+    }
+    write_string_map(w, &options.constraints);
+    w.bool(options.optional);
+    w.bool(options.repeatable);
+    w.i32(options.priority);
+    write_string_map(w, &options.meta);
+    w.opt(options.alias.as_deref(), ImageWriter::str);
+    w.len(options.middleware.len());
+    for id in options.middleware.iter() {
+        w.str(id);
+    }
+    write_query_conditions(w, &options.query);
+    write_header_conditions(w, &options.headers);
+    w.opt(options.version, ImageWriter::u32);
+    w.u8(match options.cache {
+        RouteCachePolicy::Default => 0,
+        RouteCachePolicy::Always => 1,
+        RouteCachePolicy::Never => 2,
+    });
+}
+
+fn read_route_options(r: &mut ImageReader<'_>) -> ImageResult<RouteOptions> {
+    let pattern = r.opt(ImageReader::string)?;
+    let count = r.len()?;
+    let mut methods = Vec::with_capacity(count);
+    for _ in 0..count {
+        methods.push(read_method(r)?);
+    }
+    let constraints = read_string_map(r)?;
+    let optional = r.bool()?;
+    let repeatable = r.bool()?;
+    let priority = r.i32()?;
+    let meta = read_string_map(r)?;
+    let alias = r.opt(ImageReader::string)?;
+    let count = r.len()?;
+    let mut middleware = Vec::with_capacity(count);
+    for _ in 0..count {
+        middleware.push(r.string()?);
+    }
+    let query = read_query_conditions(r)?;
+    let headers = read_header_conditions(r)?;
+    let version = r.opt(ImageReader::u32)?;
+    let cache = match r.u8()? {
+        0 => RouteCachePolicy::Default,
+        1 => RouteCachePolicy::Always,
+        2 => RouteCachePolicy::Never,
+        other => return Err(r.malformed(format!("unknown route cache policy {other}"))),
+    };
+
+    Ok(RouteOptions {
+        pattern,
+        methods,
+        constraints,
+        optional,
+        repeatable,
+        priority,
+        meta,
+        alias,
+        middleware,
+        query,
+        headers,
+        version,
+        cache,
+    })
+}
+
+fn read_method(r: &mut ImageReader<'_>) -> ImageResult<HttpMethod> {
+    let idx = r.u8()?;
+    HttpMethod::ALL
+        .get(usize::from(idx))
+        .copied()
+        .ok_or_else(|| r.malformed(format!("unknown http method {idx}")))
+}
+
+fn write_string_map(w: &mut ImageWriter, map: &HashMap<String, String>) {
+    w.len(map.len());
+    for (name, value) in map.iter() {
+        w.str(name);
+        w.str(value);
+    }
+}
+
+fn read_string_map(r: &mut ImageReader<'_>) -> ImageResult<HashMap<String, String>> {
+    let count = r.len()?;
+    let mut map = HashMap::with_capacity(count);
+    for _ in 0..count {
+        let name = r.string()?;
+        map.insert(name, r.string()?);
+    }
+    Ok(map)
+}
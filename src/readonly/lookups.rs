@@ -0,0 +1,92 @@
+use super::cache::{stripe_count, stripe_index};
+use crate::path::PathError;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const PATH_ERROR_KINDS: usize = PathError::KINDS.len();
+
+/// How a lookup ended, as counted by [`LookupStats`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum LookupOutcome<'a> {
+    Matched,
+    NotFound,
+    /// No route for the method, but the path resolves under another one.
+    MethodNotAllowed,
+    InvalidPath(&'a PathError),
+}
+
+#[derive(Debug, Default)]
+#[repr(align(128))]
+struct LookupStripe {
+    matched: AtomicU64,
+    not_found: AtomicU64,
+    method_not_allowed: AtomicU64,
+    invalid_path: [AtomicU64; PATH_ERROR_KINDS],
+}
+
+/// Lookup outcome counters, striped across threads like the cache counters.
+#[derive(Debug)]
+pub(crate) struct LookupStats {
+    stripes: Box<[LookupStripe]>,
+}
+
+impl Default for LookupStats {
+    fn default() -> Self {
+        Self {
+            stripes: (0..stripe_count())
+                .map(|_| LookupStripe::default())
+                .collect(),
+        }
+    }
+}
+
+impl LookupStats {
+    pub(crate) fn record(&self, outcome: LookupOutcome<'_>) {
+        let stripe = &self.stripes[stripe_index(self.stripes.len())];
+        let counter = match outcome {
+            LookupOutcome::Matched => &stripe.matched,
+            LookupOutcome::NotFound => &stripe.not_found,
+            LookupOutcome::MethodNotAllowed => &stripe.method_not_allowed,
+            LookupOutcome::InvalidPath(err) => &stripe.invalid_path[err.kind_index()],
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn counters(&self) -> LookupCounters {
+        let mut counters = LookupCounters {
+            invalid_path: PathError::KINDS.map(|kind| (kind, 0)),
+            ..LookupCounters::default()
+        };
+        for stripe in self.stripes.iter() {
+            counters.matched += stripe.matched.load(Ordering::Relaxed);
+            counters.not_found += stripe.not_found.load(Ordering::Relaxed);
+            counters.method_not_allowed += stripe.method_not_allowed.load(Ordering::Relaxed);
+            for (total, counter) in counters.invalid_path.iter_mut().zip(&stripe.invalid_path) {
+                total.1 += counter.load(Ordering::Relaxed);
+            }
+        }
+        counters
+    }
+}
+
+/// Point-in-time lookup outcomes. `invalid_path` pairs every
+/// [`PathError::kind`] with the number of targets rejected for it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LookupCounters {
+    pub matched: u64,
+    pub not_found: u64,
+    pub method_not_allowed: u64,
+    pub invalid_path: [(&'static str, u64); PATH_ERROR_KINDS],
+}
+
+impl LookupCounters {
+    pub fn total(&self) -> u64 {
+        self.matched
+            + self.not_found
+            + self.method_not_allowed
+            + self
+                .invalid_path
+                .iter()
+                .map(|(_, count)| count)
+                .sum::<u64>()
+    }
+}
@@ -1,7 +1,31 @@
+mod analysis;
 mod cache;
 pub mod converter;
 mod error;
+mod explain;
+pub mod hosts;
+pub mod image;
+mod lookups;
+mod routes;
 pub mod snapshot;
+mod target;
+mod usage;
+mod variants;
+pub mod versions;
+mod warm;
 
+pub use analysis::{FindingKind, RouteAnalysis, RouteFinding};
+pub use cache::CacheCounters;
 pub use error::{ReadOnlyError, ReadOnlyResult};
+pub use explain::{
+    Decision, MatchSource, MatchTrace, NormalizationStep, PatternOutcome, TraceStep,
+};
+pub use hosts::{HostScopeSnapshot, HostSnapshot};
+pub use image::{ImageError, ImageResult};
+pub use lookups::LookupCounters;
+pub use routes::{RouteFilter, RouteTable};
 pub use snapshot::{ReadOnlyNode, RouterReadOnly};
+pub use target::TargetMatch;
+pub use usage::{RouteUsage, UsageReport};
+pub use versions::{VersionMatch, VersionSetSnapshot, VersionSnapshot};
+pub use warm::{WarmFailure, WarmReport};
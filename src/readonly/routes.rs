@@ -0,0 +1,121 @@
+use crate::enums::HttpMethod;
+use crate::registry::RouteRecord;
+use hashbrown::HashMap as FastHashMap;
+use std::sync::Arc;
+
+/// Registered routes in registration order, with lookup by key. Cheap to clone.
+#[derive(Debug, Clone, Default)]
+pub struct RouteTable {
+    records: Arc<[RouteRecord]>,
+    by_key: Arc<FastHashMap<u16, usize>>,
+}
+
+impl RouteTable {
+    pub fn new(records: Vec<RouteRecord>) -> Self {
+        let by_key = records
+            .iter()
+            .enumerate()
+            .map(|(idx, record)| (record.key, idx))
+            .collect();
+        Self {
+            records: records.into(),
+            by_key: Arc::new(by_key),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, RouteRecord> {
+        self.records.iter()
+    }
+
+    /// Reverse lookup: the route registered under `key`.
+    pub fn get(&self, key: u16) -> Option<&RouteRecord> {
+        self.by_key.get(&key).map(|&idx| &self.records[idx])
+    }
+
+    /// Routes accepted by every criterion of `filter`, in registration order.
+    pub fn filter<'a>(&'a self, filter: &'a RouteFilter) -> impl Iterator<Item = &'a RouteRecord> {
+        self.records
+            .iter()
+            .filter(move |record| filter.accepts(record))
+    }
+}
+
+impl<'a> IntoIterator for &'a RouteTable {
+    type Item = &'a RouteRecord;
+    type IntoIter = std::slice::Iter<'a, RouteRecord>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Criteria for [`RouteTable::filter`]. Unset criteria accept every route.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RouteFilter {
+    method: Option<HttpMethod>,
+    prefix: Option<String>,
+    meta: Vec<(String, Option<String>)>,
+}
+
+impl RouteFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn method(mut self, method: HttpMethod) -> Self {
+        self.method = Some(method);
+        self
+    }
+
+    /// Keeps routes whose registered path is `prefix` or lies below it, segment-wise.
+    pub fn prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Keeps routes with meta entry `key`, of any value.
+    pub fn meta_key<K: Into<String>>(mut self, key: K) -> Self {
+        self.meta.push((key.into(), None));
+        self
+    }
+
+    /// Keeps routes whose meta entry `key` equals `value`.
+    pub fn meta<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.meta.push((key.into(), Some(value.into())));
+        self
+    }
+
+    pub fn accepts(&self, record: &RouteRecord) -> bool {
+        if self.method.is_some_and(|method| method != record.method) {
+            return false;
+        }
+        if let Some(prefix) = self.prefix.as_deref()
+            && !under_prefix(&record.path, prefix)
+        {
+            return false;
+        }
+        self.meta.iter().all(|(key, value)| {
+            match (record.options.meta.get(key), value.as_deref()) {
+                (Some(found), Some(expected)) => found == expected,
+                (Some(_), None) => true,
+                (None, _) => false,
+            }
+        })
+    }
+}
+
+fn under_prefix(path: &str, prefix: &str) -> bool {
+    let prefix = prefix.trim_end_matches('/');
+    match path.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/'),
+        None => false,
+    }
+}
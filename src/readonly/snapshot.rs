@@ -1,79 +1,179 @@
 use crate::enums::HttpMethod;
-use crate::matcher::{find_route, with_param_buffer};
+use crate::matcher::{
+    allowed_methods, find_corrected_path, find_route, trace_route, with_param_buffer,
+};
 use crate::pattern::SegmentPattern;
 use crate::radix::{HTTP_METHOD_COUNT, RadixTree};
-use crate::router::{Preprocessor, Router};
-use crate::types::{RouteMatch, RouteParams};
+use crate::registry::RouteRegistry;
+use crate::request::RequestHeaders;
+use crate::router::{CacheOptions, PreprocessOutcome, Preprocessor, Router, RouterOptions};
+use crate::types::{MiddlewareChain, RouteMatch, RouteParams};
+use crate::version::{VersioningOptions, resolve_version};
 use hashbrown::HashMap as FastHashMap;
-use parking_lot::{RwLock, RwLockUpgradableReadGuard};
 use regex::Regex;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 
-use super::cache::{CacheStats, DEFAULT_CACHE_CAPACITY, RouteCache, RouteCacheKey};
+use super::cache::{
+    CacheCounters, CacheInsert, CacheStats, CacheStore, CachedLookup, RouteCache, uncached_routes,
+};
 use super::converter::{copy_static_maps, extract_root};
+use super::explain::{Decision, MatchSource, MatchTrace, TraceStep};
+use super::hosts::HostSnapshot;
+use super::lookups::{LookupCounters, LookupOutcome, LookupStats};
+use super::routes::RouteTable;
+use super::target::TargetMatch;
+use super::usage::{UsageCounters, UsageReport};
+use super::variants::VariantSnapshot;
+use super::versions::{VersionMatch, VersionSnapshot};
+use super::warm::{WarmFailure, WarmReport};
 use super::{ReadOnlyError, ReadOnlyResult};
 
 #[derive(Debug)]
 pub struct RouterReadOnly {
     pub(crate) static_maps: [FastHashMap<Box<str>, u16>; HTTP_METHOD_COUNT],
     pub(crate) root: ReadOnlyNode,
-    preprocessor: Preprocessor,
-    cache: Option<Arc<RwLock<RouteCache>>>,
+    pub(crate) hosts: Option<Arc<HostSnapshot>>,
+    pub(crate) versions: Option<Arc<VersionSnapshot>>,
+    pub(crate) versioning: Arc<VersioningOptions>,
+    pub(crate) middleware: Vec<MiddlewareChain>,
+    pub(crate) variants: Option<Arc<VariantSnapshot>>,
+    pub(crate) preprocessor: Preprocessor,
+    pub(crate) routes: RouteTable,
+    /// Indexed by route key; `true` keeps the route's lookups out of the cache.
+    pub(crate) uncached: Option<Arc<[bool]>>,
+    usage: Option<Arc<UsageCounters>>,
+    lookups: Option<Arc<LookupStats>>,
+    generation: u64,
+    cache: Option<CacheStore>,
     cache_stats: Option<Arc<CacheStats>>,
     debug: bool,
-    param_pattern_default: Arc<Regex>,
+    pub(crate) param_pattern_default: Arc<Regex>,
 }
 
 impl RouterReadOnly {
     pub fn from_router(router: &Router) -> Self {
-        router.with_registry(|registry| {
-            let tree = registry.tree();
-            let static_maps = copy_static_maps(tree);
-            let root = extract_root(&tree.root_node);
-            let preprocessor = tree.preprocessor.clone();
-            let options = tree.options.clone();
-            let cache = Some(Arc::new(RwLock::new(RouteCache::new(
-                DEFAULT_CACHE_CAPACITY,
-            ))));
-            let cache_stats = Some(Arc::new(CacheStats::default()));
-            let debug = options.debug;
-            let param_pattern_default = Arc::new(options.param_pattern_default_regex());
-
-            RouterReadOnly {
-                static_maps,
-                root,
-                preprocessor,
-                cache,
-                cache_stats,
-                debug,
-                param_pattern_default,
-            }
-        })
+        router.with_registry(Self::from_registry)
+    }
+
+    pub(crate) fn from_registry(registry: &RouteRegistry) -> Self {
+        let mut snapshot = Self::from_radix_tree(registry.tree());
+        snapshot.hosts = HostSnapshot::from_table(registry.hosts()).map(Arc::new);
+        snapshot.versions = VersionSnapshot::from_table(registry.versions()).map(Arc::new);
+        snapshot.middleware = registry.middleware_chains();
+        snapshot.enable_usage(&registry.tree().options, snapshot.middleware.len());
+        snapshot.routes = RouteTable::new(registry.records().to_vec());
+        snapshot.uncached = uncached_routes(
+            registry.records(),
+            registry.tree().options.cache.default_policy,
+        );
+        snapshot.variants = VariantSnapshot::from_table(registry.variants()).map(Arc::new);
+        snapshot
     }
 
     pub fn from_radix_tree(tree: &RadixTree) -> Self {
-        let static_maps = copy_static_maps(tree);
-        let root = extract_root(&tree.root_node);
-        let preprocessor = tree.preprocessor.clone();
-        let options = tree.options.clone();
-        let cache = Some(Arc::new(RwLock::new(RouteCache::new(
-            DEFAULT_CACHE_CAPACITY,
-        ))));
-        let cache_stats = Some(Arc::new(CacheStats::default()));
-        let debug = options.debug;
-        let param_pattern_default = Arc::new(options.param_pattern_default_regex());
+        let mut snapshot = Self::with_options(&tree.options);
+        snapshot.static_maps = copy_static_maps(tree);
+        snapshot.root = extract_root(&tree.root_node);
+        snapshot.preprocessor = tree.preprocessor.clone();
+        snapshot
+    }
 
+    /// Sets up usage counters for keys below `route_count` when `options` asks
+    /// for them.
+    pub(crate) fn enable_usage(&mut self, options: &RouterOptions, route_count: usize) {
+        self.usage = options
+            .track_usage
+            .then(|| Arc::new(UsageCounters::new(route_count)));
+    }
+
+    /// Empty snapshot configured from `options`, with a fresh route cache.
+    pub(crate) fn with_options(options: &RouterOptions) -> Self {
         RouterReadOnly {
-            static_maps,
-            root,
-            preprocessor,
-            cache,
-            cache_stats,
-            debug,
-            param_pattern_default,
+            static_maps: std::array::from_fn(|_| FastHashMap::default()),
+            root: ReadOnlyNode::default(),
+            hosts: None,
+            versions: None,
+            versioning: Arc::new(options.versioning.clone()),
+            middleware: Vec::new(),
+            variants: None,
+            preprocessor: Preprocessor::new(options.clone()),
+            routes: RouteTable::default(),
+            uncached: None,
+            usage: None,
+            lookups: options
+                .track_lookups
+                .then(|| Arc::new(LookupStats::default())),
+            generation: next_generation(),
+            cache: options
+                .cache
+                .enabled
+                .then(|| CacheStore::new(&options.cache)),
+            cache_stats: options
+                .cache
+                .enabled
+                .then(|| Arc::new(CacheStats::default())),
+            debug: options.debug,
+            param_pattern_default: Arc::new(options.param_pattern_default_regex()),
         }
     }
 
+    /// Preprocesses a lookup target, counting rejected ones.
+    fn preprocess(&self, target: &str) -> ReadOnlyResult<PreprocessOutcome> {
+        self.preprocessor.apply_target(target).map_err(|err| {
+            if let Some(lookups) = &self.lookups {
+                lookups.record(LookupOutcome::InvalidPath(&err));
+            }
+            ReadOnlyError::from(err)
+        })
+    }
+
+    /// Counts how a lookup of `outcome` ended. Misses are told apart from 405s by
+    /// the methods the path resolves under: `allowed` when the caller already has
+    /// them, say from a negative cache entry, worked out here otherwise.
+    fn record_lookup(
+        &self,
+        method: HttpMethod,
+        outcome: &PreprocessOutcome,
+        matched: bool,
+        allowed: Option<u32>,
+    ) {
+        let Some(lookups) = &self.lookups else {
+            return;
+        };
+        let result = if matched {
+            LookupOutcome::Matched
+        } else if allowed
+            .or_else(|| self.allowed_methods(outcome))
+            .is_some_and(|allowed| allowed & !(1 << method as usize) != 0)
+        {
+            LookupOutcome::MethodNotAllowed
+        } else {
+            LookupOutcome::NotFound
+        };
+        lookups.record(result);
+    }
+
+    /// Mask of the methods `outcome` resolves under, from the static maps and a
+    /// single tree walk. `None` when lookups are not tracked, as nothing reads it.
+    fn allowed_methods(&self, outcome: &PreprocessOutcome) -> Option<u32> {
+        self.lookups.as_ref()?;
+        let walked = allowed_methods(
+            &self.root,
+            outcome.normalized(),
+            &self.param_pattern_default,
+        );
+        Some(
+            HttpMethod::ALL
+                .into_iter()
+                .filter(|method| {
+                    self.find_static_normalized(*method, outcome.cache_key())
+                        .is_some()
+                })
+                .fold(walked, |mask, method| mask | 1 << method as usize),
+        )
+    }
+
     fn find_static_normalized(&self, method: HttpMethod, normalized: &str) -> Option<u16> {
         let idx = method as usize;
         self.static_maps[idx].get(normalized).cloned()
@@ -83,84 +183,641 @@ impl RouterReadOnly {
     pub fn find(&self, method: HttpMethod, path: &str) -> ReadOnlyResult<RouteMatch> {
         tracing::event!(tracing::Level::TRACE, operation="find", method=?method, path=%path);
 
-        let outcome = self.preprocessor.apply(path).map_err(ReadOnlyError::from)?;
-        let normalized = outcome.normalized();
-        let cache_key = outcome.cache_key();
-        let cache_lookup_key = self
-            .cache
-            .as_ref()
-            .map(|_| RouteCacheKey::new(method, cache_key.to_string()));
+        let outcome = self.preprocess(path)?;
+        self.find_preprocessed(method, &outcome, None)
+    }
+
+    /// Same as [`find`](Self::find), but returns the cached result itself: cache hits
+    /// share one allocation instead of cloning the parameter map.
+    pub fn find_shared(&self, method: HttpMethod, path: &str) -> ReadOnlyResult<Arc<RouteMatch>> {
+        let outcome = self.preprocess(path)?;
+        self.find_preprocessed_shared(method, &outcome, None)
+    }
+
+    /// Same as [`find`](Self::find), also evaluating the header conditions of routes
+    /// that share the matched path and method.
+    pub fn find_request<H: RequestHeaders>(
+        &self,
+        method: HttpMethod,
+        path: &str,
+        headers: &H,
+    ) -> ReadOnlyResult<RouteMatch> {
+        let outcome = self.preprocess(path)?;
+        self.find_preprocessed(method, &outcome, Some(headers))
+    }
+
+    /// Resolves a request target that may carry a query string and fragment. The
+    /// match is returned together with the raw query and its lazily parsed form.
+    pub fn find_target(&self, method: HttpMethod, target: &str) -> ReadOnlyResult<TargetMatch> {
+        let outcome = self.preprocess(target)?;
+        let route = self.find_preprocessed(method, &outcome, None)?;
+        Ok(TargetMatch::new(route, outcome.into_query()))
+    }
+
+    /// Resolves a batch of lookups against this snapshot, returning results in input
+    /// order. Requests sharing a method and normalized path are resolved once. The
+    /// cache is read once and written once per batch, grouped so each shard lock is
+    /// taken a single time per pass, and the tree walks share one parameter buffer.
+    #[tracing::instrument(skip_all, fields(count = requests.len()))]
+    pub fn find_many(&self, requests: &[(HttpMethod, &str)]) -> Vec<ReadOnlyResult<RouteMatch>> {
+        let outcomes: Vec<_> = requests
+            .iter()
+            .map(|(_, path)| self.preprocess(path))
+            .collect();
+
+        // Each distinct (method, cache key) pair is resolved once; `slots` maps every
+        // request onto its pair.
+        let mut unique: Vec<(HttpMethod, &PreprocessOutcome)> = Vec::new();
+        let slots: Vec<Option<usize>> = {
+            let mut index: FastHashMap<(HttpMethod, &str), usize> = FastHashMap::default();
+            requests
+                .iter()
+                .zip(outcomes.iter())
+                .map(|((method, _), outcome)| {
+                    let outcome = outcome.as_ref().ok()?;
+                    let slot = *index
+                        .entry((*method, outcome.cache_key()))
+                        .or_insert_with(|| {
+                            unique.push((*method, outcome));
+                            unique.len() - 1
+                        });
+                    Some(slot)
+                })
+                .collect()
+        };
+        let keys: Vec<(HttpMethod, &str)> = unique
+            .iter()
+            .map(|(method, outcome)| (*method, outcome.cache_key()))
+            .collect();
 
-        if let (Some(cache), Some(key)) = (self.cache.as_ref(), cache_lookup_key.as_ref()) {
-            let guard = cache.upgradable_read();
-            if let Some(hit) = guard.peek(key) {
+        let mut found: Vec<Option<Arc<RouteMatch>>> = vec![None; unique.len()];
+        let mut cached = vec![false; unique.len()];
+        let mut allowed: Vec<Option<u32>> = vec![None; unique.len()];
+        if let Some(cache) = self.cache.as_ref().map(CacheStore::local) {
+            for (idx, hit) in cache.get_many(&keys).into_iter().enumerate() {
                 if let Some(stats) = &self.cache_stats {
-                    stats.record_hit();
+                    stats.record_lookup(hit.as_ref());
                 }
-                if self.debug {
-                    tracing::event!(
-                        tracing::Level::DEBUG,
-                        cache = "hit",
-                        method = ?method,
-                        cache_key = %cache_key,
-                        "router cache hit"
-                    );
+                match hit {
+                    Some(CachedLookup::Found(shared)) => found[idx] = Some(shared),
+                    Some(CachedLookup::NotFound { allowed: mask }) => allowed[idx] = Some(mask),
+                    None => continue,
                 }
-                let mut guard = RwLockUpgradableReadGuard::upgrade(guard);
-                guard.touch(key);
-                return Ok(hit);
-            } else {
+                cached[idx] = true;
+            }
+        }
+
+        with_param_buffer(|buf| {
+            for (idx, (method, outcome)) in unique.iter().enumerate() {
+                if cached[idx] {
+                    continue;
+                }
+                let resolved = match self.find_static_normalized(*method, outcome.cache_key()) {
+                    Some(route_key) => Some((route_key, RouteParams::new())),
+                    None => {
+                        buf.clear();
+                        find_route(
+                            &self.root,
+                            *method,
+                            outcome.normalized(),
+                            buf,
+                            &self.param_pattern_default,
+                        )
+                    }
+                };
+                if resolved.is_none() {
+                    allowed[idx] = self.allowed_methods(outcome);
+                }
+                found[idx] = resolved.map(Arc::new);
+            }
+        });
+
+        if let Some(cache) = self.cache.as_ref().map(CacheStore::local) {
+            let misses = keys
+                .iter()
+                .zip(found.iter().zip(allowed.iter()))
+                .zip(cached.iter())
+                .filter(|(_, cached)| !**cached)
+                .filter_map(|(((method, cache_key), (found, allowed)), _)| {
+                    let lookup = self.cacheable(found.as_ref(), allowed.unwrap_or(0))?;
+                    Some((*method, *cache_key, lookup))
+                })
+                .collect();
+            for insert in cache.insert_many(misses) {
                 if let Some(stats) = &self.cache_stats {
-                    stats.record_miss();
+                    stats.record_insert(insert);
                 }
-                if self.debug {
-                    tracing::event!(
-                        tracing::Level::DEBUG,
-                        cache = "miss",
-                        method = ?method,
-                        cache_key = %cache_key,
-                        "router cache miss"
-                    );
+            }
+        }
+
+        let resolved: Vec<_> = slots
+            .into_iter()
+            .map(|slot| slot.map(|slot| (found[slot].clone(), cached[slot], allowed[slot])))
+            .collect();
+        requests
+            .iter()
+            .zip(outcomes)
+            .zip(resolved)
+            .map(|(((method, _), outcome), resolved)| {
+                let outcome = outcome?;
+                let (found, cached, allowed) = resolved.expect("preprocessed requests have a slot");
+                let selected = found.and_then(|shared| {
+                    let key = self.select_variant(shared.0, &outcome, None)?;
+                    self.record_usage(key, cached);
+                    Some(Arc::unwrap_or_clone(with_key(shared, key)))
+                });
+                self.record_lookup(*method, &outcome, selected.is_some(), allowed);
+                selected.ok_or_else(|| ReadOnlyError::RouteNotFound {
+                    method: *method,
+                    path: outcome.normalized().to_string(),
+                })
+            })
+            .collect()
+    }
+
+    fn find_preprocessed(
+        &self,
+        method: HttpMethod,
+        outcome: &PreprocessOutcome,
+        headers: Option<&dyn RequestHeaders>,
+    ) -> ReadOnlyResult<RouteMatch> {
+        self.find_preprocessed_shared(method, outcome, headers)
+            .map(Arc::unwrap_or_clone)
+    }
+
+    fn find_preprocessed_shared(
+        &self,
+        method: HttpMethod,
+        outcome: &PreprocessOutcome,
+        headers: Option<&dyn RequestHeaders>,
+    ) -> ReadOnlyResult<Arc<RouteMatch>> {
+        let mut allowed = None;
+        let selected = match self.find_slot(method, outcome) {
+            Ok((shared, cached)) => self.select_variant(shared.0, outcome, headers).map(|key| {
+                self.record_usage(key, cached);
+                with_key(shared, key)
+            }),
+            Err(mask) => {
+                allowed = mask;
+                None
+            }
+        };
+        self.record_lookup(method, outcome, selected.is_some(), allowed);
+        selected.ok_or_else(|| ReadOnlyError::RouteNotFound {
+            method,
+            path: outcome.normalized().to_string(),
+        })
+    }
+
+    /// Resolves the tree slot for `outcome`; cached results are slot keys, before
+    /// any query-condition variant is picked. The flag tells whether the result came
+    /// from the cache. A miss carries the mask of methods the path resolves under,
+    /// when lookups are tracked.
+    fn find_slot(
+        &self,
+        method: HttpMethod,
+        outcome: &PreprocessOutcome,
+    ) -> Result<(Arc<RouteMatch>, bool), Option<u32>> {
+        let cache_key = outcome.cache_key();
+
+        if let Some(cache) = self.cache.as_ref().map(CacheStore::local) {
+            let hit = cache.get(method, cache_key);
+            if let Some(stats) = &self.cache_stats {
+                stats.record_lookup(hit.as_ref());
+            }
+            if self.debug {
+                tracing::event!(
+                    tracing::Level::DEBUG,
+                    cache = match hit {
+                        Some(CachedLookup::Found(_)) => "hit",
+                        Some(CachedLookup::NotFound { .. }) => "negative",
+                        None => "miss",
+                    },
+                    method = ?method,
+                    cache_key = %cache_key,
+                    "router cache lookup"
+                );
+            }
+            match hit {
+                Some(CachedLookup::Found(shared)) => return Ok((shared, true)),
+                Some(CachedLookup::NotFound { allowed }) => return Err(Some(allowed)),
+                None => {}
+            }
+        }
+
+        let found = self.resolve_slot(method, outcome).map(Arc::new);
+        let allowed = match found {
+            Some(_) => None,
+            None => self.allowed_methods(outcome),
+        };
+        if let Some(cache) = self.cache.as_ref().map(CacheStore::local) {
+            self.store(
+                &cache,
+                method,
+                cache_key,
+                found.as_ref(),
+                allowed.unwrap_or(0),
+            );
+        }
+        found.map(|shared| (shared, false)).ok_or(allowed)
+    }
+
+    fn record_usage(&self, key: u16, cached: bool) {
+        if let Some(usage) = &self.usage {
+            usage.record(key, cached);
+        }
+    }
+
+    /// Static map, then tree walk; never consults the cache.
+    fn resolve_slot(&self, method: HttpMethod, outcome: &PreprocessOutcome) -> Option<RouteMatch> {
+        match self.find_static_normalized(method, outcome.cache_key()) {
+            Some(route_key) => Some((route_key, RouteParams::new())),
+            None => with_param_buffer(|buf| {
+                find_route(
+                    &self.root,
+                    method,
+                    outcome.normalized(),
+                    buf,
+                    &self.param_pattern_default,
+                )
+            }),
+        }
+    }
+
+    /// Pre-populates the cache with `paths`, such as a list exported by
+    /// [`hot_set`](Self::hot_set) before the last deploy. Warm-up lookups are not
+    /// counted in the cache metrics. With the cache disabled the paths are still
+    /// resolved, so the report lists the ones that no longer match.
+    pub fn warm_cache<I, S>(&self, paths: I) -> WarmReport
+    where
+        I: IntoIterator<Item = (HttpMethod, S)>,
+        S: AsRef<str>,
+    {
+        let cache = self.cache.as_ref().map(CacheStore::local);
+        let mut report = WarmReport::default();
+        for (method, path) in paths {
+            let path = path.as_ref();
+            let outcome = match self.preprocessor.apply_target(path) {
+                Ok(outcome) => outcome,
+                Err(err) => {
+                    report.failed.push(WarmFailure {
+                        method,
+                        path: path.to_string(),
+                        error: err.into(),
+                    });
+                    continue;
                 }
+            };
+            if let Some(CachedLookup::Found(_)) = cache
+                .as_deref()
+                .and_then(|cache| cache.peek(method, outcome.cache_key()))
+            {
+                report.already_cached += 1;
+                continue;
+            }
+
+            let found = self.resolve_slot(method, &outcome).map(Arc::new);
+            let allowed = match found {
+                Some(_) => None,
+                None => self.allowed_methods(&outcome),
+            };
+            let stored = cache.as_deref().is_some_and(|cache| {
+                self.store(
+                    cache,
+                    method,
+                    outcome.cache_key(),
+                    found.as_ref(),
+                    allowed.unwrap_or(0),
+                )
+            });
+            match found {
+                Some(_) if stored => report.warmed += 1,
+                Some(_) => report.skipped += 1,
+                None => report.failed.push(WarmFailure {
+                    method,
+                    path: path.to_string(),
+                    error: ReadOnlyError::RouteNotFound {
+                        method,
+                        path: outcome.normalized().to_string(),
+                    },
+                }),
             }
         }
+        report
+    }
 
-        if let Some(route_key) = self.find_static_normalized(method, cache_key) {
-            let result = (route_key, RouteParams::new());
-            if let (Some(cache), Some(key)) = (self.cache.as_ref(), cache_lookup_key.as_ref()) {
-                cache.write().insert(key.clone(), result.clone());
+    /// Method and normalized path of every cached match, recently used ones first,
+    /// ready to feed into [`warm_cache`](Self::warm_cache) on the next deploy. Empty
+    /// when the cache is disabled. A per-thread cache reports the union of every
+    /// live thread's entries.
+    pub fn hot_set(&self) -> Vec<(HttpMethod, String)> {
+        self.cache
+            .as_ref()
+            .map(CacheStore::hot_keys)
+            .unwrap_or_default()
+    }
+
+    fn is_uncached(&self, key: u16) -> bool {
+        self.uncached
+            .as_deref()
+            .is_some_and(|uncached| uncached.get(key as usize).copied().unwrap_or(false))
+    }
+
+    /// The cache entry for a tree walk's outcome, or `None` when the matched route
+    /// opted out of caching. `allowed` is kept with a miss; see
+    /// [`CachedLookup::NotFound`].
+    fn cacheable(&self, found: Option<&Arc<RouteMatch>>, allowed: u32) -> Option<CachedLookup> {
+        match found {
+            Some(shared) if self.is_uncached(shared.0) => None,
+            Some(shared) => Some(CachedLookup::Found(shared.clone())),
+            None => Some(CachedLookup::NotFound { allowed }),
+        }
+    }
+
+    /// Caches the outcome of a tree walk unless the matched route opted out,
+    /// counting evictions and results the admission policy turns away. Returns
+    /// whether the result was stored.
+    fn store(
+        &self,
+        cache: &RouteCache,
+        method: HttpMethod,
+        cache_key: &str,
+        found: Option<&Arc<RouteMatch>>,
+        allowed: u32,
+    ) -> bool {
+        let Some(lookup) = self.cacheable(found, allowed) else {
+            return false;
+        };
+        let insert = cache.insert(method, cache_key, lookup);
+        if let Some(stats) = &self.cache_stats {
+            stats.record_insert(insert);
+        }
+        matches!(insert, CacheInsert::Stored | CacheInsert::Evicted)
+    }
+
+    /// Resolves `target` like [`find_target`](Self::find_target) while recording
+    /// each step. Leaves the cache untouched, so it is safe to call for a single
+    /// request (say, one carrying a debug header) next to the real lookup.
+    pub fn explain(&self, method: HttpMethod, target: &str) -> MatchTrace {
+        let mut trace = MatchTrace {
+            method,
+            target: target.to_string(),
+            query: None,
+            normalization: self.preprocessor.stages(target),
+            steps: Vec::new(),
+            decision: Decision::NotFound,
+        };
+        let outcome = match self.preprocessor.apply_target(target) {
+            Ok(outcome) => outcome,
+            Err(err) => {
+                trace.decision = Decision::InvalidPath {
+                    reason: err.to_string(),
+                };
+                return trace;
             }
-            return Ok(result);
+        };
+        trace.query = outcome.query().map(str::to_string);
+
+        let found = self.explain_slot(method, &outcome, &mut trace.steps);
+        let Some((slot, params, source)) = found else {
+            return trace;
+        };
+        let key = match self.variants.as_deref() {
+            Some(variants) if variants.contains(slot) => {
+                let selected = self.select_variant(slot, &outcome, None);
+                trace.steps.push(TraceStep::Variant { slot, selected });
+                selected
+            }
+            _ => Some(slot),
+        };
+        if let Some(key) = key {
+            trace.decision = Decision::Matched {
+                key,
+                params,
+                source,
+            };
+        }
+        trace
+    }
+
+    fn explain_slot(
+        &self,
+        method: HttpMethod,
+        outcome: &PreprocessOutcome,
+        steps: &mut Vec<TraceStep>,
+    ) -> Option<(u16, RouteParams, MatchSource)> {
+        if let Some(cache) = self.cache.as_ref().map(CacheStore::local) {
+            match cache.peek(method, outcome.cache_key()) {
+                Some(CachedLookup::Found(shared)) => {
+                    steps.push(TraceStep::Cache {
+                        hit: Some(shared.0),
+                    });
+                    let (key, params) = Arc::unwrap_or_clone(shared);
+                    return Some((key, params, MatchSource::Cache));
+                }
+                Some(CachedLookup::NotFound { .. }) => {
+                    steps.push(TraceStep::NegativeCache);
+                    return None;
+                }
+                None => steps.push(TraceStep::Cache { hit: None }),
+            }
+        }
+
+        let hit = self.find_static_normalized(method, outcome.cache_key());
+        steps.push(TraceStep::StaticMap { hit });
+        if let Some(key) = hit {
+            return Some((key, RouteParams::new(), MatchSource::StaticMap));
+        }
+
+        trace_route(
+            &self.root,
+            method,
+            outcome.normalized(),
+            &self.param_pattern_default,
+            steps,
+        )
+        .map(|(key, params)| (key, params, MatchSource::Tree))
+    }
+
+    fn select_variant(
+        &self,
+        slot: u16,
+        outcome: &PreprocessOutcome,
+        headers: Option<&dyn RequestHeaders>,
+    ) -> Option<u16> {
+        match self.variants.as_ref() {
+            Some(variants) => variants.select(slot, outcome.query(), headers),
+            None => Some(slot),
         }
+    }
+
+    /// Same as [`find`](Self::find), also returning the middleware chain composed for
+    /// the matched route at seal time.
+    pub fn find_with_middleware(
+        &self,
+        method: HttpMethod,
+        path: &str,
+    ) -> ReadOnlyResult<(RouteMatch, MiddlewareChain)> {
+        let found = self.find(method, path)?;
+        let chain = self.middleware_chain(found.0);
+        Ok((found, chain))
+    }
+
+    /// Middleware chain of the route with `key`; empty when none applies.
+    pub fn middleware(&self, key: u16) -> &[String] {
+        self.middleware
+            .get(key as usize)
+            .map(|chain| chain.as_ref())
+            .unwrap_or(&[])
+    }
+
+    fn middleware_chain(&self, key: u16) -> MiddlewareChain {
+        self.middleware
+            .get(key as usize)
+            .cloned()
+            .unwrap_or_else(|| Arc::from(Vec::new()))
+    }
 
-        let found = with_param_buffer(|buf| {
-            find_route(
-                &self.root,
+    /// Matches `path` against the routes of every host pattern that accepts `host`,
+    /// most specific pattern first, falling back to host-agnostic routes when no
+    /// scoped route matches. Host-scoped matches bypass the route cache, whose key
+    /// has no host, so they are not counted in
+    /// [`cache_counters`](Self::cache_counters); the fallback goes through
+    /// [`find`](Self::find) and is cached as usual.
+    pub fn find_host(
+        &self,
+        method: HttpMethod,
+        host: &str,
+        path: &str,
+    ) -> ReadOnlyResult<RouteMatch> {
+        if let Some(hosts) = self.hosts.as_ref() {
+            let outcome = self.preprocess(path)?;
+            if let Some((slot, params)) = hosts.find(
                 method,
-                normalized,
-                buf,
+                host,
+                outcome.normalized(),
                 &self.param_pattern_default,
+            ) && let Some(key) = self.select_variant(slot, &outcome, None)
+            {
+                self.record_usage(key, false);
+                self.record_lookup(method, &outcome, true, None);
+                return Ok((key, params));
+            }
+        }
+        self.find(method, path)
+    }
+
+    /// Resolves `path` against the versioned route sets. The requested version is
+    /// read from the sources in [`VersioningOptions`]; the newest registered version
+    /// not above it that has a matching route wins, and unversioned routes are the
+    /// last resort.
+    pub fn find_versioned<H: RequestHeaders>(
+        &self,
+        method: HttpMethod,
+        path: &str,
+        headers: &H,
+    ) -> ReadOnlyResult<VersionMatch> {
+        let (requested, stripped) = resolve_version(&self.versioning, path, headers);
+        let outcome = self.preprocess(&stripped)?;
+
+        let versions = self.versions.as_deref();
+        let requested = requested.or_else(|| versions.and_then(VersionSnapshot::latest));
+        if let (Some(versions), Some(max)) = (versions, requested)
+            && let Some((version, route)) = versions.find(
+                method,
+                outcome.normalized(),
+                max,
+                &self.param_pattern_default,
+                |slot| self.select_variant(slot, &outcome, Some(headers)),
             )
-        });
+        {
+            self.record_usage(route.0, false);
+            self.record_lookup(method, &outcome, true, None);
+            return Ok(VersionMatch {
+                route,
+                requested,
+                version: Some(version),
+            });
+        }
 
-        if let Some((route_key, params)) = found {
-            if let (Some(cache), Some(key)) = (self.cache.as_ref(), cache_lookup_key.as_ref()) {
-                cache
-                    .write()
-                    .insert(key.clone(), (route_key, params.clone()));
-            }
-            Ok((route_key, params))
-        } else {
-            Err(ReadOnlyError::RouteNotFound {
+        let route = self.find_preprocessed(method, &outcome, Some(headers))?;
+        Ok(VersionMatch {
+            route,
+            requested,
+            version: None,
+        })
+    }
+
+    /// Resolves `path` ignoring the ASCII case of static segments and returns the
+    /// registered spelling, suitable for a redirect. Parameter values are kept as sent.
+    pub fn find_corrected(&self, method: HttpMethod, path: &str) -> ReadOnlyResult<String> {
+        let outcome = self
+            .preprocessor
+            .apply_target(path)
+            .map_err(ReadOnlyError::from)?;
+        let normalized = outcome.normalized();
+
+        match find_corrected_path(&self.root, method, normalized, &self.param_pattern_default) {
+            Some((_, corrected)) => Ok(corrected),
+            None => Err(ReadOnlyError::RouteNotFound {
                 method,
                 path: normalized.to_string(),
-            })
+            }),
         }
     }
 
+    pub fn hosts(&self) -> Option<&HostSnapshot> {
+        self.hosts.as_deref()
+    }
+
+    /// Every registered route.
+    pub fn routes(&self) -> &RouteTable {
+        &self.routes
+    }
+
+    pub fn versions(&self) -> Option<&VersionSnapshot> {
+        self.versions.as_deref()
+    }
+
     pub fn cache_metrics(&self) -> Option<(u64, u64)> {
         self.cache_stats.as_ref().map(|stats| stats.snapshot())
     }
+
+    /// Full cache counters, including negative hits and admission rejections;
+    /// `None` when the cache is disabled.
+    pub fn cache_counters(&self) -> Option<CacheCounters> {
+        self.cache_stats.as_ref().map(|stats| stats.counters())
+    }
+
+    /// Hits of every route since the snapshot was sealed or loaded; `None` unless
+    /// [`track_usage`](crate::RouterOptionsBuilder::track_usage) is on.
+    /// [`UsageReport::never_hit`] lists candidates for deletion.
+    pub fn usage_report(&self) -> Option<UsageReport> {
+        self.usage.as_ref().map(|usage| usage.report(&self.routes))
+    }
+
+    /// Lookup outcomes since the snapshot was sealed or loaded; `None` unless
+    /// [`track_lookups`](crate::RouterOptionsBuilder::track_lookups) is on.
+    pub fn lookup_counters(&self) -> Option<LookupCounters> {
+        self.lookups.as_ref().map(|lookups| lookups.counters())
+    }
+
+    /// Process-wide sequence number of this snapshot, shared by its clones. A newer
+    /// snapshot, sealed or loaded later, always has a higher generation.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Number of route keys, variants included.
+    pub fn route_count(&self) -> usize {
+        self.routes.len().max(self.middleware.len())
+    }
+
+    /// Number of cached lookups; `None` when the cache is disabled. With a
+    /// per-thread cache this sums the entries of every live thread.
+    pub fn cache_entries(&self) -> Option<usize> {
+        self.cache.as_ref().map(CacheStore::entries)
+    }
 }
 
 impl Clone for RouterReadOnly {
@@ -168,7 +825,17 @@ impl Clone for RouterReadOnly {
         Self {
             static_maps: self.static_maps.clone(),
             root: self.root.clone(),
+            hosts: self.hosts.clone(),
+            versions: self.versions.clone(),
+            versioning: self.versioning.clone(),
+            middleware: self.middleware.clone(),
+            variants: self.variants.clone(),
             preprocessor: self.preprocessor.clone(),
+            routes: self.routes.clone(),
+            uncached: self.uncached.clone(),
+            usage: self.usage.clone(),
+            lookups: self.lookups.clone(),
+            generation: self.generation,
             cache: self.cache.clone(),
             cache_stats: self.cache_stats.clone(),
             debug: self.debug,
@@ -182,10 +849,18 @@ impl Default for RouterReadOnly {
         Self {
             static_maps: std::array::from_fn(|_| FastHashMap::default()),
             root: ReadOnlyNode::default(),
+            hosts: None,
+            versions: None,
+            versioning: Arc::new(VersioningOptions::default()),
+            middleware: Vec::new(),
+            variants: None,
             preprocessor: Preprocessor::default(),
-            cache: Some(Arc::new(RwLock::new(RouteCache::new(
-                DEFAULT_CACHE_CAPACITY,
-            )))),
+            routes: RouteTable::default(),
+            uncached: None,
+            usage: None,
+            lookups: None,
+            generation: next_generation(),
+            cache: Some(CacheStore::new(&CacheOptions::default())),
             cache_stats: Some(Arc::new(CacheStats::default())),
             debug: false,
             param_pattern_default: Arc::new(
@@ -204,3 +879,18 @@ pub struct ReadOnlyNode {
     pub(crate) static_children: FastHashMap<Box<str>, ReadOnlyNode>,
     pub(crate) patterns: Vec<(SegmentPattern, ReadOnlyNode)>,
 }
+
+/// `shared` with its route key replaced by `key`; reuses the allocation when the key
+/// is unchanged.
+fn with_key(shared: Arc<RouteMatch>, key: u16) -> Arc<RouteMatch> {
+    if shared.0 == key {
+        return shared;
+    }
+    Arc::new((key, shared.1.clone()))
+}
+
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn next_generation() -> u64 {
+    GENERATION.fetch_add(1, Ordering::Relaxed) + 1
+}
@@ -0,0 +1,40 @@
+use crate::query::{QueryParams, parse_query};
+use crate::types::RouteMatch;
+use std::sync::OnceLock;
+
+/// Result of resolving a full request target such as `/search?q=a%2Fb#top`.
+#[derive(Debug, Clone)]
+pub struct TargetMatch {
+    pub route: RouteMatch,
+    query: Option<String>,
+    query_params: OnceLock<QueryParams>,
+}
+
+impl TargetMatch {
+    pub(crate) fn new(route: RouteMatch, query: Option<String>) -> Self {
+        Self {
+            route,
+            query,
+            query_params: OnceLock::new(),
+        }
+    }
+
+    pub fn key(&self) -> u16 {
+        self.route.0
+    }
+
+    /// Raw query string as sent, without the leading `?`.
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    /// Percent-decoded query parameters, parsed on first access.
+    pub fn query_params(&self) -> &QueryParams {
+        self.query_params
+            .get_or_init(|| self.query.as_deref().map(parse_query).unwrap_or_default())
+    }
+
+    pub fn into_route(self) -> RouteMatch {
+        self.route
+    }
+}
@@ -0,0 +1,109 @@
+use super::routes::RouteTable;
+use crate::enums::HttpMethod;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Per-route-key hit counters of a sealed snapshot, bumped with relaxed atomics.
+/// Hit times come from the monotonic clock, as milliseconds since the counters
+/// were created, and are turned into wall-clock times only when reported.
+#[derive(Debug)]
+pub(crate) struct UsageCounters {
+    routes: Box<[RouteCounter]>,
+    started: Instant,
+    started_at: SystemTime,
+}
+
+#[derive(Debug, Default)]
+struct RouteCounter {
+    hits: AtomicU64,
+    cache_hits: AtomicU64,
+    /// One more than the milliseconds from `started` to the latest hit; zero until
+    /// the first hit.
+    last_hit_ms: AtomicU64,
+}
+
+impl UsageCounters {
+    pub(crate) fn new(route_count: usize) -> Self {
+        Self {
+            routes: (0..route_count).map(|_| RouteCounter::default()).collect(),
+            started: Instant::now(),
+            started_at: SystemTime::now(),
+        }
+    }
+
+    pub(crate) fn record(&self, key: u16, cached: bool) {
+        let Some(counter) = self.routes.get(key as usize) else {
+            return;
+        };
+        counter.hits.fetch_add(1, Ordering::Relaxed);
+        if cached {
+            counter.cache_hits.fetch_add(1, Ordering::Relaxed);
+        }
+        // Only the first hit of each millisecond writes the timestamp.
+        let now = self.started.elapsed().as_millis() as u64 + 1;
+        if counter.last_hit_ms.load(Ordering::Relaxed) < now {
+            counter.last_hit_ms.store(now, Ordering::Relaxed);
+        }
+    }
+
+    /// Reads every counter; method and path come from `routes`.
+    pub(crate) fn report(&self, routes: &RouteTable) -> UsageReport {
+        let routes = self
+            .routes
+            .iter()
+            .enumerate()
+            .map(|(key, counter)| {
+                let key = key as u16;
+                let record = routes.get(key);
+                let last_hit_ms = counter.last_hit_ms.load(Ordering::Relaxed);
+                RouteUsage {
+                    key,
+                    method: record.map(|record| record.method),
+                    path: record.map(|record| record.path.clone()),
+                    hits: counter.hits.load(Ordering::Relaxed),
+                    cache_hits: counter.cache_hits.load(Ordering::Relaxed),
+                    last_hit: last_hit_ms
+                        .checked_sub(1)
+                        .map(|elapsed| self.started_at + Duration::from_millis(elapsed)),
+                }
+            })
+            .collect();
+        UsageReport { routes }
+    }
+}
+
+/// Hit counts of one route. `method` and `path` are `None` for keys without a
+/// registered route.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteUsage {
+    pub key: u16,
+    pub method: Option<HttpMethod>,
+    pub path: Option<String>,
+    pub hits: u64,
+    /// Hits answered from the route cache, included in `hits`.
+    pub cache_hits: u64,
+    /// Time of the latest hit, to the millisecond.
+    pub last_hit: Option<SystemTime>,
+}
+
+/// Point-in-time usage of every route, ordered by key.
+#[derive(Debug, Clone, Default)]
+pub struct UsageReport {
+    routes: Vec<RouteUsage>,
+}
+
+impl UsageReport {
+    pub fn routes(&self) -> &[RouteUsage] {
+        &self.routes
+    }
+
+    pub fn get(&self, key: u16) -> Option<&RouteUsage> {
+        self.routes.get(key as usize)
+    }
+
+    /// Routes that have not matched a single request since the snapshot was sealed
+    /// or loaded.
+    pub fn never_hit(&self) -> impl Iterator<Item = &RouteUsage> {
+        self.routes.iter().filter(|usage| usage.hits == 0)
+    }
+}
@@ -0,0 +1,114 @@
+use crate::query::{CompiledQueryCondition, QueryParams, parse_query};
+use crate::registry::{RouteVariant, VariantTable};
+use crate::request::{CompiledHeaderCondition, RequestHeaders};
+use hashbrown::HashMap as FastHashMap;
+
+#[derive(Debug, Clone)]
+pub(crate) struct CompiledVariant {
+    source: RouteVariant,
+    query: Vec<CompiledQueryCondition>,
+    headers: Vec<CompiledHeaderCondition>,
+}
+
+impl CompiledVariant {
+    pub(crate) fn compile(source: RouteVariant) -> Result<Self, regex::Error> {
+        let query = source
+            .query
+            .iter()
+            .map(|cond| cond.compile())
+            .collect::<Result<_, _>>()?;
+        let headers = source
+            .headers
+            .iter()
+            .map(|cond| cond.compile())
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            source,
+            query,
+            headers,
+        })
+    }
+
+    pub(crate) fn source(&self) -> &RouteVariant {
+        &self.source
+    }
+
+    fn accepts(&self, params: &QueryParams, headers: Option<&dyn RequestHeaders>) -> bool {
+        self.query.iter().all(|cond| cond.is_satisfied(params))
+            && match headers {
+                Some(headers) => self.headers.iter().all(|cond| cond.is_satisfied(headers)),
+                None => self.headers.is_empty(),
+            }
+    }
+}
+
+/// Sealed form of [`VariantTable`]: candidates of each slot in evaluation order.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct VariantSnapshot {
+    groups: FastHashMap<u16, Vec<CompiledVariant>>,
+}
+
+impl VariantSnapshot {
+    /// Orders candidates by specificity, then priority, then registration order;
+    /// the unconditional fallback, if any, always comes last. Returns `None` when
+    /// no slot has variants.
+    pub(crate) fn from_table(table: &VariantTable) -> Option<Self> {
+        if table.is_empty() {
+            return None;
+        }
+
+        let mut groups = FastHashMap::new();
+        for (slot, variants) in table.groups() {
+            let mut ordered: Vec<_> = variants.iter().enumerate().collect();
+            ordered.sort_by(|(ia, a), (ib, b)| {
+                b.specificity()
+                    .cmp(&a.specificity())
+                    .then(b.priority.cmp(&a.priority))
+                    .then(ia.cmp(ib))
+            });
+            let compiled = ordered
+                .into_iter()
+                .map(|(_, variant)| {
+                    CompiledVariant::compile(variant.clone())
+                        .expect("route conditions are validated on registration")
+                })
+                .collect();
+            groups.insert(slot, compiled);
+        }
+        Some(Self { groups })
+    }
+
+    /// Builds a snapshot from groups already in evaluation order.
+    pub(crate) fn from_ordered(groups: FastHashMap<u16, Vec<CompiledVariant>>) -> Self {
+        Self { groups }
+    }
+
+    pub(crate) fn groups(&self) -> impl Iterator<Item = (u16, &[CompiledVariant])> {
+        self.groups
+            .iter()
+            .map(|(slot, variants)| (*slot, variants.as_slice()))
+    }
+
+    pub(crate) fn contains(&self, slot: u16) -> bool {
+        self.groups.contains_key(&slot)
+    }
+
+    /// Picks the route for a matched slot. Slots without variants resolve to
+    /// themselves; the query string is only parsed when the slot has a group.
+    /// Without `headers`, variants with header conditions never match.
+    pub(crate) fn select(
+        &self,
+        slot: u16,
+        query: Option<&str>,
+        headers: Option<&dyn RequestHeaders>,
+    ) -> Option<u16> {
+        let Some(group) = self.groups.get(&slot) else {
+            return Some(slot);
+        };
+        let params = query.map(parse_query).unwrap_or_default();
+        group
+            .iter()
+            .find(|variant| variant.accepts(&params, headers))
+            .map(|variant| variant.source.key)
+    }
+}
@@ -0,0 +1,105 @@
+use super::converter::{copy_static_maps, extract_root};
+use super::snapshot::ReadOnlyNode;
+use crate::enums::HttpMethod;
+use crate::matcher::{find_route, with_param_buffer};
+use crate::radix::HTTP_METHOD_COUNT;
+use crate::registry::VersionTable;
+use crate::types::{RouteMatch, RouteParams};
+use hashbrown::HashMap as FastHashMap;
+use regex::Regex;
+
+/// Result of a versioned lookup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionMatch {
+    pub route: RouteMatch,
+    /// Version the request asked for, after defaults were applied.
+    pub requested: Option<u32>,
+    /// Version of the route that matched; `None` for an unversioned route.
+    pub version: Option<u32>,
+}
+
+impl VersionMatch {
+    pub fn key(&self) -> u16 {
+        self.route.0
+    }
+}
+
+/// Sealed form of [`VersionTable`], newest version first.
+#[derive(Debug, Clone, Default)]
+pub struct VersionSnapshot {
+    pub(crate) sets: Vec<VersionSetSnapshot>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct VersionSetSnapshot {
+    pub(crate) version: u32,
+    pub(crate) static_maps: [FastHashMap<Box<str>, u16>; HTTP_METHOD_COUNT],
+    pub(crate) root: ReadOnlyNode,
+}
+
+impl VersionSetSnapshot {
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn find(
+        &self,
+        method: HttpMethod,
+        normalized: &str,
+        default_param_pattern: &Regex,
+    ) -> Option<RouteMatch> {
+        if let Some(&route_key) = self.static_maps[method as usize].get(normalized) {
+            return Some((route_key, RouteParams::new()));
+        }
+        with_param_buffer(|buf| {
+            find_route(&self.root, method, normalized, buf, default_param_pattern)
+        })
+    }
+}
+
+impl VersionSnapshot {
+    pub(crate) fn from_table(table: &VersionTable) -> Option<Self> {
+        if table.is_empty() {
+            return None;
+        }
+
+        let mut sets: Vec<_> = table
+            .trees()
+            .map(|(version, tree)| VersionSetSnapshot {
+                version,
+                static_maps: copy_static_maps(tree),
+                root: extract_root(&tree.root_node),
+            })
+            .collect();
+        sets.reverse();
+        Some(Self { sets })
+    }
+
+    pub fn sets(&self) -> &[VersionSetSnapshot] {
+        &self.sets
+    }
+
+    pub fn latest(&self) -> Option<u32> {
+        self.sets.first().map(|set| set.version)
+    }
+
+    /// Tries every version up to `max`, newest first. `accept` maps a matched slot
+    /// to the final route key and may reject it, in which case older versions are
+    /// tried.
+    pub(crate) fn find(
+        &self,
+        method: HttpMethod,
+        normalized: &str,
+        max: u32,
+        default_param_pattern: &Regex,
+        mut accept: impl FnMut(u16) -> Option<u16>,
+    ) -> Option<(u32, RouteMatch)> {
+        self.sets
+            .iter()
+            .filter(|set| set.version <= max)
+            .find_map(|set| {
+                let (slot, params) = set.find(method, normalized, default_param_pattern)?;
+                accept(slot).map(|key| (set.version, (key, params)))
+            })
+    }
+}
@@ -0,0 +1,28 @@
+use super::ReadOnlyError;
+use crate::enums::HttpMethod;
+
+/// Outcome of [`RouterReadOnly::warm_cache`](super::RouterReadOnly::warm_cache).
+/// `warmed` counts paths resolved and stored, `already_cached` paths found in the
+/// cache as they were, and `skipped` paths that resolved but were kept out by the
+/// route's cache policy or the admission policy.
+#[derive(Debug, Default)]
+pub struct WarmReport {
+    pub warmed: usize,
+    pub already_cached: usize,
+    pub skipped: usize,
+    pub failed: Vec<WarmFailure>,
+}
+
+impl WarmReport {
+    pub fn is_clean(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// A warm-up path that did not resolve, as it was given.
+#[derive(Debug)]
+pub struct WarmFailure {
+    pub method: HttpMethod,
+    pub path: String,
+    pub error: ReadOnlyError,
+}
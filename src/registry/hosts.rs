@@ -0,0 +1,148 @@
+use crate::enums::HttpMethod;
+use crate::host::{HostError, HostResult, host_pattern_path};
+use crate::radix::RadixTree;
+use crate::router::RouterOptions;
+use hashbrown::HashMap as FastHashMap;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU16;
+
+/// Host patterns and the path tree registered under each of them.
+///
+/// Host patterns live in their own radix tree keyed by reversed labels; the key a
+/// pattern receives there is the index of its scope in `scopes`.
+#[derive(Debug)]
+pub struct HostTable {
+    patterns: RadixTree,
+    scopes: Vec<HostScope>,
+    index: FastHashMap<String, usize>,
+    options: RouterOptions,
+    route_keys: Arc<AtomicU16>,
+}
+
+#[derive(Debug)]
+pub struct HostScope {
+    pub host: String,
+    /// This scope's host pattern alone, used to try it after the best-ranked
+    /// scope in `patterns` has no route for the path.
+    pub pattern: RadixTree,
+    pub tree: RadixTree,
+}
+
+impl HostTable {
+    pub fn new(options: RouterOptions, route_keys: Arc<AtomicU16>) -> Self {
+        Self {
+            patterns: RadixTree::new(host_pattern_options()),
+            scopes: Vec::new(),
+            index: FastHashMap::new(),
+            options,
+            route_keys,
+        }
+    }
+
+    pub fn insert(&mut self, host: &str, method: HttpMethod, path: &str) -> HostResult<u16> {
+        let host_path = host_pattern_path(host)?;
+        let scope_idx = self.scope_index(host, &host_path)?;
+        let host_params = self.patterns.param_names(&host_path)?;
+        let scope = &mut self.scopes[scope_idx];
+
+        let path_params = scope.tree.param_names(path)?;
+        if let Some(param) = path_params.iter().find(|name| {
+            name.as_str() != "*" && host_params.iter().any(|host_name| host_name == *name)
+        }) {
+            return Err(HostError::ParamConflict {
+                host: host.to_string(),
+                path: path.to_string(),
+                param: param.clone(),
+            });
+        }
+
+        Ok(scope.tree.insert(method, path)?)
+    }
+
+    /// Dry run of [`insert`](Self::insert): reports the error it would return
+    /// without adding the host pattern or the route.
+    pub fn check(&self, host: &str, method: HttpMethod, path: &str) -> HostResult<()> {
+        let host_path = host_pattern_path(host)?;
+        let scope = self.index.get(&host_path).map(|&idx| &self.scopes[idx]);
+        let host_params = match scope {
+            Some(scope) => scope.pattern.param_names(&host_path)?,
+            None => {
+                self.patterns.check_insert(HttpMethod::Get, &host_path)?;
+                self.patterns.param_names(&host_path)?
+            }
+        };
+
+        let tree = match scope {
+            Some(scope) => &scope.tree,
+            None => &RadixTree::new(self.options.clone()),
+        };
+        let path_params = tree.param_names(path)?;
+        if let Some(param) = path_params.iter().find(|name| {
+            name.as_str() != "*" && host_params.iter().any(|host_name| host_name == *name)
+        }) {
+            return Err(HostError::ParamConflict {
+                host: host.to_string(),
+                path: path.to_string(),
+                param: param.clone(),
+            });
+        }
+
+        Ok(tree.check_insert(method, path)?)
+    }
+
+    pub fn finalize(&mut self) {
+        if self.scopes.is_empty() {
+            return;
+        }
+        self.patterns.finalize();
+        for scope in self.scopes.iter_mut() {
+            scope.pattern.finalize();
+            scope.tree.finalize();
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scopes.is_empty()
+    }
+
+    pub fn patterns(&self) -> &RadixTree {
+        &self.patterns
+    }
+
+    pub fn scopes(&self) -> &[HostScope] {
+        &self.scopes
+    }
+
+    fn scope_index(&mut self, host: &str, host_path: &str) -> HostResult<usize> {
+        if let Some(&idx) = self.index.get(host_path) {
+            return Ok(idx);
+        }
+
+        let pattern = host_pattern_tree(host_path)?;
+        let key = self.patterns.insert(HttpMethod::Get, host_path)? as usize;
+        debug_assert_eq!(key, self.scopes.len());
+        self.scopes.push(HostScope {
+            host: host.to_string(),
+            pattern,
+            tree: RadixTree::with_route_key_counter(self.options.clone(), self.route_keys.clone()),
+        });
+        self.index.insert(host_path.to_string(), key);
+        Ok(key)
+    }
+}
+
+/// A tree holding only `host_path`, under route key 0.
+pub(crate) fn host_pattern_tree(host_path: &str) -> HostResult<RadixTree> {
+    let mut tree = RadixTree::new(host_pattern_options());
+    tree.insert(HttpMethod::Get, host_path)?;
+    Ok(tree)
+}
+
+/// Host literals are lowercased while the pattern is built, so the tree itself
+/// stays case sensitive to keep parameter names and constraints intact.
+fn host_pattern_options() -> RouterOptions {
+    RouterOptions {
+        case_sensitive: true,
+        ..RouterOptions::default()
+    }
+}
@@ -0,0 +1,81 @@
+use crate::registry::RouteRecord;
+use crate::router::dedup_chain;
+use crate::types::MiddlewareChain;
+use hashbrown::HashMap as FastHashMap;
+use std::sync::Arc;
+
+/// Middleware attached to every route registered under `prefix`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MiddlewareScope {
+    pub prefix: String,
+    pub middleware: Vec<String>,
+}
+
+/// Builds the chain of every recorded route, indexed by route key.
+///
+/// Prefix scopes come first, outermost prefix first and in registration order for
+/// equal depth, followed by the route's own (group) middleware. Duplicates keep
+/// their first position. Identical chains share one allocation.
+pub(crate) fn compose_chains(
+    records: &[RouteRecord],
+    scopes: &[MiddlewareScope],
+    case_sensitive: bool,
+) -> Vec<MiddlewareChain> {
+    let empty: MiddlewareChain = Arc::from(Vec::new());
+    let Some(max_key) = records.iter().map(|record| record.key).max() else {
+        return Vec::new();
+    };
+
+    let mut ordered: Vec<(Vec<&str>, &MiddlewareScope)> = scopes
+        .iter()
+        .map(|scope| (segments(&scope.prefix), scope))
+        .collect();
+    ordered.sort_by_key(|(prefix, _)| prefix.len());
+
+    let mut interned: FastHashMap<Vec<String>, MiddlewareChain> = FastHashMap::new();
+    let mut chains = vec![empty.clone(); max_key as usize + 1];
+
+    for record in records.iter() {
+        let route_segments = segments(&record.path);
+        let scoped = ordered
+            .iter()
+            .filter(|(prefix, _)| covers(prefix, &route_segments, case_sensitive))
+            .flat_map(|(_, scope)| scope.middleware.iter());
+        let chain = dedup_chain(scoped.chain(record.options.middleware.iter()));
+        if chain.is_empty() {
+            continue;
+        }
+
+        let shared = interned
+            .entry(chain)
+            .or_insert_with_key(|chain| Arc::from(chain.clone()))
+            .clone();
+        chains[record.key as usize] = shared;
+    }
+
+    chains
+}
+
+fn segments(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// A prefix covers a route when each of its segments equals the route segment at
+/// the same position; parameter segments match any parameter segment.
+fn covers(prefix: &[&str], route: &[&str], case_sensitive: bool) -> bool {
+    if prefix.len() > route.len() {
+        return false;
+    }
+    prefix.iter().zip(route.iter()).all(|(p, r)| {
+        if p.starts_with(':') {
+            return r.starts_with(':');
+        }
+        if case_sensitive {
+            p == r
+        } else {
+            p.eq_ignore_ascii_case(r)
+        }
+    })
+}
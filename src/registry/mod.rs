@@ -1,5 +1,16 @@
+mod hosts;
+mod middleware;
+mod records;
 mod stats;
 mod store;
+mod variants;
+mod versions;
 
+pub(crate) use hosts::host_pattern_tree;
+pub use hosts::{HostScope, HostTable};
+pub use middleware::MiddlewareScope;
+pub use records::RouteRecord;
 pub use stats::RegistryMetrics;
 pub use store::RouteRegistry;
+pub use variants::{RouteVariant, VariantTable};
+pub use versions::VersionTable;
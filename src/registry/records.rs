@@ -0,0 +1,16 @@
+use crate::enums::HttpMethod;
+use crate::router::RouteOptions;
+
+/// A route as it was registered, kept so the route set can be replayed elsewhere
+/// (for example when mounting one router into another) and listed after sealing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteRecord {
+    pub key: u16,
+    pub method: HttpMethod,
+    pub path: String,
+    /// Pattern as stored in the tree: constraints applied, then normalized.
+    pub normalized: String,
+    pub host: Option<String>,
+    /// Effective options of the route, with `methods` narrowed to `method`.
+    pub options: RouteOptions,
+}
@@ -1,41 +1,273 @@
 use crate::enums::HttpMethod;
-use crate::radix::{RadixResult, RadixTree};
-use crate::registry::RegistryMetrics;
-use crate::router::RouterOptions;
+use crate::host::{HostError, HostResult};
+use crate::radix::{MAX_ROUTES, RadixError, RadixResult, RadixTree};
+use crate::registry::middleware::compose_chains;
+use crate::registry::variants::duplicate_slot;
+use crate::registry::{
+    HostTable, MiddlewareScope, RegistryMetrics, RouteRecord, VariantTable, VersionTable,
+};
+use crate::router::{RouteOptions, RouterOptions, RouterResult};
+use crate::types::MiddlewareChain;
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
 
 #[derive(Debug)]
 pub struct RouteRegistry {
     tree: RadixTree,
+    hosts: HostTable,
+    versions: VersionTable,
+    records: Vec<RouteRecord>,
+    middleware: Vec<MiddlewareScope>,
+    variants: VariantTable,
     metrics: RegistryMetrics,
 }
 
 impl RouteRegistry {
     pub fn new(options: RouterOptions) -> Self {
+        let tree = RadixTree::new(options.clone());
+        let hosts = HostTable::new(options.clone(), tree.route_key_counter());
+        let versions = VersionTable::new(options, tree.route_key_counter());
         Self {
-            tree: RadixTree::new(options),
+            tree,
+            hosts,
+            versions,
+            records: Vec::new(),
+            middleware: Vec::new(),
+            variants: VariantTable::default(),
             metrics: RegistryMetrics::default(),
         }
     }
 
     pub fn insert(&mut self, method: HttpMethod, path: &str) -> RadixResult<u16> {
-        let key = self.tree.insert(method, path)?;
+        let options = RouteOptions::default();
+        let key = self.insert_tree_slot(method, path, &options)?;
+        self.record(key, method, path, None, options);
+        self.metrics.record_insert();
+        Ok(key)
+    }
+
+    pub fn insert_host(&mut self, host: &str, method: HttpMethod, path: &str) -> HostResult<u16> {
+        let options = RouteOptions::default();
+        let key = self.insert_host_slot(host, method, path, &options)?;
+        self.record(key, method, path, Some(host), options);
         self.metrics.record_insert();
         Ok(key)
     }
 
     pub fn insert_bulk(&mut self, entries: Vec<(HttpMethod, String)>) -> RadixResult<Vec<u16>> {
-        let out = self.tree.insert_bulk(entries)?;
+        let out = self.tree.insert_bulk(entries.clone())?;
+        for ((method, path), key) in entries.into_iter().zip(out.iter().copied()) {
+            self.record(key, method, &path, None, RouteOptions::default());
+        }
         self.metrics.record_bulk(out.len());
         Ok(out)
     }
 
+    /// Registers `path` once per method in `options`, rewriting unconstrained
+    /// parameters that have an entry in `options.constraints`. Routes with a
+    /// `version` go to that version's tree unless they are host scoped.
+    pub fn insert_with_options(
+        &mut self,
+        host: Option<&str>,
+        path: &str,
+        options: &RouteOptions,
+    ) -> RouterResult<Vec<u16>> {
+        let constrained = apply_constraints(path, &options.constraints);
+        let mut keys = Vec::with_capacity(options.methods.len());
+        for &method in options.methods.iter() {
+            let key = match host {
+                Some(host) => self.insert_host_slot(host, method, &constrained, options)?,
+                None => match options.version {
+                    Some(version) => {
+                        self.insert_version_slot(version, method, &constrained, options)?
+                    }
+                    None => self.insert_tree_slot(method, &constrained, options)?,
+                },
+            };
+            let narrowed = RouteOptions {
+                methods: vec![method],
+                ..options.clone()
+            };
+            self.record(key, method, path, host, narrowed);
+            self.metrics.record_insert();
+            keys.push(key);
+        }
+        Ok(keys)
+    }
+
+    /// Registers a previously recorded route, keeping its host scope and options.
+    pub fn insert_record(&mut self, record: &RouteRecord) -> RouterResult<u16> {
+        let keys = self.insert_with_options(
+            record.host.as_deref(),
+            &record.path,
+            &RouteOptions {
+                methods: vec![record.method],
+                ..record.options.clone()
+            },
+        )?;
+        Ok(keys[0])
+    }
+
+    /// Dry run of [`insert_record`](Self::insert_record) against the routes already
+    /// here: returns the error it would report, options validation included,
+    /// without registering anything. The route limit is checked
+    /// separately by [`check_capacity`](Self::check_capacity).
+    pub fn check_record(&self, record: &RouteRecord) -> RouterResult<()> {
+        record.options.validate()?;
+        let path = apply_constraints(&record.path, &record.options.constraints);
+        let joinable = |err: &RadixError| {
+            duplicate_slot(err).is_some_and(|slot| self.variants.can_join(slot, &record.options))
+        };
+        if let Some(host) = record.host.as_deref() {
+            return match self.hosts.check(host, record.method, &path) {
+                Err(HostError::Radix(err)) if joinable(&err) => Ok(()),
+                other => Ok(other?),
+            };
+        }
+        let checked = match record.options.version {
+            Some(version) => self.versions.check(version, record.method, &path),
+            None => self.tree.check_insert(record.method, &path),
+        };
+        match checked {
+            Err(err) if joinable(&err) => Ok(()),
+            other => Ok(other?),
+        }
+    }
+
+    /// Fails when `count` more route keys would exceed [`MAX_ROUTES`].
+    pub fn check_capacity(&self, count: usize) -> RadixResult<()> {
+        let current = self.tree.route_key_counter().load(Ordering::Relaxed);
+        if current as usize + count > MAX_ROUTES as usize {
+            return Err(RadixError::MaxRoutesExceeded {
+                requested: Some(count),
+                current_next_key: current,
+                limit: MAX_ROUTES,
+            });
+        }
+        Ok(())
+    }
+
+    fn insert_tree_slot(
+        &mut self,
+        method: HttpMethod,
+        path: &str,
+        options: &RouteOptions,
+    ) -> RadixResult<u16> {
+        let inserted = self.tree.insert(method, path);
+        self.settle_slot(inserted, options)
+    }
+
+    fn insert_version_slot(
+        &mut self,
+        version: u32,
+        method: HttpMethod,
+        path: &str,
+        options: &RouteOptions,
+    ) -> RadixResult<u16> {
+        let inserted = self.versions.insert(version, method, path);
+        self.settle_slot(inserted, options)
+    }
+
+    fn insert_host_slot(
+        &mut self,
+        host: &str,
+        method: HttpMethod,
+        path: &str,
+        options: &RouteOptions,
+    ) -> HostResult<u16> {
+        let inserted = match self.hosts.insert(host, method, path) {
+            Ok(key) => Ok(key),
+            Err(HostError::Radix(err)) => Err(err),
+            Err(err) => return Err(err),
+        };
+        Ok(self.settle_slot(inserted, options)?)
+    }
+
+    /// Turns a tree insert into a route key. A route landing on an occupied slot
+    /// becomes a variant of it when the two differ in their query conditions.
+    fn settle_slot(
+        &mut self,
+        inserted: RadixResult<u16>,
+        options: &RouteOptions,
+    ) -> RadixResult<u16> {
+        match inserted {
+            Ok(key) => {
+                self.variants.register_slot(key, options);
+                Ok(key)
+            }
+            Err(err) => match duplicate_slot(&err) {
+                Some(slot) => {
+                    let counter = self.tree.route_key_counter();
+                    self.variants.join_slot(slot, options, &counter, err)
+                }
+                None => Err(err),
+            },
+        }
+    }
+
+    fn record(
+        &mut self,
+        key: u16,
+        method: HttpMethod,
+        path: &str,
+        host: Option<&str>,
+        options: RouteOptions,
+    ) {
+        let options = RouteOptions {
+            methods: vec![method],
+            ..options
+        };
+        let constrained = apply_constraints(path, &options.constraints);
+        let normalized = match self.tree.preprocessor.apply(&constrained) {
+            Ok(outcome) => outcome.normalized().to_string(),
+            Err(_) => constrained,
+        };
+        self.records.push(RouteRecord {
+            key,
+            method,
+            path: path.to_string(),
+            normalized,
+            host: host.map(str::to_string),
+            options,
+        });
+    }
+
+    pub fn add_middleware_scope(&mut self, prefix: &str, middleware: Vec<String>) {
+        self.middleware.push(MiddlewareScope {
+            prefix: prefix.to_string(),
+            middleware,
+        });
+    }
+
+    pub fn middleware_scopes(&self) -> &[MiddlewareScope] {
+        &self.middleware
+    }
+
+    /// Composed middleware chain of every route, indexed by route key.
+    pub fn middleware_chains(&self) -> Vec<MiddlewareChain> {
+        compose_chains(
+            &self.records,
+            &self.middleware,
+            self.tree.options.case_sensitive,
+        )
+    }
+
+    /// Routes sharing a path and method, grouped by the key that owns the slot.
+    pub fn variants(&self) -> &VariantTable {
+        &self.variants
+    }
+
     pub fn finalize(&mut self) {
         self.tree.finalize();
+        self.hosts.finalize();
+        self.versions.finalize();
     }
 
     pub fn reset_after_seal(&mut self) {
         let options = self.tree.options.clone();
-        self.tree = RadixTree::new(options);
+        self.tree = RadixTree::new(options.clone());
+        self.hosts = HostTable::new(options.clone(), self.tree.route_key_counter());
+        self.versions = VersionTable::new(options, self.tree.route_key_counter());
         self.metrics = RegistryMetrics::default();
     }
 
@@ -47,7 +279,44 @@ impl RouteRegistry {
         &mut self.tree
     }
 
+    pub fn hosts(&self) -> &HostTable {
+        &self.hosts
+    }
+
+    pub fn versions(&self) -> &VersionTable {
+        &self.versions
+    }
+
+    /// Routes in registration order. Records survive sealing so a sealed router can
+    /// still be mounted into another one.
+    pub fn records(&self) -> &[RouteRecord] {
+        &self.records
+    }
+
+    pub fn options(&self) -> &RouterOptions {
+        &self.tree.options
+    }
+
     pub fn metrics(&self) -> &RegistryMetrics {
         &self.metrics
     }
 }
+
+/// Appends `(regex)` to every `:name` segment without an inline constraint when
+/// `constraints` has an entry for `name`.
+fn apply_constraints(path: &str, constraints: &HashMap<String, String>) -> String {
+    if constraints.is_empty() {
+        return path.to_string();
+    }
+
+    path.split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) if !name.contains('(') => match constraints.get(name) {
+                Some(regex) => format!(":{name}({regex})"),
+                None => segment.to_string(),
+            },
+            _ => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
@@ -0,0 +1,138 @@
+use crate::query::QueryCondition;
+use crate::radix::{MAX_ROUTES, RadixError};
+use crate::request::HeaderCondition;
+use crate::router::RouteOptions;
+use hashbrown::HashMap as FastHashMap;
+use std::sync::atomic::{AtomicU16, Ordering};
+
+/// One of several routes sharing a tree slot (same path and method), told apart
+/// by request conditions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteVariant {
+    pub key: u16,
+    pub query: Vec<QueryCondition>,
+    pub headers: Vec<HeaderCondition>,
+    pub priority: i32,
+}
+
+impl RouteVariant {
+    fn from_options(key: u16, options: &RouteOptions) -> Self {
+        Self {
+            key,
+            query: options.query.clone(),
+            headers: options.headers.clone(),
+            priority: options.priority,
+        }
+    }
+
+    pub fn is_conditional(&self) -> bool {
+        !self.query.is_empty() || !self.headers.is_empty()
+    }
+
+    /// Sum of condition weights; unconditional variants score zero and act as
+    /// the fallback of their slot.
+    pub fn specificity(&self) -> u32 {
+        self.query
+            .iter()
+            .map(QueryCondition::specificity)
+            .sum::<u32>()
+            + self
+                .headers
+                .iter()
+                .map(HeaderCondition::specificity)
+                .sum::<u32>()
+    }
+
+    fn same_conditions(&self, other: &RouteVariant) -> bool {
+        same_set(&self.query, &other.query) && same_set(&self.headers, &other.headers)
+    }
+}
+
+/// Variant groups keyed by the route key that owns the tree slot. Slots whose only
+/// route is unconditional have no entry.
+#[derive(Debug, Default)]
+pub struct VariantTable {
+    groups: FastHashMap<u16, Vec<RouteVariant>>,
+}
+
+impl VariantTable {
+    /// Called after a route took a fresh slot.
+    pub fn register_slot(&mut self, key: u16, options: &RouteOptions) {
+        if !options.query.is_empty() || !options.headers.is_empty() {
+            self.groups
+                .insert(key, vec![RouteVariant::from_options(key, options)]);
+        }
+    }
+
+    /// Whether a route with `options` could join the occupied slot owned by `slot`,
+    /// as [`join_slot`](Self::join_slot) would decide it.
+    pub fn can_join(&self, slot: u16, options: &RouteOptions) -> bool {
+        let candidate = RouteVariant::from_options(0, options);
+        match self.groups.get(&slot) {
+            None => candidate.is_conditional(),
+            Some(group) => !group
+                .iter()
+                .any(|variant| variant.same_conditions(&candidate)),
+        }
+    }
+
+    /// Called when a route hits an occupied slot owned by `slot`. Returns the key
+    /// for the new variant, or hands back `duplicate` when the routes cannot be
+    /// told apart.
+    pub fn join_slot(
+        &mut self,
+        slot: u16,
+        options: &RouteOptions,
+        route_keys: &AtomicU16,
+        duplicate: RadixError,
+    ) -> Result<u16, RadixError> {
+        if !self.can_join(slot, options) {
+            return Err(duplicate);
+        }
+        let candidate = RouteVariant::from_options(0, options);
+
+        let current = route_keys.load(Ordering::Relaxed);
+        if current == MAX_ROUTES {
+            return Err(RadixError::MaxRoutesExceeded {
+                requested: None,
+                current_next_key: current,
+                limit: MAX_ROUTES,
+            });
+        }
+        let key = route_keys.fetch_add(1, Ordering::Relaxed);
+
+        let group = self.groups.entry(slot).or_insert_with(|| {
+            vec![RouteVariant {
+                key: slot,
+                query: Vec::new(),
+                headers: Vec::new(),
+                priority: 0,
+            }]
+        });
+        group.push(RouteVariant { key, ..candidate });
+        Ok(key)
+    }
+
+    pub fn groups(&self) -> impl Iterator<Item = (u16, &[RouteVariant])> {
+        self.groups
+            .iter()
+            .map(|(slot, variants)| (*slot, variants.as_slice()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+}
+
+fn same_set<T: PartialEq>(a: &[T], b: &[T]) -> bool {
+    a.len() == b.len() && a.iter().all(|item| b.contains(item))
+}
+
+/// Extracts the owner key of an occupied slot from a duplicate-route error.
+pub(crate) fn duplicate_slot(err: &RadixError) -> Option<u16> {
+    match err {
+        RadixError::DuplicateRoute { existing_key, .. }
+        | RadixError::DuplicateWildcardRoute { existing_key, .. } => Some(*existing_key),
+        _ => None,
+    }
+}
@@ -0,0 +1,55 @@
+use crate::enums::HttpMethod;
+use crate::radix::{RadixResult, RadixTree};
+use crate::router::RouterOptions;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU16;
+
+/// One path tree per API version. Trees share the router's key counter, so keys
+/// stay unique across versions.
+#[derive(Debug)]
+pub struct VersionTable {
+    trees: BTreeMap<u32, RadixTree>,
+    options: RouterOptions,
+    route_keys: Arc<AtomicU16>,
+}
+
+impl VersionTable {
+    pub fn new(options: RouterOptions, route_keys: Arc<AtomicU16>) -> Self {
+        Self {
+            trees: BTreeMap::new(),
+            options,
+            route_keys,
+        }
+    }
+
+    pub fn insert(&mut self, version: u32, method: HttpMethod, path: &str) -> RadixResult<u16> {
+        let tree = self.trees.entry(version).or_insert_with(|| {
+            RadixTree::with_route_key_counter(self.options.clone(), self.route_keys.clone())
+        });
+        tree.insert(method, path)
+    }
+
+    /// Dry run of [`insert`](Self::insert); see [`RadixTree::check_insert`].
+    pub fn check(&self, version: u32, method: HttpMethod, path: &str) -> RadixResult<()> {
+        match self.trees.get(&version) {
+            Some(tree) => tree.check_insert(method, path),
+            None => RadixTree::new(self.options.clone()).check_insert(method, path),
+        }
+    }
+
+    pub fn finalize(&mut self) {
+        for tree in self.trees.values_mut() {
+            tree.finalize();
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.trees.is_empty()
+    }
+
+    /// Trees in ascending version order.
+    pub fn trees(&self) -> impl Iterator<Item = (u32, &RadixTree)> {
+        self.trees.iter().map(|(version, tree)| (*version, tree))
+    }
+}
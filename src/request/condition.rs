@@ -0,0 +1,92 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::RequestHeaders;
+
+/// Requirement a route places on a request header. Names are matched ASCII
+/// case-insensitively, values as sent.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HeaderCondition {
+    /// The header must be present, with any value.
+    Present { name: String },
+    /// The header value must equal `value`.
+    Equals { name: String, value: String },
+    /// The header value must fully match the regex `pattern`.
+    Matches { name: String, pattern: String },
+}
+
+impl HeaderCondition {
+    pub fn present<S: Into<String>>(name: S) -> Self {
+        Self::Present {
+            name: lowercase(name),
+        }
+    }
+
+    pub fn equals<S: Into<String>, V: Into<String>>(name: S, value: V) -> Self {
+        Self::Equals {
+            name: lowercase(name),
+            value: value.into(),
+        }
+    }
+
+    pub fn matches<S: Into<String>, P: Into<String>>(name: S, pattern: P) -> Self {
+        Self::Matches {
+            name: lowercase(name),
+            pattern: pattern.into(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Present { name } | Self::Equals { name, .. } | Self::Matches { name, .. } => name,
+        }
+    }
+
+    /// Same weights as [`QueryCondition::specificity`](crate::query::QueryCondition::specificity).
+    pub fn specificity(&self) -> u32 {
+        match self {
+            Self::Equals { .. } => 3,
+            Self::Matches { .. } => 2,
+            Self::Present { .. } => 1,
+        }
+    }
+
+    pub(crate) fn compile(&self) -> Result<CompiledHeaderCondition, regex::Error> {
+        Ok(match self {
+            Self::Present { name } => CompiledHeaderCondition::Present { name: name.clone() },
+            Self::Equals { name, value } => CompiledHeaderCondition::Equals {
+                name: name.clone(),
+                value: value.clone(),
+            },
+            Self::Matches { name, pattern } => CompiledHeaderCondition::Matches {
+                name: name.clone(),
+                regex: Regex::new(&format!("^(?:{})$", pattern))?,
+            },
+        })
+    }
+}
+
+fn lowercase<S: Into<String>>(name: S) -> String {
+    let mut name = name.into();
+    name.make_ascii_lowercase();
+    name
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum CompiledHeaderCondition {
+    Present { name: String },
+    Equals { name: String, value: String },
+    Matches { name: String, regex: Regex },
+}
+
+impl CompiledHeaderCondition {
+    pub(crate) fn is_satisfied(&self, headers: &dyn RequestHeaders) -> bool {
+        match self {
+            Self::Present { name } => headers.header(name).is_some(),
+            Self::Equals { name, value } => headers.header(name) == Some(value.as_str()),
+            Self::Matches { name, regex } => {
+                headers.header(name).is_some_and(|v| regex.is_match(v))
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+
+/// Read access to request headers for [`find_request`](crate::Router::find_request).
+///
+/// Header names are compared ASCII case-insensitively; when a header repeats,
+/// implementations return its first value.
+pub trait RequestHeaders {
+    fn header(&self, name: &str) -> Option<&str>;
+}
+
+impl<T: RequestHeaders + ?Sized> RequestHeaders for &T {
+    fn header(&self, name: &str) -> Option<&str> {
+        (**self).header(name)
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> RequestHeaders for [(K, V)] {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.iter()
+            .find(|(key, _)| key.as_ref().eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_ref())
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>, const N: usize> RequestHeaders for [(K, V); N] {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.as_slice().header(name)
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> RequestHeaders for Vec<(K, V)> {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.as_slice().header(name)
+    }
+}
+
+impl RequestHeaders for HashMap<String, String> {
+    fn header(&self, name: &str) -> Option<&str> {
+        if let Some(value) = self.get(name) {
+            return Some(value);
+        }
+        self.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// No headers at all, for lookups that only need the path.
+impl RequestHeaders for () {
+    fn header(&self, _name: &str) -> Option<&str> {
+        None
+    }
+}
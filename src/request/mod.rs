@@ -0,0 +1,6 @@
+mod condition;
+mod headers;
+
+pub(crate) use condition::CompiledHeaderCondition;
+pub use condition::HeaderCondition;
+pub use headers::RequestHeaders;
@@ -1,5 +1,8 @@
+use super::RouterOptionsError;
+use crate::enums::HttpMethod;
+use crate::host::HostError;
 use crate::radix::RadixError;
-use crate::readonly::ReadOnlyError;
+use crate::readonly::{ReadOnlyError, RouteFinding};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -8,11 +11,37 @@ pub enum RouterError {
     AddWhileSealed { path: String },
     #[error("router is sealed; cannot add {count} routes in bulk")]
     BulkAddWhileSealed { count: usize },
+    #[error("router is sealed; cannot attach middleware to '{prefix}'")]
+    MiddlewareWhileSealed { prefix: String },
+    #[error("router is sealed; cannot mount routes under '{prefix}'")]
+    MountWhileSealed { prefix: String },
+    #[error("cannot mount route '{path}' under '{prefix}': {source}")]
+    MountConflict {
+        prefix: String,
+        path: String,
+        #[source]
+        source: Box<RouterError>,
+    },
+    #[error("cannot import OpenAPI operation {method:?} '{path}': {source}")]
+    OpenApiImport {
+        path: String,
+        method: HttpMethod,
+        #[source]
+        source: Box<RouterError>,
+    },
+    #[error("invalid OpenAPI document: {0}")]
+    OpenApiParse(#[from] serde_json::Error),
+    #[error("strict mode rejected {} conflicting route(s); first: {}", findings.len(), findings[0])]
+    RouteConflicts { findings: Vec<RouteFinding> },
     #[error("router is not sealed; cannot perform route lookup")]
     FindWhileMutable,
     #[error("router is not sealed; readonly snapshot is unavailable")]
     ReadOnlyUnavailable,
     #[error(transparent)]
+    Options(#[from] RouterOptionsError),
+    #[error(transparent)]
+    Host(#[from] HostError),
+    #[error(transparent)]
     Radix(#[from] RadixError),
     #[error(transparent)]
     ReadOnly(#[from] ReadOnlyError),
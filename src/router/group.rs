@@ -0,0 +1,88 @@
+use super::mount::mount_path;
+use super::{ExplicitFields, RouteOptions, RouteOverlay, Router, RouterResult};
+use crate::enums::HttpMethod;
+
+/// Registers routes under a shared prefix and shared [`RouteOptions`].
+///
+/// Created by [`Router::group`]. Prefixes concatenate and options merge from the
+/// outermost group inward (see [`RouteOptions::merge`]); fields a [`RouteOverlay`]
+/// sets explicitly win even when they equal the defaults. Routes still go through
+/// the router's registry one by one.
+#[derive(Debug)]
+pub struct RouteGroup<'a> {
+    router: &'a Router,
+    prefix: String,
+    options: RouteOverlay,
+}
+
+impl<'a> RouteGroup<'a> {
+    pub(crate) fn new(router: &'a Router, prefix: String, options: RouteOverlay) -> Self {
+        Self {
+            router,
+            prefix,
+            options,
+        }
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn options(&self) -> &RouteOptions {
+        self.options.options()
+    }
+
+    /// Appends `middleware` to the chain of routes added to this group from now on.
+    pub fn use_middleware<I, S>(&mut self, middleware: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options
+            .options_mut()
+            .middleware
+            .extend(middleware.into_iter().map(Into::into));
+    }
+
+    pub fn add(&mut self, method: HttpMethod, path: &str) -> RouterResult<u16> {
+        let options = RouteOptions {
+            methods: vec![method],
+            alias: None,
+            ..self.options.options().clone()
+        };
+        let explicit = ExplicitFields {
+            methods: true,
+            ..self.options.explicit()
+        };
+        let keys = self.router.add_overlay(
+            &mount_path(&self.prefix, path),
+            RouteOverlay::new(options, explicit),
+        )?;
+        Ok(keys[0])
+    }
+
+    pub fn add_with_options(
+        &mut self,
+        path: &str,
+        options: impl Into<RouteOverlay>,
+    ) -> RouterResult<Vec<u16>> {
+        self.router.add_overlay(
+            &mount_path(&self.prefix, path),
+            self.options.merge(&options.into()),
+        )
+    }
+
+    pub fn group<R>(
+        &mut self,
+        prefix: &str,
+        options: impl Into<RouteOverlay>,
+        build: impl FnOnce(&mut RouteGroup<'a>) -> R,
+    ) -> R {
+        let mut nested = RouteGroup::new(
+            self.router,
+            mount_path(&self.prefix, prefix),
+            self.options.merge(&options.into()),
+        );
+        build(&mut nested)
+    }
+}
@@ -0,0 +1,200 @@
+use super::mount::mount_path;
+use super::{RouteOptions, Router, RouterError, RouterOptions, RouterOptionsError};
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// Declarative description of a router: its config plus routes and groups.
+///
+/// The schema is plain serde, so it can be read from JSON, TOML or any other
+/// serde format. Every field except `path` (routes) and `prefix` (groups) may be
+/// omitted and falls back to its default. Unknown keys are rejected at every
+/// level, so a misspelt option fails to parse instead of being dropped.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RouteManifest {
+    pub config: RouterOptions,
+    pub routes: Vec<RouteDefinition>,
+    pub groups: Vec<GroupDefinition>,
+}
+
+/// A single route. Options are flattened, so `methods`, `constraints`, `alias` and
+/// the other [`RouteOptions`] fields sit next to `path`; `alias` doubles as the
+/// route's name in error reports.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawRouteDefinition")]
+pub struct RouteDefinition {
+    pub path: String,
+    pub host: Option<String>,
+    #[serde(flatten)]
+    pub options: RouteOptions,
+}
+
+/// Routes and nested groups sharing a prefix and options, like [`Router::group`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawGroupDefinition")]
+pub struct GroupDefinition {
+    pub name: Option<String>,
+    pub prefix: String,
+    #[serde(flatten)]
+    pub options: RouteOptions,
+    pub routes: Vec<RouteDefinition>,
+    pub groups: Vec<GroupDefinition>,
+}
+
+// serde cannot combine `deny_unknown_fields` with `flatten`, so definitions are
+// read through these and whatever `RouteOptions` did not claim ends up in
+// `unknown`.
+#[derive(Deserialize)]
+struct RawRouteDefinition {
+    path: String,
+    host: Option<String>,
+    #[serde(flatten)]
+    options: RouteOptions,
+    #[serde(flatten)]
+    unknown: BTreeMap<String, IgnoredAny>,
+}
+
+#[derive(Deserialize)]
+struct RawGroupDefinition {
+    name: Option<String>,
+    prefix: String,
+    #[serde(flatten)]
+    options: RouteOptions,
+    #[serde(default)]
+    routes: Vec<RouteDefinition>,
+    #[serde(default)]
+    groups: Vec<GroupDefinition>,
+    #[serde(flatten)]
+    unknown: BTreeMap<String, IgnoredAny>,
+}
+
+impl TryFrom<RawRouteDefinition> for RouteDefinition {
+    type Error = String;
+
+    fn try_from(raw: RawRouteDefinition) -> Result<Self, Self::Error> {
+        reject_unknown("route", &raw.path, &raw.unknown)?;
+        Ok(Self {
+            path: raw.path,
+            host: raw.host,
+            options: raw.options,
+        })
+    }
+}
+
+impl TryFrom<RawGroupDefinition> for GroupDefinition {
+    type Error = String;
+
+    fn try_from(raw: RawGroupDefinition) -> Result<Self, Self::Error> {
+        reject_unknown("group", &raw.prefix, &raw.unknown)?;
+        Ok(Self {
+            name: raw.name,
+            prefix: raw.prefix,
+            options: raw.options,
+            routes: raw.routes,
+            groups: raw.groups,
+        })
+    }
+}
+
+fn reject_unknown(
+    kind: &str,
+    path: &str,
+    unknown: &BTreeMap<String, IgnoredAny>,
+) -> Result<(), String> {
+    match unknown.keys().next() {
+        Some(key) => Err(format!("unknown field `{key}` in {kind} '{path}'")),
+        None => Ok(()),
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ManifestError {
+    #[error("invalid router config: {0}")]
+    Config(#[from] RouterOptionsError),
+    /// `location` is the route's position in the manifest, such as
+    /// `groups[1].routes[0]`, followed by its alias when it has one.
+    #[error("route {location} ('{path}') is invalid: {source}")]
+    Route {
+        location: String,
+        path: String,
+        #[source]
+        source: Box<RouterError>,
+    },
+}
+
+pub type ManifestResult<T> = Result<T, ManifestError>;
+
+impl Router {
+    /// Builds an unsealed router from `manifest`. Stops at the first route that
+    /// fails to register.
+    pub fn from_manifest(manifest: &RouteManifest) -> ManifestResult<Router> {
+        manifest.config.validate()?;
+        let router = Router::new(Some(manifest.config.clone()));
+
+        let root = RouteOptions::default();
+        add_routes(&router, "routes", "", &root, &manifest.routes)?;
+        add_groups(&router, "groups", "", &root, &manifest.groups)?;
+        Ok(router)
+    }
+}
+
+fn add_routes(
+    router: &Router,
+    location: &str,
+    prefix: &str,
+    inherited: &RouteOptions,
+    routes: &[RouteDefinition],
+) -> ManifestResult<()> {
+    for (idx, route) in routes.iter().enumerate() {
+        let path = mount_path(prefix, &route.path);
+        let options = inherited.merge(&route.options);
+        let added = match route.host.as_deref() {
+            Some(host) => router.add_host_with_options(host, &path, options),
+            None => router.add_with_options(&path, options),
+        };
+        added.map_err(|err| ManifestError::Route {
+            location: entry_location(location, idx, route.options.alias.as_deref()),
+            path,
+            source: Box::new(err),
+        })?;
+    }
+    Ok(())
+}
+
+fn add_groups(
+    router: &Router,
+    location: &str,
+    prefix: &str,
+    inherited: &RouteOptions,
+    groups: &[GroupDefinition],
+) -> ManifestResult<()> {
+    for (idx, group) in groups.iter().enumerate() {
+        let location = entry_location(location, idx, group.name.as_deref());
+        let prefix = mount_path(prefix, &group.prefix);
+        let options = inherited.merge(&group.options);
+        add_routes(
+            router,
+            &format!("{location}.routes"),
+            &prefix,
+            &options,
+            &group.routes,
+        )?;
+        add_groups(
+            router,
+            &format!("{location}.groups"),
+            &prefix,
+            &options,
+            &group.groups,
+        )?;
+    }
+    Ok(())
+}
+
+fn entry_location(list: &str, idx: usize, name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("{list}[{idx}] \"{name}\""),
+        None => format!("{list}[{idx}]"),
+    }
+}
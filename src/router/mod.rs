@@ -1,13 +1,22 @@
 mod errors;
+mod group;
+mod manifest;
+mod mount;
 mod options;
 mod preprocess;
 mod service;
 
 pub use crate::readonly::RouterReadOnly;
 pub use errors::{RouterError, RouterResult};
+pub use group::RouteGroup;
+pub use manifest::{
+    GroupDefinition, ManifestError, ManifestResult, RouteDefinition, RouteManifest,
+};
 pub use options::{
-    MatchOrder, ParamStyle, RepeatMatchMode, RouteOptions, RouteOptionsBuilder, RouterOptions,
-    RouterOptionsBuilder, RouterOptionsError,
+    CacheAdmission, CacheOptions, CacheScope, DEFAULT_CACHE_CAPACITY, DEFAULT_CACHE_SHARDS,
+    DefaultCachePolicy, MatchOrder, ParamStyle, RepeatMatchMode, RouteCachePolicy, RouteOptions,
+    RouteOptionsBuilder, RouteOverlay, RouterOptions, RouterOptionsBuilder, RouterOptionsError,
 };
+pub(crate) use options::{ExplicitFields, dedup_chain};
 pub use preprocess::{PreprocessOutcome, Preprocessor};
 pub use service::Router;
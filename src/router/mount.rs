@@ -0,0 +1,40 @@
+/// Joins a mount prefix and a sub-router path, e.g. `/orgs/:org` + `/users` gives
+/// `/orgs/:org/users`. A root sub-route maps to the prefix itself.
+pub(crate) fn mount_path(prefix: &str, path: &str) -> String {
+    let prefix = prefix.trim_end_matches('/');
+    if prefix.is_empty() {
+        return path.to_string();
+    }
+
+    let mut out = String::with_capacity(prefix.len() + path.len() + 1);
+    if !prefix.starts_with('/') {
+        out.push('/');
+    }
+    out.push_str(prefix);
+
+    if path.is_empty() || path == "/" {
+        return out;
+    }
+    if !path.starts_with('/') {
+        out.push('/');
+    }
+    out.push_str(path);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_prefix_and_path_with_single_slash() {
+        assert_eq!(mount_path("/orgs/:org/", "/users"), "/orgs/:org/users");
+        assert_eq!(mount_path("api", "users"), "/api/users");
+    }
+
+    #[test]
+    fn root_sub_route_maps_to_prefix() {
+        assert_eq!(mount_path("/api", "/"), "/api");
+        assert_eq!(mount_path("/", "/users"), "/users");
+    }
+}
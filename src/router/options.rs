@@ -1,4 +1,7 @@
 use crate::enums::HttpMethod;
+use crate::query::QueryCondition;
+use crate::request::HeaderCondition;
+use crate::version::VersioningOptions;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -7,6 +10,8 @@ use thiserror::Error;
 const ROUTE_PRIORITY_MIN: i32 = -100;
 const ROUTE_PRIORITY_MAX: i32 = 100;
 pub const DEFAULT_PARAM_PATTERN: &str = "[^/]+";
+pub const DEFAULT_CACHE_CAPACITY: usize = 256;
+pub const DEFAULT_CACHE_SHARDS: usize = 8;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum MatchOrder {
@@ -29,7 +34,18 @@ pub enum ParamStyle {
     Braces,
 }
 
+/// Whether lookups resolving to a route may be stored in the route cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum RouteCachePolicy {
+    /// Follow the router's [`DefaultCachePolicy`].
+    #[default]
+    Default,
+    Always,
+    Never,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
 pub struct RouteOptions {
     pub pattern: Option<String>,
     pub methods: Vec<HttpMethod>,
@@ -39,6 +55,11 @@ pub struct RouteOptions {
     pub priority: i32,
     pub meta: HashMap<String, String>,
     pub alias: Option<String>,
+    pub middleware: Vec<String>,
+    pub query: Vec<QueryCondition>,
+    pub headers: Vec<HeaderCondition>,
+    pub version: Option<u32>,
+    pub cache: RouteCachePolicy,
 }
 
 impl Default for RouteOptions {
@@ -52,6 +73,11 @@ impl Default for RouteOptions {
             priority: 0,
             meta: HashMap::new(),
             alias: None,
+            middleware: Vec::new(),
+            query: Vec::new(),
+            headers: Vec::new(),
+            version: None,
+            cache: RouteCachePolicy::Default,
         }
     }
 }
@@ -79,13 +105,166 @@ impl RouteOptions {
         {
             return Err(RouterConfigError::EmptyAlias);
         }
+        for condition in self.query.iter() {
+            if let Err(err) = condition.compile() {
+                return Err(RouterConfigError::QueryConditionInvalid {
+                    name: condition.name().to_string(),
+                    error: err.to_string(),
+                });
+            }
+        }
+        for condition in self.headers.iter() {
+            if let Err(err) = condition.compile() {
+                return Err(RouterConfigError::HeaderConditionInvalid {
+                    name: condition.name().to_string(),
+                    error: err.to_string(),
+                });
+            }
+        }
         Ok(())
     }
+
+    /// Layers `inner` over `self`: constraint and meta maps are combined with inner
+    /// entries winning, and scalar fields come from `inner` unless it leaves them at
+    /// their defaults (`[GET]` for methods, `0` for priority, `None` for version,
+    /// `Default` for the cache policy).
+    /// Middleware is appended after the outer chain, and query and header conditions
+    /// accumulate. The alias names a single route and is never inherited. Route
+    /// groups take a [`RouteOverlay`] instead, so inner values equal to the defaults
+    /// can still win.
+    pub fn merge(&self, inner: &RouteOptions) -> RouteOptions {
+        self.merge_with(inner, ExplicitFields::non_default(inner))
+    }
+
+    /// [`merge`](Self::merge), with the fields marked in `explicit` taken from
+    /// `inner` even when they hold their defaults.
+    pub(crate) fn merge_with(
+        &self,
+        inner: &RouteOptions,
+        explicit: ExplicitFields,
+    ) -> RouteOptions {
+        let mut constraints = self.constraints.clone();
+        constraints.extend(inner.constraints.clone());
+        let mut meta = self.meta.clone();
+        meta.extend(inner.meta.clone());
+
+        RouteOptions {
+            pattern: inner.pattern.clone().or_else(|| self.pattern.clone()),
+            methods: if explicit.methods {
+                inner.methods.clone()
+            } else {
+                self.methods.clone()
+            },
+            constraints,
+            optional: if explicit.optional {
+                inner.optional
+            } else {
+                self.optional
+            },
+            repeatable: if explicit.repeatable {
+                inner.repeatable
+            } else {
+                self.repeatable
+            },
+            priority: if explicit.priority {
+                inner.priority
+            } else {
+                self.priority
+            },
+            meta,
+            alias: inner.alias.clone(),
+            middleware: dedup_chain(self.middleware.iter().chain(inner.middleware.iter())),
+            query: union_conditions(&self.query, &inner.query),
+            headers: union_conditions(&self.headers, &inner.headers),
+            version: inner.version.or(self.version),
+            cache: if inner.cache != RouteCachePolicy::Default {
+                inner.cache
+            } else {
+                self.cache
+            },
+        }
+    }
+}
+
+/// Which of the scalar [`RouteOptions`] fields with a default a layer sets itself.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ExplicitFields {
+    pub(crate) methods: bool,
+    pub(crate) optional: bool,
+    pub(crate) repeatable: bool,
+    pub(crate) priority: bool,
+}
+
+impl ExplicitFields {
+    /// Fields of `options` that differ from their defaults, all that can be told
+    /// apart once plain options are built.
+    fn non_default(options: &RouteOptions) -> Self {
+        Self {
+            methods: options.methods != [HttpMethod::Get],
+            optional: options.optional,
+            repeatable: options.repeatable,
+            priority: options.priority != 0,
+        }
+    }
+
+    pub(crate) fn union(self, other: Self) -> Self {
+        Self {
+            methods: self.methods || other.methods,
+            optional: self.optional || other.optional,
+            repeatable: self.repeatable || other.repeatable,
+            priority: self.priority || other.priority,
+        }
+    }
+}
+
+/// [`RouteOptions`] for a route group layer, remembering which fields were set
+/// explicitly. Merged into a group, an explicit `[GET]` or priority `0` replaces
+/// the outer value where plain options would inherit it. Built with
+/// [`RouteOptionsBuilder::overlay`]; plain options convert with only their
+/// non-default fields counting as set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteOverlay {
+    options: RouteOptions,
+    explicit: ExplicitFields,
+}
+
+impl RouteOverlay {
+    pub(crate) fn new(options: RouteOptions, explicit: ExplicitFields) -> Self {
+        Self { options, explicit }
+    }
+
+    /// `inner` layered over this overlay; fields either one set stay explicit.
+    pub(crate) fn merge(&self, inner: &RouteOverlay) -> RouteOverlay {
+        Self {
+            options: self.options.merge_with(&inner.options, inner.explicit),
+            explicit: self.explicit.union(inner.explicit),
+        }
+    }
+
+    pub fn options(&self) -> &RouteOptions {
+        &self.options
+    }
+
+    pub(crate) fn options_mut(&mut self) -> &mut RouteOptions {
+        &mut self.options
+    }
+
+    pub(crate) fn explicit(&self) -> ExplicitFields {
+        self.explicit
+    }
+}
+
+impl From<RouteOptions> for RouteOverlay {
+    fn from(options: RouteOptions) -> Self {
+        let explicit = ExplicitFields::non_default(&options);
+        Self { options, explicit }
+    }
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct RouteOptionsBuilder {
     options: RouteOptions,
+    explicit: ExplicitFields,
 }
 
 impl RouteOptionsBuilder {
@@ -99,6 +278,7 @@ impl RouteOptionsBuilder {
         I: Into<Vec<HttpMethod>>,
     {
         self.options.methods = methods.into();
+        self.explicit.methods = true;
         self
     }
 
@@ -109,16 +289,19 @@ impl RouteOptionsBuilder {
 
     pub fn optional(mut self, value: bool) -> Self {
         self.options.optional = value;
+        self.explicit.optional = true;
         self
     }
 
     pub fn repeatable(mut self, value: bool) -> Self {
         self.options.repeatable = value;
+        self.explicit.repeatable = true;
         self
     }
 
     pub fn priority(mut self, value: i32) -> Self {
         self.options.priority = value;
+        self.explicit.priority = true;
         self
     }
 
@@ -132,13 +315,169 @@ impl RouteOptionsBuilder {
         self
     }
 
+    pub fn middleware<I, S>(mut self, middleware: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options.middleware = middleware.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn query<I>(mut self, conditions: I) -> Self
+    where
+        I: IntoIterator<Item = QueryCondition>,
+    {
+        self.options.query = conditions.into_iter().collect();
+        self
+    }
+
+    pub fn headers<I>(mut self, conditions: I) -> Self
+    where
+        I: IntoIterator<Item = HeaderCondition>,
+    {
+        self.options.headers = conditions.into_iter().collect();
+        self
+    }
+
+    pub fn version(mut self, version: u32) -> Self {
+        self.options.version = Some(version);
+        self
+    }
+
+    pub fn cache(mut self, policy: RouteCachePolicy) -> Self {
+        self.options.cache = policy;
+        self
+    }
+
     pub fn build(self) -> Result<RouteOptions, RouterConfigError> {
         self.options.validate()?;
         Ok(self.options)
     }
+
+    /// Like [`build`](Self::build), for a route group layer: every field set on
+    /// this builder overrides the outer layers, even when it equals the default.
+    pub fn overlay(self) -> Result<RouteOverlay, RouterConfigError> {
+        self.options.validate()?;
+        Ok(RouteOverlay {
+            options: self.options,
+            explicit: self.explicit,
+        })
+    }
+}
+
+fn union_conditions<T: Clone + PartialEq>(outer: &[T], inner: &[T]) -> Vec<T> {
+    let mut out = outer.to_vec();
+    for condition in inner.iter() {
+        if !out.contains(condition) {
+            out.push(condition.clone());
+        }
+    }
+    out
+}
+
+/// Keeps the first occurrence of every middleware identifier, preserving order.
+pub(crate) fn dedup_chain<'a, I>(middleware: I) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    let mut out: Vec<String> = Vec::new();
+    for id in middleware {
+        if !out.contains(id) {
+            out.push(id.clone());
+        }
+    }
+    out
+}
+
+/// How a full cache shard decides whether a new entry may replace an old one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum CacheAdmission {
+    /// Every new entry is admitted and evicts the CLOCK victim.
+    #[default]
+    Always,
+    /// TinyLFU-style: a new entry is only admitted when it has been looked up more
+    /// often than the victim, so one-off paths cannot flush hot routes.
+    Frequency,
+}
+
+/// Cache policy of routes that leave theirs at [`RouteCachePolicy::Default`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum DefaultCachePolicy {
+    #[default]
+    All,
+    /// Only static routes and routes whose parameters are all constrained to a
+    /// list of literals, such as `:lang(en|de|fr)`. Wildcards never qualify.
+    LowCardinality,
+}
+
+/// Where cached lookups live.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum CacheScope {
+    /// One sharded cache shared by every thread and every clone of the snapshot.
+    #[default]
+    Shared,
+    /// Each thread keeps a private cache of `capacity` entries, so lookups never
+    /// touch memory written by other threads. Trades memory for contention.
+    PerThread,
+}
+
+/// Settings of the lookup cache. `capacity` is the total number of entries and is
+/// split evenly across `shards`, each behind its own lock. With `negative` set,
+/// paths that matched no route are cached too and skip the tree walk on repeats.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheOptions {
+    pub enabled: bool,
+    pub capacity: usize,
+    pub shards: usize,
+    pub negative: bool,
+    pub admission: CacheAdmission,
+    pub scope: CacheScope,
+    pub default_policy: DefaultCachePolicy,
+}
+
+impl Default for CacheOptions {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            capacity: DEFAULT_CACHE_CAPACITY,
+            shards: DEFAULT_CACHE_SHARDS,
+            negative: false,
+            admission: CacheAdmission::Always,
+            scope: CacheScope::Shared,
+            default_policy: DefaultCachePolicy::All,
+        }
+    }
+}
+
+impl CacheOptions {
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), RouterConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.capacity == 0 {
+            return Err(RouterConfigError::CacheCapacityInvalid);
+        }
+        if self.shards == 0 || self.shards > self.capacity {
+            return Err(RouterConfigError::CacheShardsInvalid {
+                shards: self.shards,
+                capacity: self.capacity,
+            });
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
 pub struct RouterConfig {
     pub case_sensitive: bool,
     pub strict_trailing_slash: bool,
@@ -149,7 +488,18 @@ pub struct RouterConfig {
     pub repeat_match_mode: RepeatMatchMode,
     pub max_param_depth: usize,
     pub debug: bool,
+    /// Make [`Router::try_seal`](crate::Router::try_seal) fail when route analysis
+    /// finds unreachable or ambiguous routes. `Router::seal` cannot fail, so it
+    /// skips the checks and logs a warning instead.
+    pub strict_routes: bool,
+    /// Count hits per route in the sealed snapshot; see `RouterReadOnly::usage_report`.
+    pub track_usage: bool,
+    /// Count lookup outcomes for metrics. Telling 405 from 404 takes one extra
+    /// walk per missed path, kept with the negative cache entry when there is one.
+    pub track_lookups: bool,
+    pub cache: CacheOptions,
     pub route_defaults: RouteOptions,
+    pub versioning: VersioningOptions,
 }
 
 impl Default for RouterConfig {
@@ -164,7 +514,12 @@ impl Default for RouterConfig {
             repeat_match_mode: RepeatMatchMode::default(),
             max_param_depth: 8,
             debug: false,
+            strict_routes: false,
+            track_usage: false,
+            track_lookups: false,
+            cache: CacheOptions::default(),
             route_defaults: RouteOptions::default(),
+            versioning: VersioningOptions::default(),
         }
     }
 }
@@ -178,6 +533,7 @@ impl RouterConfig {
         if self.max_param_depth == 0 {
             return Err(RouterConfigError::MaxParamDepthInvalid { provided: 0 });
         }
+        self.cache.validate()?;
         self.route_defaults.validate()?;
         Ok(())
     }
@@ -239,11 +595,36 @@ impl RouterConfigBuilder {
         self
     }
 
+    pub fn strict_routes(mut self, value: bool) -> Self {
+        self.config.strict_routes = value;
+        self
+    }
+
+    pub fn track_usage(mut self, value: bool) -> Self {
+        self.config.track_usage = value;
+        self
+    }
+
+    pub fn track_lookups(mut self, value: bool) -> Self {
+        self.config.track_lookups = value;
+        self
+    }
+
+    pub fn cache(mut self, cache: CacheOptions) -> Self {
+        self.config.cache = cache;
+        self
+    }
+
     pub fn route_defaults(mut self, route_defaults: RouteOptions) -> Self {
         self.config.route_defaults = route_defaults;
         self
     }
 
+    pub fn versioning(mut self, versioning: VersioningOptions) -> Self {
+        self.config.versioning = versioning;
+        self
+    }
+
     pub fn build(self) -> Result<RouterConfig, RouterConfigError> {
         let config = self.config;
         config.validate()?;
@@ -255,12 +636,20 @@ impl RouterConfigBuilder {
 pub enum RouterConfigError {
     #[error("max_param_depth must be at least 1 (got {provided})")]
     MaxParamDepthInvalid { provided: usize },
+    #[error("cache capacity must be at least 1")]
+    CacheCapacityInvalid,
+    #[error("cache shard count must be between 1 and the capacity {capacity} (got {shards})")]
+    CacheShardsInvalid { shards: usize, capacity: usize },
     #[error("route methods cannot be empty")]
     EmptyRouteMethods,
     #[error("route priority {value} is outside the supported range {min}..={max}")]
     RoutePriorityOutOfRange { value: i32, min: i32, max: i32 },
     #[error("alias must not be empty")]
     EmptyAlias,
+    #[error("query condition for '{name}' has an invalid pattern: {error}")]
+    QueryConditionInvalid { name: String, error: String },
+    #[error("header condition for '{name}' has an invalid pattern: {error}")]
+    HeaderConditionInvalid { name: String, error: String },
 }
 
 pub type RouterOptions = RouterConfig;
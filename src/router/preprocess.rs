@@ -1,4 +1,5 @@
 use crate::path::{NormalizationOptions, PathResult, normalize_path};
+use crate::readonly::NormalizationStep;
 use crate::router::RouterOptions;
 
 #[derive(Debug, Clone)]
@@ -6,6 +7,8 @@ pub struct PreprocessOutcome {
     original: String,
     normalized: String,
     cache_key: String,
+    query: Option<String>,
+    fragment: Option<String>,
 }
 
 impl PreprocessOutcome {
@@ -20,6 +23,20 @@ impl PreprocessOutcome {
     pub fn cache_key(&self) -> &str {
         &self.cache_key
     }
+
+    /// Raw query string of a request target, without the leading `?`.
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    /// Raw fragment of a request target, without the leading `#`.
+    pub fn fragment(&self) -> Option<&str> {
+        self.fragment.as_deref()
+    }
+
+    pub(crate) fn into_query(self) -> Option<String> {
+        self.query
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -43,6 +60,69 @@ impl Preprocessor {
     pub fn apply(&self, path: &str) -> PathResult<PreprocessOutcome> {
         apply(path, &self.config)
     }
+
+    /// Path of `target` after each enabled normalization stage, starting with the
+    /// raw path. Each stage re-runs normalization with one more option switched
+    /// on, so the last output equals [`PreprocessOutcome::normalized`].
+    pub fn stages(&self, target: &str) -> Vec<NormalizationStep> {
+        let path = split_target(target).0;
+        let mut options = NormalizationOptions {
+            decode_percent: false,
+            normalize_path: false,
+            allow_duplicate_slash: self.config.allow_duplicate_slash,
+            strict_trailing_slash: self.config.strict_trailing_slash,
+            case_sensitive: true,
+        };
+        let mut stages = vec![NormalizationStep {
+            stage: "raw",
+            output: path.to_string(),
+        }];
+        let mut push = |stage: &'static str, options: &NormalizationOptions| {
+            if let Ok(output) = normalize_path(path, options) {
+                stages.push(NormalizationStep { stage, output });
+            }
+        };
+        if self.config.decode_uri {
+            options.decode_percent = true;
+            push("decode_uri", &options);
+        }
+        if self.config.normalize_path {
+            options.normalize_path = true;
+            push("normalize_path", &options);
+        }
+        if !self.config.case_sensitive {
+            options.case_sensitive = false;
+            push("lowercase", &options);
+        }
+        stages
+    }
+
+    /// Preprocesses a request target: the query and fragment are split off before
+    /// normalization and never reach the cache key.
+    pub fn apply_target(&self, target: &str) -> PathResult<PreprocessOutcome> {
+        apply_target(target, &self.config)
+    }
+}
+
+pub fn apply_target(target: &str, config: &RouterOptions) -> PathResult<PreprocessOutcome> {
+    let (path, query, fragment) = split_target(target);
+
+    let mut outcome = apply(path, config)?;
+    outcome.original = target.to_string();
+    outcome.query = query.map(str::to_string);
+    outcome.fragment = fragment.map(str::to_string);
+    Ok(outcome)
+}
+
+fn split_target(target: &str) -> (&str, Option<&str>, Option<&str>) {
+    let (rest, fragment) = match target.split_once('#') {
+        Some((rest, fragment)) => (rest, Some(fragment)),
+        None => (target, None),
+    };
+    match rest.split_once('?') {
+        Some((path, query)) => (path, Some(query), fragment),
+        None => (rest, None, fragment),
+    }
 }
 
 pub fn apply(path: &str, config: &RouterOptions) -> PathResult<PreprocessOutcome> {
@@ -61,5 +141,7 @@ pub fn apply(path: &str, config: &RouterOptions) -> PathResult<PreprocessOutcome
         original: path.to_string(),
         normalized,
         cache_key,
+        query: None,
+        fragment: None,
     })
 }
@@ -1,10 +1,19 @@
-use super::{RouterError, RouterResult};
+use super::mount::mount_path;
+use super::{RouteGroup, RouteOptions, RouteOverlay, RouterError, RouterResult};
+use crate::dump::TreeDump;
 use crate::enums::HttpMethod;
-use crate::readonly::RouterReadOnly;
-use crate::registry::RouteRegistry;
+use crate::metrics::MetricsEncoder;
+use crate::readonly::{
+    MatchTrace, RouteAnalysis, RouteFinding, RouteTable, RouterReadOnly, TargetMatch, UsageReport,
+    VersionMatch, WarmReport,
+};
+use crate::registry::{RouteRecord, RouteRegistry};
+use crate::request::RequestHeaders;
 use crate::router::RouterOptions;
-use crate::types::RouteMatch;
+use crate::types::{MiddlewareChain, RouteMatch};
+use hashbrown::HashMap as FastHashMap;
 use parking_lot::RwLock;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::OnceLock;
 
@@ -49,6 +58,151 @@ impl Router {
         Ok(key)
     }
 
+    /// Registers `path` for every method in `options`, layered over the router's
+    /// `route_defaults`. Returns one key per method, in the order of `options.methods`.
+    pub fn add_with_options(&self, path: &str, options: RouteOptions) -> RouterResult<Vec<u16>> {
+        self.add_overlay(path, options.into())
+    }
+
+    /// [`add_with_options`](Self::add_with_options) for a route group's merged
+    /// layers, whose explicit fields win over `route_defaults`.
+    pub(crate) fn add_overlay(&self, path: &str, overlay: RouteOverlay) -> RouterResult<Vec<u16>> {
+        let mut guard = self.inner.write();
+
+        if guard.readonly.get().is_some() {
+            return Err(RouterError::AddWhileSealed {
+                path: path.to_string(),
+            });
+        }
+
+        let effective = guard
+            .registry
+            .options()
+            .route_defaults
+            .merge_with(overlay.options(), overlay.explicit());
+        effective.validate()?;
+
+        let keys = guard.registry.insert_with_options(None, path, &effective)?;
+        Ok(keys)
+    }
+
+    /// Registers every `(path, options)` pair, or none of them. Each pair is merged
+    /// over `route_defaults`, validated and dry-run against the live registry and
+    /// against the pairs before it; `on_error` wraps the failure with the index of
+    /// the offending pair. Returns the keys of each pair, in input order.
+    pub(crate) fn add_all_with_options(
+        &self,
+        routes: &[(String, RouteOptions)],
+        on_error: impl Fn(usize, RouterError) -> RouterError,
+    ) -> RouterResult<Vec<Vec<u16>>> {
+        let mut guard = self.inner.write();
+
+        if let Some((path, _)) = routes.first()
+            && guard.readonly.get().is_some()
+        {
+            return Err(RouterError::AddWhileSealed { path: path.clone() });
+        }
+
+        let registry_options = guard.registry.options().clone();
+        let mut scratch = RouteRegistry::new(registry_options.clone());
+        let mut effective = Vec::with_capacity(routes.len());
+        let mut count = 0;
+        for (idx, (path, options)) in routes.iter().enumerate() {
+            let merged = registry_options.route_defaults.merge(options);
+            let checked = merged.validate().map_err(RouterError::from).and_then(|()| {
+                for &method in merged.methods.iter() {
+                    let record = RouteRecord {
+                        key: 0,
+                        method,
+                        path: path.clone(),
+                        normalized: String::new(),
+                        host: None,
+                        options: RouteOptions {
+                            methods: vec![method],
+                            ..merged.clone()
+                        },
+                    };
+                    guard.registry.check_record(&record)?;
+                    scratch.insert_record(&record)?;
+                }
+                Ok(())
+            });
+            checked.map_err(|err| on_error(idx, err))?;
+            count += merged.methods.len();
+            effective.push(merged);
+        }
+        guard.registry.check_capacity(count)?;
+
+        routes
+            .iter()
+            .zip(effective.iter())
+            .map(|((path, _), options)| guard.registry.insert_with_options(None, path, options))
+            .collect()
+    }
+
+    /// [`add_with_options`](Self::add_with_options) for routes scoped to `host`.
+    pub fn add_host_with_options(
+        &self,
+        host: &str,
+        path: &str,
+        options: RouteOptions,
+    ) -> RouterResult<Vec<u16>> {
+        let mut guard = self.inner.write();
+
+        if guard.readonly.get().is_some() {
+            return Err(RouterError::AddWhileSealed {
+                path: path.to_string(),
+            });
+        }
+
+        let effective = guard.registry.options().route_defaults.merge(&options);
+        effective.validate()?;
+
+        let keys = guard
+            .registry
+            .insert_with_options(Some(host), path, &effective)?;
+        Ok(keys)
+    }
+
+    /// Runs `build` with a [`RouteGroup`] whose routes share `prefix` and `options`.
+    pub fn group<R>(
+        &self,
+        prefix: &str,
+        options: impl Into<RouteOverlay>,
+        build: impl FnOnce(&mut RouteGroup<'_>) -> R,
+    ) -> R {
+        let mut group = RouteGroup::new(self, prefix.to_string(), options.into());
+        build(&mut group)
+    }
+
+    /// Registers a route that only matches requests whose host satisfies `host`,
+    /// e.g. `api.example.com`, `:tenant.example.com` or `*.example.com`.
+    pub fn add_host(&self, host: &str, method: HttpMethod, path: &str) -> RouterResult<u16> {
+        let mut guard = self.inner.write();
+
+        if guard.readonly.get().is_some() {
+            return Err(RouterError::AddWhileSealed {
+                path: path.to_string(),
+            });
+        }
+
+        let key = guard.registry.insert_host(host, method, path)?;
+        Ok(key)
+    }
+
+    /// Registers `path` in the route set of API `version`. Lookups through
+    /// [`find_versioned`](Self::find_versioned) fall back to older versions, so a
+    /// version only needs the routes it changes.
+    pub fn add_versioned(&self, version: u32, method: HttpMethod, path: &str) -> RouterResult<u16> {
+        let options = RouteOptions {
+            methods: vec![method],
+            version: Some(version),
+            ..RouteOptions::default()
+        };
+        let keys = self.add_with_options(path, options)?;
+        Ok(keys[0])
+    }
+
     pub fn add_bulk<I>(&self, entries: I) -> RouterResult<Vec<u16>>
     where
         I: IntoIterator<Item = (HttpMethod, String)>,
@@ -66,14 +220,130 @@ impl Router {
         Ok(keys)
     }
 
+    /// Attaches `middleware`, in order, to every route whose pattern lies under
+    /// `prefix`. Chains are composed once at [`seal`](Self::seal).
+    pub fn use_middleware<I, S>(&self, prefix: &str, middleware: I) -> RouterResult<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut guard = self.inner.write();
+
+        if guard.readonly.get().is_some() {
+            return Err(RouterError::MiddlewareWhileSealed {
+                prefix: prefix.to_string(),
+            });
+        }
+
+        let middleware = middleware.into_iter().map(Into::into).collect();
+        guard.registry.add_middleware_scope(prefix, middleware);
+        Ok(())
+    }
+
+    /// Grafts every route of `other` under `prefix`, which may contain parameters.
+    ///
+    /// Routes receive fresh keys in this router; the returned map goes from the key a
+    /// route had in `other` to its key here. The whole batch is dry-run against the
+    /// live trees and against itself before any route is added, so a conflict leaves
+    /// this router unchanged. Each mounted route keeps the middleware chain it had
+    /// in `other`, after this router's own scopes; `other`'s scopes do not reach
+    /// routes registered here.
+    pub fn mount(&self, prefix: &str, other: &Router) -> RouterResult<HashMap<u16, u16>> {
+        let mounted = other.with_registry(|registry| {
+            let chains = registry.middleware_chains();
+            registry
+                .records()
+                .iter()
+                .map(|record| RouteRecord {
+                    path: mount_path(prefix, &record.path),
+                    options: RouteOptions {
+                        middleware: chains
+                            .get(record.key as usize)
+                            .map(|chain| chain.to_vec())
+                            .unwrap_or_default(),
+                        ..record.options.clone()
+                    },
+                    ..record.clone()
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let mut guard = self.inner.write();
+
+        if guard.readonly.get().is_some() {
+            return Err(RouterError::MountWhileSealed {
+                prefix: prefix.to_string(),
+            });
+        }
+
+        let conflict = |record: &RouteRecord, source: RouterError| RouterError::MountConflict {
+            prefix: prefix.to_string(),
+            path: record.path.clone(),
+            source: Box::new(source),
+        };
+        guard.registry.check_capacity(mounted.len())?;
+        let mut scratch = RouteRegistry::new(guard.registry.options().clone());
+        for record in mounted.iter() {
+            guard
+                .registry
+                .check_record(record)
+                .and_then(|()| scratch.insert_record(record).map(drop))
+                .map_err(|source| conflict(record, source))?;
+        }
+
+        let mut remap = HashMap::with_capacity(mounted.len());
+        for record in mounted.iter() {
+            let key = guard
+                .registry
+                .insert_record(record)
+                .map_err(|source| conflict(record, source))?;
+            remap.insert(record.key, key);
+        }
+        Ok(remap)
+    }
+
+    /// Seals the router. Never fails; [`strict_routes`](RouterOptions::strict_routes)
+    /// is only enforced by [`try_seal`](Self::try_seal), and sealing a strict router
+    /// here logs a warning pointing there.
     pub fn seal(&self) {
         let mut guard = self.inner.write();
+        if guard.readonly.get().is_none() && guard.registry.options().strict_routes {
+            tracing::event!(
+                tracing::Level::WARN,
+                "strict_routes is set but seal() skips route checks; call try_seal() to enforce them"
+            );
+        }
+        seal_registry(&mut guard);
+    }
 
-        guard.registry.finalize();
-        let snapshot = RouterReadOnly::from_radix_tree(guard.registry.tree());
-        let arc = Arc::new(snapshot);
-        guard.registry.reset_after_seal();
-        let _ = guard.readonly.set(arc);
+    /// Seals the router, first running [`analyze`](Self::analyze) when strict
+    /// route checks are enabled. On conflicts the router stays mutable.
+    pub fn try_seal(&self) -> RouterResult<()> {
+        let mut guard = self.inner.write();
+
+        if guard.readonly.get().is_none() && guard.registry.options().strict_routes {
+            let findings: Vec<RouteFinding> = analyze_registry(&guard.registry)?
+                .conflicts()
+                .cloned()
+                .collect();
+            if !findings.is_empty() {
+                return Err(RouterError::RouteConflicts { findings });
+            }
+        }
+
+        seal_registry(&mut guard);
+        Ok(())
+    }
+
+    /// Reports routes that other routes shadow, without sealing. Before sealing the
+    /// routes are replayed into a scratch tree, so this costs about one seal.
+    pub fn analyze(&self) -> RouterResult<RouteAnalysis> {
+        let guard = self.inner.read();
+
+        match guard.readonly.get() {
+            Some(ro) => Ok(ro.analyze()),
+            None => analyze_registry(&guard.registry),
+        }
     }
 
     pub fn find(&self, method: HttpMethod, path: &str) -> RouterResult<RouteMatch> {
@@ -85,6 +355,163 @@ impl Router {
         }
     }
 
+    /// Resolves several lookups under one read guard; see
+    /// [`RouterReadOnly::find_many`]. Results are in input order.
+    pub fn find_many(
+        &self,
+        requests: &[(HttpMethod, &str)],
+    ) -> RouterResult<Vec<RouterResult<RouteMatch>>> {
+        let guard = self.inner.read();
+
+        match guard.readonly.get() {
+            Some(ro) => Ok(ro
+                .find_many(requests)
+                .into_iter()
+                .map(|result| result.map_err(RouterError::from))
+                .collect()),
+            None => Err(RouterError::FindWhileMutable),
+        }
+    }
+
+    /// Pre-populates the route cache; see [`RouterReadOnly::warm_cache`].
+    pub fn warm_cache<I, S>(&self, paths: I) -> RouterResult<WarmReport>
+    where
+        I: IntoIterator<Item = (HttpMethod, S)>,
+        S: AsRef<str>,
+    {
+        let guard = self.inner.read();
+
+        match guard.readonly.get() {
+            Some(ro) => Ok(ro.warm_cache(paths)),
+            None => Err(RouterError::FindWhileMutable),
+        }
+    }
+
+    /// OpenMetrics exposition of the sealed snapshot's counters; see
+    /// [`MetricsEncoder`].
+    pub fn encode_metrics(&self, encoder: &MetricsEncoder) -> RouterResult<String> {
+        let guard = self.inner.read();
+
+        match guard.readonly.get() {
+            Some(ro) => Ok(encoder.encode(ro)),
+            None => Err(RouterError::FindWhileMutable),
+        }
+    }
+
+    /// Per-route hit counts; see [`RouterReadOnly::usage_report`].
+    pub fn usage_report(&self) -> RouterResult<Option<UsageReport>> {
+        let guard = self.inner.read();
+
+        match guard.readonly.get() {
+            Some(ro) => Ok(ro.usage_report()),
+            None => Err(RouterError::FindWhileMutable),
+        }
+    }
+
+    /// Currently cached lookups; see [`RouterReadOnly::hot_set`].
+    pub fn hot_set(&self) -> RouterResult<Vec<(HttpMethod, String)>> {
+        let guard = self.inner.read();
+
+        match guard.readonly.get() {
+            Some(ro) => Ok(ro.hot_set()),
+            None => Err(RouterError::FindWhileMutable),
+        }
+    }
+
+    /// Step-by-step account of how `target` resolves; see [`RouterReadOnly::explain`].
+    pub fn explain(&self, method: HttpMethod, target: &str) -> RouterResult<MatchTrace> {
+        let guard = self.inner.read();
+
+        match guard.readonly.get() {
+            Some(ro) => Ok(ro.explain(method, target)),
+            None => Err(RouterError::FindWhileMutable),
+        }
+    }
+
+    /// Same as [`find`](Self::find), sharing the cached result; see
+    /// [`RouterReadOnly::find_shared`].
+    pub fn find_shared(&self, method: HttpMethod, path: &str) -> RouterResult<Arc<RouteMatch>> {
+        let guard = self.inner.read();
+
+        match guard.readonly.get() {
+            Some(ro) => Ok(ro.find_shared(method, path)?),
+            None => Err(RouterError::FindWhileMutable),
+        }
+    }
+
+    pub fn find_target(&self, method: HttpMethod, target: &str) -> RouterResult<TargetMatch> {
+        let guard = self.inner.read();
+
+        match guard.readonly.get() {
+            Some(ro) => Ok(ro.find_target(method, target)?),
+            None => Err(RouterError::FindWhileMutable),
+        }
+    }
+
+    pub fn find_request<H: RequestHeaders>(
+        &self,
+        method: HttpMethod,
+        path: &str,
+        headers: &H,
+    ) -> RouterResult<RouteMatch> {
+        let guard = self.inner.read();
+
+        match guard.readonly.get() {
+            Some(ro) => Ok(ro.find_request(method, path, headers)?),
+            None => Err(RouterError::FindWhileMutable),
+        }
+    }
+
+    pub fn find_versioned<H: RequestHeaders>(
+        &self,
+        method: HttpMethod,
+        path: &str,
+        headers: &H,
+    ) -> RouterResult<VersionMatch> {
+        let guard = self.inner.read();
+
+        match guard.readonly.get() {
+            Some(ro) => Ok(ro.find_versioned(method, path, headers)?),
+            None => Err(RouterError::FindWhileMutable),
+        }
+    }
+
+    pub fn find_with_middleware(
+        &self,
+        method: HttpMethod,
+        path: &str,
+    ) -> RouterResult<(RouteMatch, MiddlewareChain)> {
+        let guard = self.inner.read();
+
+        match guard.readonly.get() {
+            Some(ro) => Ok(ro.find_with_middleware(method, path)?),
+            None => Err(RouterError::FindWhileMutable),
+        }
+    }
+
+    pub fn find_host(
+        &self,
+        method: HttpMethod,
+        host: &str,
+        path: &str,
+    ) -> RouterResult<RouteMatch> {
+        let guard = self.inner.read();
+
+        match guard.readonly.get() {
+            Some(ro) => Ok(ro.find_host(method, host, path)?),
+            None => Err(RouterError::FindWhileMutable),
+        }
+    }
+
+    pub fn find_corrected(&self, method: HttpMethod, path: &str) -> RouterResult<String> {
+        let guard = self.inner.read();
+
+        match guard.readonly.get() {
+            Some(ro) => Ok(ro.find_corrected(method, path)?),
+            None => Err(RouterError::FindWhileMutable),
+        }
+    }
+
     pub fn get_readonly(&self) -> RouterResult<Arc<RouterReadOnly>> {
         let guard = self.inner.read();
 
@@ -94,8 +521,51 @@ impl Router {
         }
     }
 
+    /// Structure of the route tree: the snapshot once sealed, the mutable tree before.
+    pub fn dump(&self) -> TreeDump {
+        let guard = self.inner.read();
+
+        match guard.readonly.get() {
+            Some(ro) => ro.dump(),
+            None => guard.registry.tree().dump(),
+        }
+    }
+
+    /// Every registered route, before or after sealing.
+    pub fn routes(&self) -> RouteTable {
+        let guard = self.inner.read();
+
+        match guard.readonly.get() {
+            Some(ro) => ro.routes().clone(),
+            None => RouteTable::new(guard.registry.records().to_vec()),
+        }
+    }
+
     pub(crate) fn with_registry<R>(&self, f: impl FnOnce(&RouteRegistry) -> R) -> R {
         let guard = self.inner.read();
         f(&guard.registry)
     }
 }
+
+fn seal_registry(state: &mut RouterState) {
+    if state.readonly.get().is_some() {
+        return;
+    }
+    state.registry.finalize();
+    let snapshot = RouterReadOnly::from_registry(&state.registry);
+    state.registry.reset_after_seal();
+    let _ = state.readonly.set(Arc::new(snapshot));
+}
+
+fn analyze_registry(registry: &RouteRegistry) -> RouterResult<RouteAnalysis> {
+    let mut scratch = RouteRegistry::new(registry.options().clone());
+    let mut keys = FastHashMap::new();
+    for record in registry.records() {
+        let key = scratch.insert_record(record)?;
+        keys.insert(key, record.key);
+    }
+    scratch.finalize();
+    Ok(RouterReadOnly::from_registry(&scratch)
+        .analyze()
+        .remap(&keys))
+}
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 pub type ParamRange = (usize, usize);
 pub type CapturedParam = (String, ParamRange);
 pub type RouteParams = HashMap<String, String>;
 pub type RouteMatch = (u16, RouteParams);
+pub type MiddlewareChain = Arc<[String]>;
@@ -0,0 +1,3 @@
+mod source;
+
+pub use source::{VersionSource, VersioningOptions, resolve_version};
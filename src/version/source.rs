@@ -0,0 +1,140 @@
+use crate::request::RequestHeaders;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// Where a requested API version is read from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VersionSource {
+    /// First path segment made of `prefix` followed by digits, e.g. `/v3/users`.
+    /// The segment is stripped before the path is matched.
+    PathPrefix { prefix: String },
+    /// A header holding the version, as `3` or `v3`.
+    Header { name: String },
+    /// A media-type parameter of the `Accept` header, e.g.
+    /// `application/json; version=3`.
+    MediaTypeParam { name: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VersioningOptions {
+    /// Sources tried in order; the first one yielding a version wins.
+    pub sources: Vec<VersionSource>,
+    /// Version assumed when no source yields one. `None` resolves to the newest
+    /// registered version.
+    pub default_version: Option<u32>,
+}
+
+impl Default for VersioningOptions {
+    fn default() -> Self {
+        Self {
+            sources: vec![
+                VersionSource::PathPrefix {
+                    prefix: "v".to_string(),
+                },
+                VersionSource::Header {
+                    name: "api-version".to_string(),
+                },
+                VersionSource::MediaTypeParam {
+                    name: "version".to_string(),
+                },
+            ],
+            default_version: None,
+        }
+    }
+}
+
+/// Reads the requested version from `path` and `headers`. Returns the version, if
+/// any, and the path left to match once a version prefix has been stripped.
+pub fn resolve_version<'p>(
+    options: &VersioningOptions,
+    path: &'p str,
+    headers: &dyn RequestHeaders,
+) -> (Option<u32>, Cow<'p, str>) {
+    for source in options.sources.iter() {
+        let found = match source {
+            VersionSource::PathPrefix { prefix } => {
+                if let Some((version, rest)) = strip_version_prefix(path, prefix) {
+                    return (Some(version), rest);
+                }
+                None
+            }
+            VersionSource::Header { name } => headers.header(name).and_then(parse_version),
+            VersionSource::MediaTypeParam { name } => headers
+                .header("accept")
+                .and_then(|accept| media_type_version(accept, name)),
+        };
+        if found.is_some() {
+            return (found, Cow::Borrowed(path));
+        }
+    }
+    (options.default_version, Cow::Borrowed(path))
+}
+
+fn strip_version_prefix<'p>(path: &'p str, prefix: &str) -> Option<(u32, Cow<'p, str>)> {
+    let trimmed = path.strip_prefix('/')?;
+    let end = trimmed.find(['/', '?', '#']).unwrap_or(trimmed.len());
+    let segment = &trimmed[..end];
+    let digits = segment
+        .get(..prefix.len())
+        .filter(|head| head.eq_ignore_ascii_case(prefix))
+        .map(|_| &segment[prefix.len()..])?;
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let version = digits.parse().ok()?;
+
+    let rest = &trimmed[end..];
+    if rest.starts_with('/') {
+        Some((version, Cow::Borrowed(rest)))
+    } else {
+        // `/v3` and `/v3?x=1` address the version root
+        Some((version, Cow::Owned(format!("/{rest}"))))
+    }
+}
+
+fn parse_version(raw: &str) -> Option<u32> {
+    let raw = raw.trim();
+    let digits = raw
+        .strip_prefix('v')
+        .or_else(|| raw.strip_prefix('V'))
+        .unwrap_or(raw);
+    digits.parse().ok()
+}
+
+fn media_type_version(accept: &str, name: &str) -> Option<u32> {
+    accept.split(',').find_map(|media_type| {
+        media_type.split(';').skip(1).find_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            if key.trim().eq_ignore_ascii_case(name) {
+                parse_version(value.trim().trim_matches('"'))
+            } else {
+                None
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_prefix_is_stripped() {
+        let strip = |path| strip_version_prefix(path, "v").map(|(v, rest)| (v, rest.into_owned()));
+        assert_eq!(strip("/v3/users"), Some((3, "/users".to_string())));
+        assert_eq!(strip("/V12"), Some((12, "/".to_string())));
+        assert_eq!(strip("/v2?x=1"), Some((2, "/?x=1".to_string())));
+        assert_eq!(strip("/video"), None);
+        assert_eq!(strip("/v/users"), None);
+    }
+
+    #[test]
+    fn media_type_param_is_read() {
+        assert_eq!(
+            media_type_version("text/html, application/json; version=\"4\"", "version"),
+            Some(4)
+        );
+        assert_eq!(media_type_version("application/json", "version"), None);
+    }
+}
@@ -0,0 +1,158 @@
+use bunner_router_rs::{
+    HttpMethod, Router, RouterError, RouterOptions,
+    readonly::{FindingKind, ReadOnlyError},
+};
+
+#[test]
+fn router_when_pattern_covered_by_earlier_one_then_reports_unreachable() {
+    let router = Router::new(None);
+    let word = router
+        .add(HttpMethod::Get, "/items/:id(\\w+)")
+        .expect("route should register");
+    let digits = router
+        .add(HttpMethod::Get, "/items/:id(\\d+)")
+        .expect("route should register");
+
+    let before = router.analyze().expect("analysis should run");
+    assert_eq!(before.findings().len(), 1);
+    let finding = &before.findings()[0];
+    assert_eq!(finding.kind, FindingKind::Unreachable);
+    assert_eq!(finding.method, HttpMethod::Get);
+    assert_eq!(finding.route, digits);
+    assert_eq!(finding.shadowed_by, word);
+    assert_eq!(finding.path, "/items/:id(\\d+)");
+    assert_eq!(finding.example, "/items/1");
+    assert!(finding.is_conflict());
+
+    router.seal();
+    assert_eq!(router.analyze().expect("analysis should run"), before);
+    let (key, _) = router
+        .find(HttpMethod::Get, &finding.example)
+        .expect("example should match");
+    assert_eq!(key, word);
+}
+
+#[test]
+fn router_when_routes_partially_overlap_then_reports_ambiguous_or_overlapping() {
+    let router = Router::new(None);
+    let digits = router
+        .add(HttpMethod::Get, "/users/:id(\\d+)")
+        .expect("route should register");
+    let word = router
+        .add(HttpMethod::Get, "/users/:id(\\w+)")
+        .expect("route should register");
+    let name = router
+        .add(HttpMethod::Get, "/files/:name")
+        .expect("route should register");
+    let rest = router
+        .add(HttpMethod::Get, "/files/*")
+        .expect("route should register");
+    router
+        .add(HttpMethod::Post, "/users/:id(\\w+)")
+        .expect("route should register");
+    router
+        .add(HttpMethod::Get, "/users/me")
+        .expect("route should register");
+
+    let analysis = router.analyze().expect("analysis should run");
+    let summary: Vec<_> = analysis
+        .findings()
+        .iter()
+        .map(|finding| (finding.kind, finding.route, finding.shadowed_by))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (FindingKind::Ambiguous, word, digits),
+            (FindingKind::Overlapping, rest, name),
+        ]
+    );
+    assert_eq!(analysis.conflicts().count(), 1);
+
+    let clean = Router::new(None);
+    clean
+        .add(HttpMethod::Get, "/users/:id")
+        .expect("route should register");
+    clean
+        .add(HttpMethod::Get, "/users/:id/posts/*")
+        .expect("route should register");
+    assert!(clean.analyze().expect("analysis should run").is_empty());
+}
+
+#[test]
+fn router_when_strict_and_routes_conflict_then_seal_fails_and_router_stays_mutable() {
+    let options = RouterOptions::builder()
+        .strict_routes(true)
+        .build()
+        .expect("config should build");
+    let router = Router::new(Some(options.clone()));
+    router
+        .add(HttpMethod::Get, "/items/:id(\\w+)")
+        .expect("route should register");
+    let digits = router
+        .add(HttpMethod::Get, "/items/:id(\\d+)")
+        .expect("route should register");
+
+    match router.try_seal().expect_err("conflict should fail sealing") {
+        RouterError::RouteConflicts { findings } => {
+            assert_eq!(findings.len(), 1);
+            assert_eq!(findings[0].route, digits);
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    match router
+        .find(HttpMethod::Get, "/items/1")
+        .expect_err("router should still be mutable")
+    {
+        RouterError::FindWhileMutable => {}
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    let overlapping = Router::new(Some(options));
+    overlapping
+        .add(HttpMethod::Get, "/files/:name")
+        .expect("route should register");
+    overlapping
+        .add(HttpMethod::Get, "/files/*")
+        .expect("route should register");
+    overlapping
+        .try_seal()
+        .expect("overlaps alone should not fail sealing");
+    match overlapping
+        .find(HttpMethod::Get, "/missing")
+        .expect_err("no route should match")
+    {
+        RouterError::ReadOnly(ReadOnlyError::RouteNotFound { .. }) => {}
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn router_when_strict_and_routes_conflict_then_seal_still_seals() {
+    let options = RouterOptions::builder()
+        .strict_routes(true)
+        .build()
+        .expect("config should build");
+    let router = Router::new(Some(options));
+    let words = router
+        .add(HttpMethod::Get, "/items/:id(\\w+)")
+        .expect("route should register");
+    router
+        .add(HttpMethod::Get, "/items/:id(\\d+)")
+        .expect("route should register");
+
+    router.seal();
+
+    let (matched_key, _) = router
+        .find(HttpMethod::Get, "/items/1")
+        .expect("sealed router should resolve lookups");
+    assert_eq!(matched_key, words);
+    assert_eq!(
+        router
+            .analyze()
+            .expect("analysis should run")
+            .conflicts()
+            .count(),
+        1
+    );
+}
@@ -1,4 +1,9 @@
-use bunner_router_rs::{HttpMethod, Router};
+use bunner_router_rs::readonly::{Decision, ReadOnlyError, TraceStep};
+use bunner_router_rs::{
+    CacheAdmission, CacheOptions, CacheScope, DefaultCachePolicy, HttpMethod, RouteCachePolicy,
+    RouteOptions, Router, RouterError, RouterOptions, RouterOptionsError, RouterReadOnly,
+};
+use std::sync::{Arc, Barrier};
 
 #[test]
 fn router_when_cache_enabled_then_records_hits_and_misses() {
@@ -34,3 +39,366 @@ fn router_when_cache_enabled_then_records_hits_and_misses() {
     assert_eq!(hits_after_second, 1);
     assert_eq!(misses_after_second, 1);
 }
+
+#[test]
+fn router_when_cache_disabled_then_reports_no_metrics() {
+    let options = RouterOptions::builder()
+        .cache(CacheOptions::disabled())
+        .build()
+        .expect("options should build");
+    let router = Router::new(Some(options));
+    router
+        .add(HttpMethod::Get, "/uncached")
+        .expect("route should register");
+    router.seal();
+
+    router
+        .find(HttpMethod::Get, "/uncached")
+        .expect("lookup should succeed");
+
+    let readonly = router
+        .get_readonly()
+        .expect("readonly snapshot should be available");
+    assert_eq!(readonly.cache_metrics(), None);
+    assert_eq!(readonly.cache_entries(), None);
+}
+
+#[test]
+fn router_when_cache_hit_then_shares_the_cached_match() {
+    let options = RouterOptions::builder()
+        .cache(CacheOptions {
+            enabled: true,
+            capacity: 4,
+            shards: 2,
+            ..CacheOptions::default()
+        })
+        .build()
+        .expect("options should build");
+    let router = Router::new(Some(options));
+    router
+        .add(HttpMethod::Get, "/users/:id")
+        .expect("route should register");
+    router.seal();
+
+    let first = router
+        .find_shared(HttpMethod::Get, "/users/7")
+        .expect("first lookup should succeed");
+    let second = router
+        .find_shared(HttpMethod::Get, "/users/7")
+        .expect("second lookup should succeed");
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(second.1.get("id").map(|s| s.as_str()), Some("7"));
+
+    for id in 0..16 {
+        router
+            .find(HttpMethod::Get, &format!("/users/{id}"))
+            .expect("lookup should succeed");
+    }
+    let readonly = router
+        .get_readonly()
+        .expect("readonly snapshot should be available");
+    assert!(readonly.cache_entries().expect("cache is enabled") <= 4);
+}
+
+#[test]
+fn router_when_cache_shards_exceed_capacity_then_rejects_options() {
+    match RouterOptions::builder()
+        .cache(CacheOptions {
+            enabled: true,
+            capacity: 4,
+            shards: 8,
+            ..CacheOptions::default()
+        })
+        .build()
+        .expect_err("more shards than entries should be rejected")
+    {
+        RouterOptionsError::CacheShardsInvalid { shards, capacity } => {
+            assert_eq!((shards, capacity), (8, 4));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn router_when_negative_caching_enabled_then_repeated_misses_hit_the_cache() {
+    let options = RouterOptions::builder()
+        .cache(CacheOptions {
+            negative: true,
+            ..CacheOptions::default()
+        })
+        .build()
+        .expect("options should build");
+    let router = Router::new(Some(options));
+    router
+        .add(HttpMethod::Get, "/known")
+        .expect("route should register");
+    router.seal();
+
+    for _ in 0..3 {
+        match router
+            .find(HttpMethod::Get, "/wp-login.php")
+            .expect_err("unknown path should not match")
+        {
+            RouterError::ReadOnly(ReadOnlyError::RouteNotFound { .. }) => {}
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    let readonly = router
+        .get_readonly()
+        .expect("readonly snapshot should be available");
+    let counters = readonly.cache_counters().expect("cache is enabled");
+    assert_eq!(counters.misses, 1);
+    assert_eq!(counters.negative_hits, 2);
+    assert_eq!(counters.hits, 0);
+
+    let trace = readonly.explain(HttpMethod::Get, "/wp-login.php");
+    assert_eq!(trace.steps, [TraceStep::NegativeCache]);
+    assert_eq!(trace.decision, Decision::NotFound);
+}
+
+#[test]
+fn router_when_frequency_admission_enabled_then_scans_do_not_evict_hot_routes() {
+    let options = RouterOptions::builder()
+        .cache(CacheOptions {
+            capacity: 2,
+            shards: 1,
+            negative: true,
+            admission: CacheAdmission::Frequency,
+            ..CacheOptions::default()
+        })
+        .build()
+        .expect("options should build");
+    let router = Router::new(Some(options));
+    router
+        .add(HttpMethod::Get, "/users/:id")
+        .expect("route should register");
+    router.seal();
+
+    for _ in 0..4 {
+        router
+            .find(HttpMethod::Get, "/users/1")
+            .expect("hot route should match");
+        router
+            .find(HttpMethod::Get, "/users/2")
+            .expect("hot route should match");
+    }
+    for idx in 0..32 {
+        let _ = router.find(HttpMethod::Get, &format!("/scan/{idx}"));
+    }
+
+    let readonly = router
+        .get_readonly()
+        .expect("readonly snapshot should be available");
+    let before = readonly.cache_counters().expect("cache is enabled");
+    assert_eq!(before.rejected, 32);
+
+    router
+        .find(HttpMethod::Get, "/users/1")
+        .expect("hot route should match");
+    let after = readonly.cache_counters().expect("cache is enabled");
+    assert_eq!(after.hits, before.hits + 1);
+}
+
+#[test]
+fn router_when_cache_is_per_thread_then_threads_do_not_share_entries() {
+    let options = RouterOptions::builder()
+        .cache(CacheOptions {
+            scope: CacheScope::PerThread,
+            ..CacheOptions::default()
+        })
+        .build()
+        .expect("options should build");
+    let router = Router::new(Some(options));
+    router
+        .add(HttpMethod::Get, "/users/:id")
+        .expect("route should register");
+    router.seal();
+
+    let readonly = router
+        .get_readonly()
+        .expect("readonly snapshot should be available");
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                for _ in 0..2 {
+                    readonly
+                        .find(HttpMethod::Get, "/users/1")
+                        .expect("lookup should succeed");
+                }
+            })
+            .join()
+            .expect("lookup thread should finish");
+    });
+    assert_eq!(readonly.cache_entries(), Some(0));
+
+    router
+        .find(HttpMethod::Get, "/users/1")
+        .expect("lookup should succeed");
+    assert_eq!(readonly.cache_entries(), Some(1));
+    assert_eq!(readonly.cache_metrics(), Some((1, 2)));
+}
+
+#[test]
+fn router_when_cache_is_per_thread_then_entries_and_hot_set_cover_live_threads() {
+    let options = RouterOptions::builder()
+        .cache(CacheOptions {
+            scope: CacheScope::PerThread,
+            ..CacheOptions::default()
+        })
+        .build()
+        .expect("options should build");
+    let router = Router::new(Some(options));
+    router
+        .add(HttpMethod::Get, "/users/:id")
+        .expect("route should register");
+    router.seal();
+
+    let readonly = router
+        .get_readonly()
+        .expect("readonly snapshot should be available");
+    readonly
+        .find(HttpMethod::Get, "/users/1")
+        .expect("lookup should succeed");
+    let (looked_up, release) = (Barrier::new(2), Barrier::new(2));
+    std::thread::scope(|scope| {
+        let worker = scope.spawn(|| {
+            for path in ["/users/1", "/users/2"] {
+                readonly
+                    .find(HttpMethod::Get, path)
+                    .expect("lookup should succeed");
+            }
+            looked_up.wait();
+            release.wait();
+        });
+        looked_up.wait();
+        assert_eq!(readonly.cache_entries(), Some(3));
+        let mut hot: Vec<String> = readonly
+            .hot_set()
+            .into_iter()
+            .map(|(method, path)| {
+                assert_eq!(method, HttpMethod::Get);
+                path
+            })
+            .collect();
+        hot.sort();
+        assert_eq!(hot, ["/users/1", "/users/2"]);
+        release.wait();
+        worker.join().expect("lookup thread should finish");
+    });
+
+    assert_eq!(readonly.cache_entries(), Some(1));
+    assert_eq!(
+        readonly.hot_set(),
+        [(HttpMethod::Get, "/users/1".to_string())]
+    );
+}
+
+#[test]
+fn router_when_many_threads_look_up_then_metrics_aggregate_all_of_them() {
+    let router = Router::new(None);
+    router
+        .add(HttpMethod::Get, "/health")
+        .expect("route should register");
+    router.seal();
+
+    let readonly = router
+        .get_readonly()
+        .expect("readonly snapshot should be available");
+    std::thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                for _ in 0..100 {
+                    readonly
+                        .find(HttpMethod::Get, "/health")
+                        .expect("lookup should succeed");
+                }
+            });
+        }
+    });
+
+    let (hits, misses) = readonly
+        .cache_metrics()
+        .expect("cache metrics should be present");
+    assert_eq!(hits + misses, 800);
+    assert!(misses >= 1);
+}
+
+#[test]
+fn router_when_route_opts_out_of_cache_then_lookups_always_walk_the_tree() {
+    let router = Router::new(None);
+    router
+        .add_with_options(
+            "/objects/:uuid",
+            RouteOptions::builder()
+                .cache(RouteCachePolicy::Never)
+                .build()
+                .expect("options should build"),
+        )
+        .expect("route should register");
+    router.seal();
+
+    let readonly = router
+        .get_readonly()
+        .expect("readonly snapshot should be available");
+    for _ in 0..3 {
+        readonly
+            .find(HttpMethod::Get, "/objects/9f0c")
+            .expect("lookup should succeed");
+    }
+    assert_eq!(readonly.cache_entries(), Some(0));
+    assert_eq!(readonly.cache_metrics(), Some((0, 3)));
+
+    let restored = RouterReadOnly::from_bytes(&readonly.to_bytes()).expect("image should load");
+    for _ in 0..2 {
+        restored
+            .find(HttpMethod::Get, "/objects/9f0c")
+            .expect("lookup should succeed");
+    }
+    assert_eq!(restored.cache_entries(), Some(0));
+}
+
+#[test]
+fn router_when_default_policy_is_low_cardinality_then_only_bounded_routes_are_cached() {
+    let options = RouterOptions::builder()
+        .cache(CacheOptions {
+            default_policy: DefaultCachePolicy::LowCardinality,
+            ..CacheOptions::default()
+        })
+        .build()
+        .expect("options should build");
+    let router = Router::new(Some(options));
+    router
+        .add(HttpMethod::Get, "/docs/:lang(en|de)/index")
+        .expect("route should register");
+    router
+        .add(HttpMethod::Get, "/users/:id")
+        .expect("route should register");
+    router
+        .add(HttpMethod::Get, "/files/*")
+        .expect("route should register");
+    router
+        .add_with_options(
+            "/teams/:id",
+            RouteOptions::builder()
+                .cache(RouteCachePolicy::Always)
+                .build()
+                .expect("options should build"),
+        )
+        .expect("route should register");
+    router.seal();
+
+    let readonly = router
+        .get_readonly()
+        .expect("readonly snapshot should be available");
+    let entries_after = |path: &str| {
+        readonly
+            .find(HttpMethod::Get, path)
+            .expect("lookup should succeed");
+        readonly.cache_entries().expect("cache is enabled")
+    };
+    assert_eq!(entries_after("/docs/en/index"), 1);
+    assert_eq!(entries_after("/users/1"), 1);
+    assert_eq!(entries_after("/files/a/b"), 1);
+    assert_eq!(entries_after("/teams/1"), 2);
+}
@@ -0,0 +1,115 @@
+use bunner_router_rs::{
+    HttpMethod, Router,
+    dump::{EdgeDump, PartDump},
+};
+
+#[test]
+fn router_when_sealed_then_dump_shows_compressed_edges() {
+    let router = Router::new(None);
+    router
+        .add(HttpMethod::Get, "/api/v1/users")
+        .expect("route should register");
+    router
+        .add(HttpMethod::Post, "/users/:id(\\d+)")
+        .expect("route should register");
+
+    let before = router.dump();
+    assert_eq!(before.root.node_count(), 6);
+    assert!(
+        before
+            .root
+            .children
+            .iter()
+            .all(|child| matches!(child.edge, EdgeDump::Static(_)))
+    );
+
+    router.seal();
+    let after = router.dump();
+    let api = &after.root.children[0];
+    assert_eq!(api.edge, EdgeDump::Static("api".to_string()));
+    assert_eq!(api.children[0].edge, EdgeDump::Fused("v1".to_string()));
+
+    let id = &after.root.children[1].children[0];
+    assert_eq!(
+        id.edge,
+        EdgeDump::Pattern(vec![PartDump::Param {
+            name: "id".to_string(),
+            constraint: Some("\\d+".to_string()),
+        }])
+    );
+    assert_eq!(id.routes.len(), 1);
+    assert_eq!(id.routes[0].method, HttpMethod::Post);
+
+    assert_eq!(
+        router
+            .get_readonly()
+            .expect("sealed router should expose its snapshot")
+            .dump(),
+        after
+    );
+}
+
+#[test]
+fn router_when_dumped_then_json_and_dot_are_stable() {
+    let router = Router::new(None);
+    router
+        .add(HttpMethod::Get, "/files/*")
+        .expect("route should register");
+    router
+        .add(HttpMethod::Get, "/a\"b")
+        .expect("route should register");
+    router.seal();
+    let dump = router.dump();
+
+    assert_eq!(
+        dump.to_json(),
+        concat!(
+            r#"{"root":{"kind":"root","edge":"/","routes":{},"wildcard_routes":{},"children":["#,
+            r#"{"kind":"static","edge":"a\"b","routes":{"GET":1},"wildcard_routes":{},"children":[]},"#,
+            r#"{"kind":"static","edge":"files","routes":{},"wildcard_routes":{"GET":0},"children":[]}"#,
+            r#"]},"static_routes":[]}"#
+        )
+    );
+    assert_eq!(
+        dump.to_dot(),
+        concat!(
+            "digraph router {\n",
+            "  node [shape=box, fontname=\"monospace\"];\n",
+            "  n0 [label=\"/\"];\n",
+            "  n0 -> n1 [label=\"a\\\"b\"];\n",
+            "  n1 [label=\"a\\\"b\\nGET -> 1\"];\n",
+            "  n0 -> n2 [label=\"files\"];\n",
+            "  n2 [label=\"files\\nGET * -> 0\"];\n",
+            "}\n"
+        )
+    );
+}
+
+#[test]
+fn router_when_registration_order_differs_then_dumps_are_identical() {
+    let paths = ["/b", "/a/:x", "/c/d", "/a/y", "/e"];
+    let build = |order: &[usize]| {
+        let router = Router::new(None);
+        let mut keys = vec![0; paths.len()];
+        for &idx in order {
+            keys[idx] = router
+                .add(HttpMethod::Get, paths[idx])
+                .expect("route should register");
+        }
+        router.seal();
+        (router.dump(), keys)
+    };
+
+    let (forward, forward_keys) = build(&[0, 1, 2, 3, 4]);
+    let (reverse, reverse_keys) = build(&[4, 3, 2, 1, 0]);
+    let relabel = |json: String, keys: &[u16]| {
+        keys.iter().enumerate().fold(json, |json, (idx, key)| {
+            json.replace(&format!("\"GET\":{key}}}"), &format!("\"GET\":#{idx}}}"))
+        })
+    };
+    assert_eq!(
+        relabel(forward.to_json(), &forward_keys),
+        relabel(reverse.to_json(), &reverse_keys)
+    );
+    assert_eq!(forward.root.node_count(), reverse.root.node_count());
+}
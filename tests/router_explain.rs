@@ -0,0 +1,126 @@
+use bunner_router_rs::{
+    HttpMethod, Router, RouterError,
+    readonly::{Decision, MatchSource, PatternOutcome, TraceStep},
+};
+
+fn sample_router() -> Router {
+    let router = Router::new(None);
+    router
+        .add(HttpMethod::Get, "/users/:id(\\d+)")
+        .expect("route should register");
+    router
+        .add(HttpMethod::Post, "/users/me")
+        .expect("route should register");
+    router
+        .add(HttpMethod::Get, "/users/*")
+        .expect("route should register");
+    router.seal();
+    router
+}
+
+#[test]
+fn router_when_constraint_rejects_segment_then_trace_shows_fallback_to_wildcard() {
+    let router = sample_router();
+    let trace = router
+        .explain(HttpMethod::Get, "/Users//me?x=1")
+        .expect("sealed router should explain");
+
+    let stages: Vec<_> = trace
+        .normalization
+        .iter()
+        .map(|step| (step.stage, step.output.as_str()))
+        .collect();
+    assert_eq!(
+        stages,
+        [
+            ("raw", "/Users//me"),
+            ("normalize_path", "/Users/me"),
+            ("lowercase", "/users/me"),
+        ]
+    );
+    assert_eq!(trace.query.as_deref(), Some("x=1"));
+
+    assert!(trace.steps.contains(&TraceStep::Terminal {
+        depth: 2,
+        route: None,
+        available: vec![HttpMethod::Post],
+    }));
+    assert!(trace.steps.contains(&TraceStep::Pattern {
+        depth: 1,
+        segment: "me".to_string(),
+        pattern: ":id(\\d+)".to_string(),
+        outcome: PatternOutcome::ConstraintRejected {
+            param: "id".to_string(),
+            constraint: "\\d+".to_string(),
+            value: "me".to_string(),
+        },
+    }));
+    assert_eq!(
+        trace.steps.last(),
+        Some(&TraceStep::Wildcard {
+            depth: 1,
+            route: Some(2),
+        })
+    );
+    match &trace.decision {
+        Decision::Matched {
+            key,
+            params,
+            source,
+        } => {
+            assert_eq!(*key, 2);
+            assert_eq!(params.get("*").map(String::as_str), Some("me"));
+            assert_eq!(*source, MatchSource::Tree);
+        }
+        other => panic!("unexpected decision: {other:?}"),
+    }
+}
+
+#[test]
+fn router_when_explaining_then_cache_is_reported_but_untouched() {
+    let router = sample_router();
+    let readonly = router
+        .get_readonly()
+        .expect("readonly snapshot should be available");
+
+    let cold = readonly.explain(HttpMethod::Get, "/users/42");
+    assert_eq!(cold.steps[0], TraceStep::Cache { hit: None });
+    assert_eq!(cold.key(), Some(0));
+    assert_eq!(readonly.cache_metrics(), Some((0, 0)));
+
+    router
+        .find(HttpMethod::Get, "/users/42")
+        .expect("route should match");
+    let warm = readonly.explain(HttpMethod::Get, "/users/42");
+    assert_eq!(warm.steps, [TraceStep::Cache { hit: Some(0) }]);
+    match warm.decision {
+        Decision::Matched { source, .. } => assert_eq!(source, MatchSource::Cache),
+        other => panic!("unexpected decision: {other:?}"),
+    }
+    assert_eq!(readonly.cache_metrics(), Some((0, 1)));
+}
+
+#[test]
+fn router_when_path_is_invalid_or_router_mutable_then_explain_reports_it() {
+    let router = sample_router();
+    let trace = router
+        .explain(HttpMethod::Get, "")
+        .expect("sealed router should explain");
+    assert!(matches!(trace.decision, Decision::InvalidPath { .. }));
+    assert!(trace.steps.is_empty());
+
+    let missing = router
+        .explain(HttpMethod::Delete, "/users/42")
+        .expect("sealed router should explain");
+    assert_eq!(missing.decision, Decision::NotFound);
+    assert!(missing.to_string().ends_with("=> not found"));
+
+    let mutable = Router::new(None);
+    match mutable
+        .explain(HttpMethod::Get, "/")
+        .expect_err("unsealed router cannot explain")
+    {
+        RouterError::FindWhileMutable => {}
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
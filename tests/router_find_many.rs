@@ -0,0 +1,145 @@
+use bunner_router_rs::{
+    HttpMethod, RouteOptions, Router, RouterError, query::QueryCondition, readonly::ReadOnlyError,
+};
+
+fn sample_router() -> (Router, Vec<u16>) {
+    let router = Router::new(None);
+    let list = router
+        .add(HttpMethod::Get, "/users")
+        .expect("route should register");
+    let show = router
+        .add(HttpMethod::Get, "/users/:id")
+        .expect("route should register");
+    let create = router
+        .add(HttpMethod::Post, "/users")
+        .expect("route should register");
+    let export = router
+        .add_with_options(
+            "/users",
+            RouteOptions::builder()
+                .query(vec![QueryCondition::equals("format", "csv")])
+                .build()
+                .expect("options should build"),
+        )
+        .expect("variant should register")[0];
+    router.seal();
+    (router, vec![list, show, create, export])
+}
+
+#[test]
+fn router_when_batch_resolved_then_results_follow_input_order() {
+    let (router, keys) = sample_router();
+    let requests = [
+        (HttpMethod::Post, "/users"),
+        (HttpMethod::Get, "/users/7"),
+        (HttpMethod::Get, "/users?format=csv"),
+        (HttpMethod::Get, "/USERS"),
+    ];
+
+    let results = router.find_many(&requests).expect("router is sealed");
+    assert_eq!(results.len(), requests.len());
+    let matched: Vec<u16> = results
+        .iter()
+        .map(|result| result.as_ref().expect("route should match").0)
+        .collect();
+    assert_eq!(matched, [keys[2], keys[1], keys[3], keys[0]]);
+    let params = &results[1].as_ref().expect("route should match").1;
+    assert_eq!(params.get("id").map(String::as_str), Some("7"));
+
+    for ((method, path), expected) in requests.into_iter().zip(matched) {
+        let (key, _) = router.find(method, path).expect("route should match");
+        assert_eq!(key, expected);
+    }
+}
+
+#[test]
+fn router_when_batch_has_failures_then_errors_stay_in_place() {
+    let (router, keys) = sample_router();
+    let results = router
+        .find_many(&[
+            (HttpMethod::Delete, "/users"),
+            (HttpMethod::Get, ""),
+            (HttpMethod::Get, "/users"),
+        ])
+        .expect("router is sealed");
+
+    match &results[0] {
+        Err(RouterError::ReadOnly(ReadOnlyError::RouteNotFound { method, path })) => {
+            assert_eq!(*method, HttpMethod::Delete);
+            assert_eq!(path, "/users");
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+    assert!(results[1].is_err());
+    assert_eq!(results[2].as_ref().expect("route should match").0, keys[0]);
+
+    match Router::new(None)
+        .find_many(&[(HttpMethod::Get, "/")])
+        .expect_err("unsealed router cannot look up")
+    {
+        RouterError::FindWhileMutable => {}
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn router_when_batch_repeats_paths_then_cache_is_shared_with_find() {
+    let (router, _) = sample_router();
+    let readonly = router
+        .get_readonly()
+        .expect("readonly snapshot should be available");
+
+    router
+        .find(HttpMethod::Get, "/users/1")
+        .expect("route should match");
+    assert_eq!(readonly.cache_metrics(), Some((0, 1)));
+
+    let results =
+        readonly.find_many(&[(HttpMethod::Get, "/users/1"), (HttpMethod::Get, "/users/2")]);
+    assert!(results.iter().all(Result::is_ok));
+    assert_eq!(readonly.cache_metrics(), Some((1, 2)));
+
+    router
+        .find(HttpMethod::Get, "/users/2")
+        .expect("route should match");
+    assert_eq!(readonly.cache_metrics(), Some((2, 2)));
+}
+
+#[test]
+fn router_when_batch_contains_duplicates_then_each_key_is_resolved_once() {
+    let (router, keys) = sample_router();
+    let readonly = router
+        .get_readonly()
+        .expect("readonly snapshot should be available");
+
+    let results = readonly.find_many(&[
+        (HttpMethod::Get, "/users/3"),
+        (HttpMethod::Post, "/users"),
+        (HttpMethod::Get, "/users/3"),
+        (HttpMethod::Delete, "/users"),
+        (HttpMethod::Get, "/users/3"),
+        (HttpMethod::Delete, "/users"),
+    ]);
+
+    for idx in [0, 2, 4] {
+        let (key, params) = results[idx].as_ref().expect("route should match");
+        assert_eq!(*key, keys[1]);
+        assert_eq!(params.get("id").map(String::as_str), Some("3"));
+    }
+    assert_eq!(results[1].as_ref().expect("route should match").0, keys[2]);
+    for idx in [3, 5] {
+        match &results[idx] {
+            Err(ReadOnlyError::RouteNotFound { method, path }) => {
+                assert_eq!(*method, HttpMethod::Delete);
+                assert_eq!(path, "/users");
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+    assert_eq!(readonly.cache_metrics(), Some((0, 3)));
+
+    router
+        .find(HttpMethod::Get, "/users/3")
+        .expect("route should match");
+    assert_eq!(readonly.cache_metrics(), Some((1, 3)));
+}
@@ -0,0 +1,174 @@
+use bunner_router_rs::{
+    HttpMethod, RouteOptions, Router, RouterError, RouterOptions, RouterOptionsError, RouterResult,
+    readonly::ReadOnlyError,
+};
+use std::collections::HashMap;
+
+fn constraints(name: &str, regex: &str) -> HashMap<String, String> {
+    HashMap::from([(name.to_string(), regex.to_string())])
+}
+
+#[test]
+fn router_when_group_has_constraint_then_applies_it_to_all_routes() {
+    let router = Router::new(None);
+    let options = RouteOptions::builder()
+        .constraints(constraints("tenant", "[a-z]+"))
+        .build()
+        .expect("options should build");
+
+    let keys = router
+        .group("/admin/:tenant", options, |g| -> RouterResult<Vec<u16>> {
+            Ok(vec![
+                g.add(HttpMethod::Get, "/users")?,
+                g.add(HttpMethod::Post, "/users")?,
+            ])
+        })
+        .expect("group routes should register");
+    router.seal();
+
+    let (matched_key, params) = router
+        .find(HttpMethod::Post, "/admin/acme/users")
+        .expect("grouped route should match");
+    assert_eq!(matched_key, keys[1]);
+    assert_eq!(params.get("tenant").map(|s| s.as_str()), Some("acme"));
+
+    let err = router.find(HttpMethod::Get, "/admin/42/users");
+    match err.expect_err("constraint should reject digits") {
+        RouterError::ReadOnly(ReadOnlyError::RouteNotFound { .. }) => {}
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn router_when_groups_nested_then_prefixes_and_options_merge() {
+    let router = Router::new(None);
+    let outer = RouteOptions::builder()
+        .methods(vec![HttpMethod::Get, HttpMethod::Put])
+        .constraints(constraints("org", "[a-z]+"))
+        .priority(5)
+        .build()
+        .expect("options should build");
+    let inner = RouteOptions::builder()
+        .constraints(constraints("id", "\\d+"))
+        .build()
+        .expect("options should build");
+
+    let keys = router
+        .group("/orgs/:org", outer, |g| {
+            g.group("/items", inner, |g| {
+                g.add_with_options("/:id", RouteOptions::default())
+            })
+        })
+        .expect("nested group routes should register");
+    router.seal();
+
+    assert_eq!(keys.len(), 2);
+    let (matched_key, params) = router
+        .find(HttpMethod::Put, "/orgs/acme/items/9")
+        .expect("nested route should match");
+    assert_eq!(matched_key, keys[1]);
+    assert_eq!(params.get("id").map(|s| s.as_str()), Some("9"));
+    assert!(router.find(HttpMethod::Get, "/orgs/acme/items/x").is_err());
+}
+
+#[test]
+fn route_options_when_merged_then_inner_entries_win() {
+    let outer = RouteOptions::builder()
+        .methods(vec![HttpMethod::Post])
+        .meta(HashMap::from([
+            ("team".to_string(), "core".to_string()),
+            ("auth".to_string(), "none".to_string()),
+        ]))
+        .priority(3)
+        .alias("outer")
+        .build()
+        .expect("options should build");
+    let inner = RouteOptions::builder()
+        .meta(HashMap::from([("auth".to_string(), "admin".to_string())]))
+        .build()
+        .expect("options should build");
+
+    let merged = outer.merge(&inner);
+
+    assert_eq!(merged.methods, vec![HttpMethod::Post]);
+    assert_eq!(merged.priority, 3);
+    assert_eq!(merged.meta.get("team").map(|s| s.as_str()), Some("core"));
+    assert_eq!(merged.meta.get("auth").map(|s| s.as_str()), Some("admin"));
+    assert_eq!(merged.alias, None);
+}
+
+#[test]
+fn router_when_group_route_options_invalid_then_returns_error() {
+    let router = Router::new(None);
+    let err = router.group("/api", RouteOptions::default(), |g| {
+        g.add_with_options(
+            "/items",
+            RouteOptions {
+                priority: 500,
+                ..RouteOptions::default()
+            },
+        )
+    });
+
+    match err.expect_err("expected invalid options error") {
+        RouterError::Options(RouterOptionsError::RoutePriorityOutOfRange { value, .. }) => {
+            assert_eq!(value, 500);
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn router_when_inner_options_repeat_defaults_then_they_still_override_outer() {
+    let defaults = RouteOptions::builder()
+        .methods(vec![HttpMethod::Delete])
+        .build()
+        .expect("options should build");
+    let router = Router::new(Some(
+        RouterOptions::builder()
+            .route_defaults(defaults)
+            .build()
+            .expect("router options should build"),
+    ));
+    let outer = RouteOptions::builder()
+        .methods(vec![HttpMethod::Post])
+        .priority(5)
+        .optional(true)
+        .build()
+        .expect("options should build");
+    let explicit_get = RouteOptions::builder()
+        .methods(vec![HttpMethod::Get])
+        .priority(0)
+        .optional(false)
+        .overlay()
+        .expect("overlay should build");
+
+    let keys = router
+        .group("/api", outer, |g| -> RouterResult<Vec<u16>> {
+            Ok(vec![
+                g.add(HttpMethod::Get, "/items")?,
+                g.add_with_options("/users", explicit_get.clone())?[0],
+                g.add_with_options("/posts", RouteOptions::default())?[0],
+            ])
+        })
+        .expect("group routes should register");
+    router.seal();
+
+    for (path, key) in [("/api/items", keys[0]), ("/api/users", keys[1])] {
+        let (matched_key, _) = router
+            .find(HttpMethod::Get, path)
+            .expect("explicit GET should win");
+        assert_eq!(matched_key, key);
+        assert!(router.find(HttpMethod::Post, path).is_err());
+    }
+    let (matched_key, _) = router
+        .find(HttpMethod::Post, "/api/posts")
+        .expect("unset methods should inherit the group's");
+    assert_eq!(matched_key, keys[2]);
+
+    let routes = router.routes();
+    let users = routes.get(keys[1]).expect("route is registered");
+    assert_eq!((users.options.priority, users.options.optional), (0, false));
+    let posts = routes.get(keys[2]).expect("route is registered");
+    assert_eq!((posts.options.priority, posts.options.optional), (5, true));
+}
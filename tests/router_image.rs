@@ -0,0 +1,140 @@
+use bunner_router_rs::{
+    HttpMethod, RouteOptions, Router, RouterReadOnly,
+    query::QueryCondition,
+    readonly::{
+        ImageError,
+        image::{IMAGE_FORMAT_VERSION, IMAGE_MAGIC},
+    },
+};
+
+fn sealed_router() -> Router {
+    let router = Router::new(None);
+    router
+        .add(HttpMethod::Get, "/health")
+        .expect("route should register");
+    router
+        .add(HttpMethod::Get, "/users/:id(\\d+)")
+        .expect("route should register");
+    router
+        .add(HttpMethod::Post, "/files/*")
+        .expect("route should register");
+    router
+        .add_host(":tenant.example.com", HttpMethod::Get, "/home")
+        .expect("host route should register");
+    router
+        .add_versioned(2, HttpMethod::Get, "/orders")
+        .expect("versioned route should register");
+    router
+        .add_with_options(
+            "/api",
+            RouteOptions::builder()
+                .query(vec![QueryCondition::equals("action", "list")])
+                .build()
+                .expect("options should build"),
+        )
+        .expect("conditional route should register");
+    router
+        .use_middleware("/users", ["auth"])
+        .expect("middleware should attach");
+    router.seal();
+    router
+}
+
+#[test]
+fn readonly_when_image_round_trips_then_lookups_match() {
+    let router = sealed_router();
+    let original = router.get_readonly().expect("router should be sealed");
+    let loaded = RouterReadOnly::from_bytes(&original.to_bytes()).expect("image should load");
+
+    for (method, path) in [
+        (HttpMethod::Get, "/health"),
+        (HttpMethod::Get, "/users/42"),
+        (HttpMethod::Post, "/files/a/b.txt"),
+        (HttpMethod::Get, "/api?action=list"),
+    ] {
+        assert_eq!(
+            loaded
+                .find(method, path)
+                .expect("loaded lookup should match"),
+            original
+                .find(method, path)
+                .expect("original lookup should match"),
+            "{method:?} {path}"
+        );
+    }
+    assert!(loaded.find(HttpMethod::Get, "/users/abc").is_err());
+    assert!(loaded.find(HttpMethod::Get, "/api?action=drop").is_err());
+
+    assert_eq!(
+        loaded
+            .find_host(HttpMethod::Get, "acme.example.com", "/home")
+            .expect("host lookup should match"),
+        original
+            .find_host(HttpMethod::Get, "acme.example.com", "/home")
+            .expect("host lookup should match"),
+    );
+    assert_eq!(
+        loaded
+            .find_versioned(HttpMethod::Get, "/v3/orders", &())
+            .expect("versioned lookup should match"),
+        original
+            .find_versioned(HttpMethod::Get, "/v3/orders", &())
+            .expect("versioned lookup should match"),
+    );
+
+    let (found, _) = loaded
+        .find(HttpMethod::Get, "/users/7")
+        .expect("route should match");
+    assert_eq!(loaded.middleware(found), ["auth".to_string()]);
+}
+
+#[test]
+fn readonly_when_image_round_trips_then_route_table_survives() {
+    let router = sealed_router();
+    let original = router.get_readonly().expect("router should be sealed");
+    let loaded = RouterReadOnly::from_bytes(&original.to_bytes()).expect("image should load");
+
+    assert_eq!(loaded.routes().len(), original.routes().len());
+    for record in original.routes() {
+        assert_eq!(loaded.routes().get(record.key), Some(record));
+    }
+}
+
+#[test]
+fn readonly_when_image_format_version_differs_then_returns_error() {
+    let router = sealed_router();
+    let mut bytes = router.get_readonly().expect("sealed").to_bytes();
+    let offset = IMAGE_MAGIC.len();
+    bytes[offset..offset + 2].copy_from_slice(&(IMAGE_FORMAT_VERSION + 1).to_le_bytes());
+
+    match RouterReadOnly::from_bytes(&bytes).expect_err("version mismatch should fail") {
+        ImageError::UnsupportedVersion { found, expected } => {
+            assert_eq!(found, IMAGE_FORMAT_VERSION + 1);
+            assert_eq!(expected, IMAGE_FORMAT_VERSION);
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn readonly_when_image_is_corrupted_then_returns_error() {
+    let router = sealed_router();
+    let bytes = router.get_readonly().expect("sealed").to_bytes();
+
+    let mut flipped = bytes.clone();
+    let last = flipped.len() - 1;
+    flipped[last] ^= 0xff;
+    assert!(matches!(
+        RouterReadOnly::from_bytes(&flipped),
+        Err(ImageError::ChecksumMismatch { .. })
+    ));
+
+    assert!(matches!(
+        RouterReadOnly::from_bytes(b"not an image"),
+        Err(ImageError::BadMagic)
+    ));
+    assert!(matches!(
+        RouterReadOnly::from_bytes(&bytes[..IMAGE_MAGIC.len() + 4]),
+        Err(ImageError::Truncated { .. })
+    ));
+}
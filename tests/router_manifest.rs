@@ -0,0 +1,140 @@
+use bunner_router_rs::{
+    HttpMethod, ManifestError, RouteManifest, Router, RouterError, RouterOptionsError,
+    radix::RadixError,
+};
+
+fn parse(json: &str) -> RouteManifest {
+    serde_json::from_str(json).expect("manifest should parse")
+}
+
+#[test]
+fn router_when_built_from_manifest_then_routes_and_groups_match() {
+    let manifest = parse(
+        r#"{
+            "config": { "case_sensitive": true },
+            "routes": [
+                { "path": "/health" },
+                { "path": "/login", "methods": ["Get", "Post"] }
+            ],
+            "groups": [{
+                "prefix": "/api",
+                "middleware": ["auth"],
+                "constraints": { "id": "\\d+" },
+                "routes": [{ "path": "/users/:id", "alias": "show-user" }],
+                "groups": [{
+                    "prefix": "/admin",
+                    "middleware": ["audit"],
+                    "routes": [{ "path": "/stats", "methods": ["Post"] }]
+                }]
+            }]
+        }"#,
+    );
+
+    let router = Router::from_manifest(&manifest).expect("manifest should load");
+    router.seal();
+
+    router
+        .find(HttpMethod::Get, "/health")
+        .expect("top-level route should match");
+    router
+        .find(HttpMethod::Post, "/login")
+        .expect("multi-method route should match");
+    assert!(router.find(HttpMethod::Get, "/HEALTH").is_err());
+
+    let ((_, params), chain) = router
+        .find_with_middleware(HttpMethod::Get, "/api/users/7")
+        .expect("grouped route should match");
+    assert_eq!(params.get("id").map(|s| s.as_str()), Some("7"));
+    assert_eq!(chain.as_ref(), ["auth".to_string()]);
+    assert!(router.find(HttpMethod::Get, "/api/users/abc").is_err());
+
+    let (_, chain) = router
+        .find_with_middleware(HttpMethod::Post, "/api/admin/stats")
+        .expect("nested group route should match");
+    assert_eq!(chain.as_ref(), ["auth".to_string(), "audit".to_string()]);
+}
+
+#[test]
+fn router_when_manifest_route_is_invalid_then_error_names_its_location() {
+    let manifest = parse(
+        r#"{
+            "groups": [{
+                "prefix": "/api",
+                "routes": [
+                    { "path": "/users" },
+                    { "path": "/users", "alias": "users-again" }
+                ]
+            }]
+        }"#,
+    );
+
+    match Router::from_manifest(&manifest).expect_err("duplicate route should fail") {
+        ManifestError::Route {
+            location,
+            path,
+            source,
+        } => {
+            assert_eq!(location, "groups[0].routes[1] \"users-again\"");
+            assert_eq!(path, "/api/users");
+            assert!(matches!(
+                *source,
+                RouterError::Radix(RadixError::DuplicateRoute { .. })
+            ));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    let manifest = parse(r#"{ "routes": [{ "path": "/a" }, { "path": "/b", "priority": 500 }] }"#);
+    match Router::from_manifest(&manifest).expect_err("priority should be rejected") {
+        ManifestError::Route {
+            location, source, ..
+        } => {
+            assert_eq!(location, "routes[1]");
+            assert!(matches!(
+                *source,
+                RouterError::Options(RouterOptionsError::RoutePriorityOutOfRange { .. })
+            ));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn router_when_manifest_config_is_invalid_then_returns_config_error() {
+    let manifest = parse(r#"{ "config": { "max_param_depth": 0 } }"#);
+
+    match Router::from_manifest(&manifest).expect_err("config should be rejected") {
+        ManifestError::Config(RouterOptionsError::MaxParamDepthInvalid { provided }) => {
+            assert_eq!(provided, 0);
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn manifest_when_keys_are_misspelt_then_parsing_fails() {
+    for (json, key) in [
+        (
+            r#"{ "routes": [{ "path": "/a", "method": ["Post"] }] }"#,
+            "method",
+        ),
+        (
+            r#"{ "groups": [{ "prefix": "/api", "middlware": ["auth"], "routes": [] }] }"#,
+            "middlware",
+        ),
+        (
+            r#"{ "config": { "case_sensitve": true } }"#,
+            "case_sensitve",
+        ),
+        (r#"{ "route": [] }"#, "route"),
+    ] {
+        let err = serde_json::from_str::<RouteManifest>(json).expect_err("typo should be rejected");
+        assert!(
+            err.to_string().contains(&format!("unknown field `{key}`")),
+            "unexpected error for {key}: {err}"
+        );
+    }
+
+    let manifest = parse(r#"{ "routes": [{ "path": "/a", "methods": ["Post"], "priority": 1 }] }"#);
+    assert_eq!(manifest.routes[0].options.methods, vec![HttpMethod::Post]);
+}
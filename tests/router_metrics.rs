@@ -0,0 +1,170 @@
+use bunner_router_rs::metrics::{MetricsEncoder, MetricsError};
+use bunner_router_rs::{CacheOptions, HttpMethod, Router, RouterOptions};
+
+#[test]
+fn router_when_metrics_encoded_then_reports_cache_and_lookup_outcomes() {
+    let options = RouterOptions::builder()
+        .track_lookups(true)
+        .build()
+        .expect("options should build");
+    let router = Router::new(Some(options));
+    router
+        .add(HttpMethod::Get, "/users/:id")
+        .expect("route should register");
+    router
+        .add(HttpMethod::Post, "/users")
+        .expect("route should register");
+    router.seal();
+
+    for _ in 0..2 {
+        router
+            .find(HttpMethod::Get, "/users/1")
+            .expect("lookup should succeed");
+    }
+    let _ = router.find(HttpMethod::Get, "/users");
+    let _ = router.find(HttpMethod::Get, "/missing");
+    let _ = router.find(HttpMethod::Get, "/a b");
+
+    let encoder = MetricsEncoder::builder()
+        .prefix("edge_router")
+        .label("service", "api")
+        .build()
+        .expect("encoder should build");
+    let text = router.encode_metrics(&encoder).expect("router is sealed");
+    let lines: Vec<&str> = text.lines().collect();
+
+    for expected in [
+        "# TYPE edge_router_routes gauge",
+        "edge_router_routes{service=\"api\"} 2",
+        "# TYPE edge_router_cache_hits counter",
+        "edge_router_cache_hits_total{service=\"api\"} 1",
+        "edge_router_cache_misses_total{service=\"api\"} 3",
+        "edge_router_cache_evictions_total{service=\"api\"} 0",
+        "edge_router_cache_entries{service=\"api\"} 1",
+        "edge_router_lookups_total{service=\"api\",result=\"match\"} 2",
+        "edge_router_lookups_total{service=\"api\",result=\"not_found\"} 1",
+        "edge_router_lookups_total{service=\"api\",result=\"method_not_allowed\"} 1",
+        "edge_router_lookups_total{service=\"api\",result=\"invalid_path\",reason=\"control_or_whitespace\"} 1",
+        "edge_router_lookups_total{service=\"api\",result=\"invalid_path\",reason=\"empty\"} 0",
+    ] {
+        assert!(
+            lines.contains(&expected),
+            "missing '{expected}' in:\n{text}"
+        );
+    }
+    assert_eq!(lines.last(), Some(&"# EOF"));
+}
+
+#[test]
+fn router_when_tracking_is_off_then_only_emits_structural_gauges() {
+    let options = RouterOptions::builder()
+        .cache(CacheOptions::disabled())
+        .build()
+        .expect("options should build");
+    let router = Router::new(Some(options));
+    router
+        .add(HttpMethod::Get, "/health")
+        .expect("route should register");
+    router.seal();
+
+    let readonly = router
+        .get_readonly()
+        .expect("readonly snapshot should be available");
+    let encoder = MetricsEncoder::builder()
+        .label("zone", "eu \"west\"\n1")
+        .build()
+        .expect("encoder should build");
+    let text = encoder.encode(&readonly);
+
+    let samples: Vec<&str> = text.lines().filter(|line| !line.starts_with('#')).collect();
+    assert_eq!(
+        samples,
+        [
+            "bunner_router_routes{zone=\"eu \\\"west\\\"\\n1\"} 1".to_string(),
+            format!(
+                "bunner_router_snapshot_generation{{zone=\"eu \\\"west\\\"\\n1\"}} {}",
+                readonly.generation()
+            ),
+        ]
+    );
+    assert!(text.ends_with("# EOF\n"));
+}
+
+#[test]
+fn router_when_encoder_settings_are_invalid_then_rejects_them() {
+    match MetricsEncoder::builder()
+        .prefix("1router")
+        .build()
+        .expect_err("prefix must be a metric name")
+    {
+        MetricsError::InvalidPrefix { prefix } => assert_eq!(prefix, "1router"),
+        other => panic!("unexpected error: {other:?}"),
+    }
+    match MetricsEncoder::builder()
+        .label("result", "x")
+        .build()
+        .expect_err("reserved label should be rejected")
+    {
+        MetricsError::ReservedLabel { name } => assert_eq!(name, "result"),
+        other => panic!("unexpected error: {other:?}"),
+    }
+    match MetricsEncoder::builder()
+        .label("env", "prod")
+        .label("env", "dev")
+        .build()
+        .expect_err("duplicate label should be rejected")
+    {
+        MetricsError::DuplicateLabel { name } => assert_eq!(name, "env"),
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn router_when_misses_hit_the_negative_cache_then_405_and_404_stay_apart() {
+    let options = RouterOptions::builder()
+        .track_lookups(true)
+        .cache(CacheOptions {
+            negative: true,
+            ..CacheOptions::default()
+        })
+        .build()
+        .expect("options should build");
+    let router = Router::new(Some(options));
+    router
+        .add(HttpMethod::Get, "/users/:id")
+        .expect("route should register");
+    router
+        .add(HttpMethod::Post, "/users")
+        .expect("route should register");
+    router
+        .add(HttpMethod::Put, "/files/*")
+        .expect("route should register");
+    router.seal();
+
+    let readonly = router
+        .get_readonly()
+        .expect("readonly snapshot should be available");
+    for _ in 0..2 {
+        for (method, path) in [
+            (HttpMethod::Get, "/users"),
+            (HttpMethod::Delete, "/users/1"),
+            (HttpMethod::Get, "/files/a/b"),
+            (HttpMethod::Get, "/missing"),
+        ] {
+            readonly.find(method, path).expect_err("lookup should miss");
+        }
+    }
+    let batch = readonly.find_many(&[
+        (HttpMethod::Get, "/users"),
+        (HttpMethod::Get, "/missing"),
+        (HttpMethod::Get, "/users"),
+    ]);
+    assert!(batch.iter().all(Result::is_err));
+
+    let counters = readonly.lookup_counters().expect("lookups are tracked");
+    assert_eq!(counters.method_not_allowed, 8);
+    assert_eq!(counters.not_found, 3);
+    assert_eq!(counters.matched, 0);
+    let cache = readonly.cache_counters().expect("cache is enabled");
+    assert_eq!(cache.negative_hits, 6);
+}
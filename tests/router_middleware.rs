@@ -0,0 +1,96 @@
+use bunner_router_rs::{HttpMethod, RouteOptions, Router, RouterError, RouterResult};
+
+#[test]
+fn router_when_prefix_scopes_attached_then_chain_is_composed_outer_first() {
+    let router = Router::new(None);
+    router
+        .use_middleware("/admin", ["auth", "audit"])
+        .expect("middleware should attach");
+    router
+        .use_middleware("/", ["log"])
+        .expect("middleware should attach");
+    router
+        .use_middleware("/admin/users", ["audit", "rate-limit"])
+        .expect("middleware should attach");
+    let users = router
+        .add(HttpMethod::Get, "/admin/users/:id")
+        .expect("route should register");
+    let health = router
+        .add(HttpMethod::Get, "/health")
+        .expect("route should register");
+    router.seal();
+
+    let ((matched_key, params), chain) = router
+        .find_with_middleware(HttpMethod::Get, "/admin/users/7")
+        .expect("route should match");
+    assert_eq!(matched_key, users);
+    assert_eq!(params.get("id").map(|s| s.as_str()), Some("7"));
+    assert_eq!(chain.as_ref(), ["log", "auth", "audit", "rate-limit"]);
+
+    let readonly = router.get_readonly().expect("snapshot should exist");
+    assert_eq!(readonly.middleware(health), ["log"]);
+}
+
+#[test]
+fn router_when_group_middleware_used_then_appended_after_scopes() {
+    let router = Router::new(None);
+    router
+        .use_middleware("/api", ["cors"])
+        .expect("middleware should attach");
+    let options = RouteOptions::builder()
+        .middleware(["auth"])
+        .build()
+        .expect("options should build");
+
+    let (open, guarded) = router
+        .group(
+            "/api",
+            RouteOptions::default(),
+            |g| -> RouterResult<(u16, u16)> {
+                let open = g.add(HttpMethod::Get, "/status")?;
+                let guarded = g.group("/orgs/:org", options, |g| {
+                    g.use_middleware(["tenant", "auth"]);
+                    g.add(HttpMethod::Get, "/billing")
+                })?;
+                Ok((open, guarded))
+            },
+        )
+        .expect("group routes should register");
+    router.seal();
+
+    let readonly = router.get_readonly().expect("snapshot should exist");
+    assert_eq!(readonly.middleware(open), ["cors"]);
+    assert_eq!(readonly.middleware(guarded), ["cors", "auth", "tenant"]);
+}
+
+#[test]
+fn router_when_sub_router_mounted_then_its_scopes_follow_the_prefix() {
+    let sub = Router::new(None);
+    sub.use_middleware("/", ["sub-auth"])
+        .expect("middleware should attach");
+    sub.add(HttpMethod::Get, "/items")
+        .expect("route should register");
+
+    let app = Router::new(None);
+    let other = app
+        .add(HttpMethod::Get, "/items")
+        .expect("route should register");
+    let remap = app.mount("/shop", &sub).expect("mount should succeed");
+    app.seal();
+
+    let readonly = app.get_readonly().expect("snapshot should exist");
+    assert_eq!(readonly.middleware(remap[&0]), ["sub-auth"]);
+    assert!(readonly.middleware(other).is_empty());
+}
+
+#[test]
+fn router_when_middleware_attached_after_seal_then_returns_error() {
+    let router = Router::new(None);
+    router.seal();
+
+    let err = router.use_middleware("/late", ["auth"]);
+    match err.expect_err("expected sealed error") {
+        RouterError::MiddlewareWhileSealed { prefix } => assert_eq!(prefix, "/late"),
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
@@ -0,0 +1,212 @@
+use bunner_router_rs::{
+    HttpMethod, Router, RouterError, RouterOptions, host::HostError, radix::RadixError,
+};
+
+#[test]
+fn router_when_sub_router_mounted_then_routes_match_under_prefix() {
+    let users = Router::new(None);
+    let list_key = users
+        .add(HttpMethod::Get, "/users")
+        .expect("route should register");
+    let show_key = users
+        .add(HttpMethod::Get, "/users/:id")
+        .expect("route should register");
+
+    let app = Router::new(None);
+    let root_key = app
+        .add(HttpMethod::Get, "/health")
+        .expect("route should register");
+    let remap = app
+        .mount("/orgs/:org", &users)
+        .expect("mount should succeed");
+    app.seal();
+
+    assert_eq!(remap.len(), 2);
+    assert!(!remap.values().any(|key| *key == root_key));
+
+    let (matched_key, params) = app
+        .find(HttpMethod::Get, "/orgs/acme/users/42")
+        .expect("mounted route should match");
+    assert_eq!(Some(&matched_key), remap.get(&show_key));
+    assert_eq!(params.get("org").map(|s| s.as_str()), Some("acme"));
+    assert_eq!(params.get("id").map(|s| s.as_str()), Some("42"));
+
+    let (matched_key, _) = app
+        .find(HttpMethod::Get, "/orgs/acme/users")
+        .expect("mounted route should match");
+    assert_eq!(Some(&matched_key), remap.get(&list_key));
+}
+
+#[test]
+fn router_when_sealed_sub_router_mounted_then_routes_are_copied() {
+    let sub = Router::new(None);
+    sub.add(HttpMethod::Post, "/")
+        .expect("route should register");
+    sub.seal();
+
+    let app = Router::new(None);
+    let remap = app.mount("/hooks", &sub).expect("mount should succeed");
+    app.seal();
+
+    let (matched_key, _) = app
+        .find(HttpMethod::Post, "/hooks")
+        .expect("mounted root route should match");
+    assert_eq!(Some(&matched_key), remap.get(&0));
+}
+
+#[test]
+fn router_when_mounted_route_conflicts_then_returns_error_and_keeps_routes() {
+    let sub = Router::new(None);
+    sub.add(HttpMethod::Get, "/fresh")
+        .expect("route should register");
+    sub.add(HttpMethod::Get, "/taken")
+        .expect("route should register");
+
+    let app = Router::new(None);
+    app.add(HttpMethod::Get, "/api/taken")
+        .expect("route should register");
+
+    let err = app.mount("/api", &sub);
+    match err.expect_err("expected mount conflict") {
+        RouterError::MountConflict {
+            prefix,
+            path,
+            source,
+        } => {
+            assert_eq!(prefix, "/api");
+            assert_eq!(path, "/api/taken");
+            assert!(matches!(
+                *source,
+                RouterError::Radix(RadixError::DuplicateRoute { .. })
+            ));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    app.seal();
+    assert!(app.find(HttpMethod::Get, "/api/fresh").is_err());
+}
+
+#[test]
+fn router_when_prefix_param_repeats_sub_route_param_then_returns_error() {
+    let sub = Router::new(None);
+    sub.add(HttpMethod::Get, "/items/:org")
+        .expect("route should register");
+
+    let app = Router::new(None);
+    let err = app.mount("/orgs/:org", &sub);
+    match err.expect_err("expected duplicate param conflict") {
+        RouterError::MountConflict { source, .. } => {
+            assert!(matches!(
+                *source,
+                RouterError::Radix(RadixError::DuplicateParamName { .. })
+            ));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn router_when_mounted_routes_clash_with_live_tree_then_nothing_is_added() {
+    let app = Router::new(None);
+    app.add(HttpMethod::Get, "/api/:id")
+        .expect("route should register");
+    app.add(HttpMethod::Get, "/api/files/*")
+        .expect("route should register");
+    app.add_host(":tenant.example.com", HttpMethod::Get, "/home")
+        .expect("host route should register");
+
+    let params = Router::new(None);
+    params
+        .add(HttpMethod::Get, "/ok")
+        .expect("route should register");
+    params
+        .add(HttpMethod::Get, "/:name/edit")
+        .expect("route should register");
+    match app.mount("/api", &params).expect_err("param names differ") {
+        RouterError::MountConflict { path, source, .. } => {
+            assert_eq!(path, "/api/:name/edit");
+            assert!(matches!(
+                *source,
+                RouterError::Radix(RadixError::ParamNameConflict { .. })
+            ));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    let files = Router::new(None);
+    files
+        .add(HttpMethod::Get, "/files/*")
+        .expect("route should register");
+    match app.mount("/api", &files).expect_err("wildcard is taken") {
+        RouterError::MountConflict { source, .. } => assert!(matches!(
+            *source,
+            RouterError::Radix(RadixError::DuplicateWildcardRoute { .. })
+        )),
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    let hosts = Router::new(None);
+    hosts
+        .add_host(":org.example.com", HttpMethod::Get, "/home")
+        .expect("host route should register");
+    match app.mount("/", &hosts).expect_err("host param names differ") {
+        RouterError::MountConflict { source, .. } => assert!(matches!(
+            *source,
+            RouterError::Host(HostError::Radix(RadixError::ParamNameConflict { .. }))
+        )),
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    app.seal();
+    assert_eq!(app.routes().len(), 3);
+}
+
+#[test]
+fn router_when_mounted_routes_clash_with_each_other_then_nothing_is_added() {
+    let options = RouterOptions::builder()
+        .case_sensitive(true)
+        .build()
+        .expect("options should build");
+    let sub = Router::new(Some(options));
+    for path in ["/a", "/Users", "/users"] {
+        sub.add(HttpMethod::Get, path)
+            .expect("route should register");
+    }
+
+    let app = Router::new(None);
+    match app.mount("/api", &sub).expect_err("paths fold together") {
+        RouterError::MountConflict { path, source, .. } => {
+            assert_eq!(path, "/api/users");
+            assert!(matches!(
+                *source,
+                RouterError::Radix(RadixError::DuplicateRoute { .. })
+            ));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert!(app.routes().is_empty());
+}
+
+#[test]
+fn router_when_sub_router_has_middleware_then_it_only_covers_mounted_routes() {
+    let sub = Router::new(None);
+    sub.use_middleware("/", ["sub-auth"])
+        .expect("middleware should attach");
+    let mounted = sub
+        .add(HttpMethod::Get, "/items")
+        .expect("route should register");
+
+    let app = Router::new(None);
+    app.use_middleware("/api", ["log"])
+        .expect("middleware should attach");
+    let own = app
+        .add(HttpMethod::Get, "/api/own")
+        .expect("route should register");
+    let remap = app.mount("/api", &sub).expect("mount should succeed");
+    app.seal();
+
+    let readonly = app.get_readonly().expect("snapshot should exist");
+    assert_eq!(readonly.middleware(own), ["log"]);
+    assert_eq!(readonly.middleware(remap[&mounted]), ["log", "sub-auth"]);
+}
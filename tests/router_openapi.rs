@@ -0,0 +1,107 @@
+use bunner_router_rs::{
+    HttpMethod, RouteOptions, Router,
+    openapi::{OpenApiInfo, WILDCARD_PARAM_NAME},
+};
+use serde_json::json;
+use std::collections::HashMap;
+
+fn meta(entries: &[(&str, &str)]) -> HashMap<String, String> {
+    entries
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn router_when_exported_to_openapi_then_paths_use_templates_and_patterns() {
+    let router = Router::new(None);
+    router
+        .add_with_options(
+            "/users/:id(\\d+)/posts/:slug",
+            RouteOptions::builder()
+                .methods(vec![HttpMethod::Get, HttpMethod::Delete])
+                .constraints(HashMap::from([("slug".to_string(), "[a-z-]+".to_string())]))
+                .build()
+                .expect("options should build"),
+        )
+        .expect("route should register");
+    router
+        .add(HttpMethod::Get, "/files/*")
+        .expect("route should register");
+
+    let doc = router.to_openapi(OpenApiInfo::new("api", "1.0.0"));
+    assert_eq!(doc.openapi, "3.0.3");
+
+    let item = doc
+        .paths
+        .get("/users/{id}/posts/{slug}")
+        .expect("templated path should be exported");
+    assert_eq!(
+        item.operations()
+            .map(|(method, _)| method)
+            .collect::<Vec<_>>(),
+        [HttpMethod::Get, HttpMethod::Delete]
+    );
+    let params = &item
+        .operation(HttpMethod::Get)
+        .expect("get should be exported")
+        .parameters;
+    assert_eq!(params[0].name, "id");
+    assert_eq!(params[0].schema.pattern.as_deref(), Some("^(?:\\d+)$"));
+    assert_eq!(params[1].name, "slug");
+    assert_eq!(params[1].schema.pattern.as_deref(), Some("^(?:[a-z-]+)$"));
+
+    let wildcard = format!("/files/{{{WILDCARD_PARAM_NAME}}}");
+    assert!(doc.paths.contains_key(&wildcard));
+}
+
+#[test]
+fn router_when_route_has_meta_then_operation_carries_tags_summary_and_id() {
+    let router = Router::new(None);
+    router
+        .add_with_options(
+            "/orders/:id",
+            RouteOptions::builder()
+                .meta(meta(&[
+                    ("operationId", "getOrder"),
+                    ("summary", "Fetch one order"),
+                    ("tags", "orders, billing"),
+                ]))
+                .build()
+                .expect("options should build"),
+        )
+        .expect("route should register");
+    router.seal();
+
+    let doc = router.to_openapi(OpenApiInfo::new("api", "1.0.0"));
+    let value = serde_json::to_value(&doc).expect("document should serialize");
+    assert_eq!(
+        value["paths"]["/orders/{id}"]["get"],
+        json!({
+            "operationId": "getOrder",
+            "summary": "Fetch one order",
+            "tags": ["orders", "billing"],
+            "parameters": [{
+                "name": "id",
+                "in": "path",
+                "required": true,
+                "schema": { "type": "string" }
+            }]
+        })
+    );
+}
+
+#[test]
+fn router_when_routes_are_versioned_or_host_scoped_then_export_follows_rules() {
+    let router = Router::new(None);
+    router
+        .add_versioned(2, HttpMethod::Get, "/orders")
+        .expect("versioned route should register");
+    router
+        .add_host("admin.example.com", HttpMethod::Get, "/panel")
+        .expect("host route should register");
+
+    let doc = router.to_openapi(OpenApiInfo::new("api", "1.0.0"));
+    assert!(doc.paths.contains_key("/v2/orders"));
+    assert!(!doc.paths.contains_key("/panel"));
+}
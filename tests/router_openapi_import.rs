@@ -0,0 +1,171 @@
+use bunner_router_rs::{
+    HttpMethod, Router, RouterError, openapi::OpenApiInfo, pattern::PatternError, radix::RadixError,
+};
+
+#[test]
+fn router_when_openapi_imported_then_operations_become_routes() {
+    let doc = r#"{
+            "openapi": "3.0.3",
+            "info": { "title": "shop", "version": "1" },
+            "paths": {
+                "/orders/{order_id}": {
+                    "summary": "path item fields are ignored",
+                    "parameters": [
+                        { "name": "order_id", "in": "path", "required": true,
+                          "schema": { "type": "string", "format": "uuid" } }
+                    ],
+                    "get": {
+                        "operationId": "getOrder",
+                        "tags": ["orders"],
+                        "responses": { "200": { "description": "ok" } }
+                    },
+                    "delete": { "operationId": "deleteOrder" }
+                },
+                "/items/{n}": {
+                    "get": {
+                        "operationId": "getItem",
+                        "parameters": [
+                            { "name": "n", "in": "path", "schema": { "type": "integer" } },
+                            { "name": "expand", "in": "query", "schema": { "type": "string" } }
+                        ]
+                    }
+                },
+                "/codes/{code}": {
+                    "get": {
+                        "parameters": [
+                            { "name": "code", "in": "path", "schema": { "pattern": "^[A-Z]{3}$" } }
+                        ]
+                    }
+                }
+            }
+        }"#;
+
+    let router = Router::new(None);
+    let keys = router.add_from_openapi(doc).expect("import should succeed");
+    router.seal();
+
+    assert_eq!(keys.len(), 3);
+    let uuid = "/orders/0f8fad5b-d9cb-469f-a165-70867728950e";
+    let (key, params) = router
+        .find(HttpMethod::Get, uuid)
+        .expect("uuid param should match");
+    assert_eq!(Some(&key), keys.get("getOrder"));
+    assert_eq!(
+        params.get("order_id").map(|s| s.as_str()),
+        Some("0f8fad5b-d9cb-469f-a165-70867728950e")
+    );
+    let (key, _) = router
+        .find(HttpMethod::Delete, uuid)
+        .expect("delete should match");
+    assert_eq!(Some(&key), keys.get("deleteOrder"));
+    assert!(router.find(HttpMethod::Get, "/orders/42").is_err());
+
+    let (key, _) = router
+        .find(HttpMethod::Get, "/items/-3")
+        .expect("integer param should match");
+    assert_eq!(Some(&key), keys.get("getItem"));
+    assert!(router.find(HttpMethod::Get, "/items/x").is_err());
+
+    router
+        .find(HttpMethod::Get, "/codes/abc")
+        .expect("lowercased path should still satisfy the pattern");
+
+    let exported = router.to_openapi(OpenApiInfo::new("shop", "1"));
+    let op = exported.paths["/orders/{order_id}"]
+        .operation(HttpMethod::Get)
+        .expect("imported operation should export");
+    assert_eq!(op.operation_id.as_deref(), Some("getOrder"));
+    assert_eq!(op.tags, ["orders".to_string()]);
+}
+
+#[test]
+fn router_when_openapi_operation_conflicts_then_error_names_it() {
+    let router = Router::new(None);
+    router
+        .add(HttpMethod::Get, "/health")
+        .expect("route should register");
+
+    let doc = r#"{
+            "openapi": "3.0.3",
+            "info": { "title": "svc", "version": "1" },
+            "paths": { "/health": { "get": { "operationId": "health" } } }
+        }"#;
+    match router
+        .add_from_openapi(doc)
+        .expect_err("duplicate should fail")
+    {
+        RouterError::OpenApiImport {
+            path,
+            method,
+            source,
+        } => {
+            assert_eq!(path, "/health");
+            assert_eq!(method, HttpMethod::Get);
+            assert!(matches!(
+                *source,
+                RouterError::Radix(RadixError::DuplicateRoute { .. })
+            ));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn router_when_openapi_import_fails_then_no_operation_is_registered() {
+    let router = Router::new(None);
+    let doc = r#"{
+        "openapi": "3.0.3",
+        "info": { "title": "svc", "version": "1" },
+        "paths": {
+            "/a/{id}": { "get": { "operationId": "first" } },
+            "/a/{name}/edit": { "get": { "operationId": "second" } }
+        }
+    }"#;
+    match router
+        .add_from_openapi(doc)
+        .expect_err("param names at one position must agree")
+    {
+        RouterError::OpenApiImport { path, source, .. } => {
+            assert_eq!(path, "/a/{name}/edit");
+            assert!(matches!(
+                *source,
+                RouterError::Radix(RadixError::ParamNameConflict { .. })
+            ));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert!(router.routes().is_empty());
+
+    let doc = r#"{
+        "openapi": "3.0.3",
+        "info": { "title": "svc", "version": "1" },
+        "paths": {
+            "/b": { "get": {} },
+            "/files/{id}.json": { "get": {} }
+        }
+    }"#;
+    match router
+        .add_from_openapi(doc)
+        .expect_err("mixed segments are not supported")
+    {
+        RouterError::OpenApiImport { path, source, .. } => {
+            assert_eq!(path, "/files/{id}.json");
+            assert!(matches!(
+                *source,
+                RouterError::Radix(RadixError::Pattern(
+                    PatternError::MixedParameterLiteralSyntax { .. }
+                ))
+            ));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert!(router.routes().is_empty());
+
+    match router
+        .add_from_openapi(r#"{ "openapi": "3.0.3" }"#)
+        .expect_err("info and paths are required")
+    {
+        RouterError::OpenApiParse(_) => {}
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
@@ -0,0 +1,86 @@
+use bunner_router_rs::{HttpMethod, RouteOptions, Router, readonly::RouteFilter};
+use std::collections::HashMap;
+
+fn admin_route(router: &Router) -> Vec<u16> {
+    let options = RouteOptions::builder()
+        .methods(vec![HttpMethod::Get, HttpMethod::Delete])
+        .constraints(HashMap::from([("id".to_string(), r"\d+".to_string())]))
+        .meta(HashMap::from([("team".to_string(), "ops".to_string())]))
+        .build()
+        .expect("options should build");
+    router
+        .add_with_options("/Admin/Users/:id", options)
+        .expect("route should register")
+}
+
+#[test]
+fn router_when_sealed_then_routes_lists_every_registration() {
+    let router = Router::new(None);
+    let health = router
+        .add(HttpMethod::Get, "/health")
+        .expect("route should register");
+    let admin = admin_route(&router);
+    router.seal();
+
+    let routes = router.routes();
+    assert_eq!(routes.len(), 3);
+    assert_eq!(
+        routes.iter().map(|route| route.key).collect::<Vec<_>>(),
+        [vec![health], admin.clone()].concat()
+    );
+
+    let route = routes.get(admin[1]).expect("key should resolve");
+    assert_eq!(route.method, HttpMethod::Delete);
+    assert_eq!(route.path, "/Admin/Users/:id");
+    assert_eq!(route.normalized, r"/admin/users/:id(\d+)");
+    assert_eq!(
+        route.options.meta.get("team").map(String::as_str),
+        Some("ops")
+    );
+    assert!(routes.get(999).is_none());
+
+    let snapshot = router
+        .get_readonly()
+        .expect("sealed router should expose its snapshot");
+    assert_eq!(snapshot.routes().len(), 3);
+}
+
+#[test]
+fn router_when_routes_filtered_then_only_matching_routes_remain() {
+    let router = Router::new(None);
+    router
+        .add(HttpMethod::Get, "/admin")
+        .expect("route should register");
+    router
+        .add(HttpMethod::Get, "/administrators")
+        .expect("route should register");
+    let admin = admin_route(&router);
+    router.seal();
+    let routes = router.routes();
+
+    let keys = |filter: RouteFilter| routes.filter(&filter).map(|r| r.key).collect::<Vec<_>>();
+    assert_eq!(
+        keys(RouteFilter::new().method(HttpMethod::Delete)),
+        [admin[1]]
+    );
+    assert_eq!(keys(RouteFilter::new().prefix("/Admin/")).len(), 2);
+    assert_eq!(keys(RouteFilter::new().meta("team", "ops")), admin);
+    assert!(keys(RouteFilter::new().meta("team", "web")).is_empty());
+    assert_eq!(
+        keys(RouteFilter::new().meta_key("team").method(HttpMethod::Get)),
+        [admin[0]]
+    );
+}
+
+#[test]
+fn router_when_not_sealed_then_routes_reflects_registry() {
+    let router = Router::new(None);
+    assert!(router.routes().is_empty());
+    let key = router
+        .add(HttpMethod::Post, "/orders")
+        .expect("route should register");
+
+    let routes = router.routes();
+    assert_eq!(routes.len(), 1);
+    assert_eq!(routes.get(key).map(|r| r.method), Some(HttpMethod::Post));
+}
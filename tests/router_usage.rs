@@ -0,0 +1,124 @@
+use bunner_router_rs::{HttpMethod, Router, RouterOptions, RouterReadOnly};
+use std::time::{Duration, SystemTime};
+
+fn tracked_router() -> (Router, [u16; 3]) {
+    let options = RouterOptions::builder()
+        .track_usage(true)
+        .build()
+        .expect("options should build");
+    let router = Router::new(Some(options));
+    let users = router
+        .add(HttpMethod::Get, "/users/:id")
+        .expect("route should register");
+    let health = router
+        .add(HttpMethod::Get, "/health")
+        .expect("route should register");
+    let legacy = router
+        .add(HttpMethod::Post, "/legacy/export")
+        .expect("route should register");
+    router.seal();
+    (router, [users, health, legacy])
+}
+
+#[test]
+fn router_when_routes_are_hit_then_counts_hits_and_cache_hits() {
+    let (router, [users, health, _]) = tracked_router();
+    for _ in 0..3 {
+        router
+            .find(HttpMethod::Get, "/users/7")
+            .expect("lookup should succeed");
+    }
+    router
+        .find(HttpMethod::Get, "/health")
+        .expect("lookup should succeed");
+    let _ = router.find(HttpMethod::Get, "/missing");
+
+    let report = router
+        .usage_report()
+        .expect("router is sealed")
+        .expect("usage tracking is on");
+    let usage = report.get(users).expect("route is tracked");
+    assert_eq!((usage.hits, usage.cache_hits), (3, 2));
+    assert_eq!(usage.path.as_deref(), Some("/users/:id"));
+    assert!(usage.last_hit.is_some());
+    assert_eq!(report.get(health).map(|usage| usage.hits), Some(1));
+}
+
+#[test]
+fn router_when_routes_are_never_hit_then_report_lists_them() {
+    let (router, [users, health, legacy]) = tracked_router();
+    router
+        .find(HttpMethod::Get, "/users/1")
+        .expect("lookup should succeed");
+
+    let report = router
+        .usage_report()
+        .expect("router is sealed")
+        .expect("usage tracking is on");
+    let never_hit: Vec<_> = report.never_hit().map(|usage| usage.key).collect();
+    assert_eq!(never_hit, [health, legacy]);
+    assert!(report.get(users).is_some_and(|usage| usage.hits == 1));
+    assert!(
+        report
+            .never_hit()
+            .all(|usage| usage.last_hit.is_none() && usage.method.is_some())
+    );
+
+    let restored = RouterReadOnly::from_bytes(
+        &router
+            .get_readonly()
+            .expect("readonly snapshot should be available")
+            .to_bytes(),
+    )
+    .expect("image should load");
+    let report = restored.usage_report().expect("usage tracking is on");
+    assert_eq!(report.never_hit().count(), 3);
+    let usage = report.get(legacy).expect("route is tracked");
+    assert_eq!(usage.method, Some(HttpMethod::Post));
+    assert_eq!(usage.path.as_deref(), Some("/legacy/export"));
+}
+
+#[test]
+fn router_when_usage_tracking_is_off_then_reports_nothing() {
+    let router = Router::new(None);
+    router
+        .add(HttpMethod::Get, "/health")
+        .expect("route should register");
+    router.seal();
+    router
+        .find(HttpMethod::Get, "/health")
+        .expect("lookup should succeed");
+
+    assert!(router.usage_report().expect("router is sealed").is_none());
+}
+
+#[test]
+fn router_when_threads_hit_routes_then_report_sums_all_of_them() {
+    let (router, [users, health, _]) = tracked_router();
+    let readonly = router
+        .get_readonly()
+        .expect("readonly snapshot should be available");
+    let before = SystemTime::now();
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..25 {
+                    readonly
+                        .find(HttpMethod::Get, "/users/7")
+                        .expect("lookup should succeed");
+                }
+            });
+        }
+    });
+
+    let report = router
+        .usage_report()
+        .expect("router is sealed")
+        .expect("usage tracking is on");
+    assert_eq!(report.routes().len(), 3);
+    let usage = report.get(users).expect("route is tracked");
+    assert_eq!(usage.hits, 100);
+    let last_hit = usage.last_hit.expect("route was hit");
+    assert!(last_hit + Duration::from_secs(1) >= before);
+    assert_eq!(report.get(health).map(|usage| usage.hits), Some(0));
+}
@@ -0,0 +1,96 @@
+use bunner_router_rs::readonly::ReadOnlyError;
+use bunner_router_rs::{HttpMethod, Router, RouterError};
+
+fn sealed_router() -> Router {
+    let router = Router::new(None);
+    router
+        .add(HttpMethod::Get, "/users/:id")
+        .expect("route should register");
+    router
+        .add(HttpMethod::Post, "/users")
+        .expect("route should register");
+    router.seal();
+    router
+}
+
+#[test]
+fn router_when_warmed_then_first_lookups_hit_the_cache() {
+    let router = sealed_router();
+    let report = router
+        .warm_cache([
+            (HttpMethod::Get, "/users/1"),
+            (HttpMethod::Post, "/users"),
+            (HttpMethod::Get, "/users/1"),
+        ])
+        .expect("router is sealed");
+    assert_eq!(report.warmed, 2);
+    assert_eq!(report.already_cached, 1);
+    assert!(report.is_clean());
+
+    let readonly = router
+        .get_readonly()
+        .expect("readonly snapshot should be available");
+    assert_eq!(readonly.cache_metrics(), Some((0, 0)));
+
+    router
+        .find(HttpMethod::Get, "/users/1")
+        .expect("lookup should succeed");
+    assert_eq!(readonly.cache_metrics(), Some((1, 0)));
+}
+
+#[test]
+fn router_when_warm_paths_do_not_match_then_reports_them() {
+    let router = sealed_router();
+    let report = router
+        .warm_cache(vec![
+            (HttpMethod::Get, "/gone".to_string()),
+            (HttpMethod::Delete, "/users/1".to_string()),
+            (HttpMethod::Get, "/users/2".to_string()),
+        ])
+        .expect("router is sealed");
+
+    assert_eq!(report.warmed, 1);
+    let failed: Vec<_> = report
+        .failed
+        .iter()
+        .map(|failure| (failure.method, failure.path.as_str()))
+        .collect();
+    assert_eq!(
+        failed,
+        [(HttpMethod::Get, "/gone"), (HttpMethod::Delete, "/users/1")]
+    );
+    match &report.failed[0].error {
+        ReadOnlyError::RouteNotFound { .. } => {}
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn router_when_hot_set_exported_then_warms_a_fresh_router() {
+    let router = sealed_router();
+    match router.hot_set().expect("router is sealed").as_slice() {
+        [] => {}
+        other => panic!("unexpected hot set: {other:?}"),
+    }
+    for path in ["/users/1", "/users/2", "/users/1", "/nope"] {
+        let _ = router.find(HttpMethod::Get, path);
+    }
+
+    let hot = router.hot_set().expect("router is sealed");
+    assert_eq!(hot.len(), 2);
+    assert_eq!(hot[0], (HttpMethod::Get, "/users/1".to_string()));
+
+    let next = sealed_router();
+    let report = next.warm_cache(hot).expect("router is sealed");
+    assert_eq!(report.warmed, 2);
+    assert!(report.is_clean());
+
+    let unsealed = Router::new(None);
+    match unsealed
+        .warm_cache([(HttpMethod::Get, "/")])
+        .expect_err("warming needs a sealed router")
+    {
+        RouterError::FindWhileMutable => {}
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
@@ -0,0 +1,97 @@
+use bunner_router_rs::{HttpMethod, Router, RouterError, RouterOptions, readonly::ReadOnlyError};
+
+fn case_sensitive_router() -> Router {
+    Router::new(Some(
+        RouterOptions::builder()
+            .case_sensitive(true)
+            .build()
+            .expect("options should build"),
+    ))
+}
+
+#[test]
+fn router_when_static_case_differs_then_returns_registered_spelling() {
+    let router = case_sensitive_router();
+    router
+        .add(HttpMethod::Get, "/users/profile")
+        .expect("route should register");
+    router
+        .add(HttpMethod::Get, "/users/settings")
+        .expect("route should register");
+    router.seal();
+
+    assert!(router.find(HttpMethod::Get, "/Users/Profile").is_err());
+
+    let corrected = router
+        .find_corrected(HttpMethod::Get, "/Users/PROFILE")
+        .expect("corrected lookup should succeed");
+    assert_eq!(corrected, "/users/profile");
+}
+
+#[test]
+fn router_when_param_route_corrected_then_keeps_param_value_as_sent() {
+    let router = case_sensitive_router();
+    router
+        .add(HttpMethod::Get, "/users/:id/Posts")
+        .expect("route should register");
+    router.seal();
+
+    let corrected = router
+        .find_corrected(HttpMethod::Get, "/USERS/AbC/posts")
+        .expect("corrected lookup should succeed");
+    assert_eq!(corrected, "/users/AbC/Posts");
+}
+
+#[test]
+fn router_when_wildcard_route_corrected_then_keeps_suffix_as_sent() {
+    let router = case_sensitive_router();
+    router
+        .add(HttpMethod::Get, "/Files/*")
+        .expect("route should register");
+    router.seal();
+
+    let corrected = router
+        .find_corrected(HttpMethod::Get, "/files/Media/Logo.PNG")
+        .expect("corrected lookup should succeed");
+    assert_eq!(corrected, "/Files/Media/Logo.PNG");
+}
+
+#[test]
+fn router_when_exact_spelling_registered_then_prefers_it() {
+    let router = case_sensitive_router();
+    router
+        .add(HttpMethod::Get, "/docs")
+        .expect("route should register");
+    router
+        .add(HttpMethod::Get, "/Docs")
+        .expect("route should register");
+    router.seal();
+
+    let corrected = router
+        .find_corrected(HttpMethod::Get, "/Docs")
+        .expect("corrected lookup should succeed");
+    assert_eq!(corrected, "/Docs");
+
+    let folded = router
+        .find_corrected(HttpMethod::Get, "/DOCS")
+        .expect("corrected lookup should succeed");
+    assert_eq!(folded, "/Docs");
+}
+
+#[test]
+fn router_when_no_route_matches_ignoring_case_then_returns_error() {
+    let router = case_sensitive_router();
+    router
+        .add(HttpMethod::Get, "/users")
+        .expect("route should register");
+    router.seal();
+
+    let err = router.find_corrected(HttpMethod::Post, "/USERS");
+    match err.expect_err("expected route not found") {
+        RouterError::ReadOnly(ReadOnlyError::RouteNotFound { method, path }) => {
+            assert_eq!(method, HttpMethod::Post);
+            assert_eq!(path, "/USERS");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
@@ -0,0 +1,155 @@
+use bunner_router_rs::{
+    HttpMethod, RouteOptions, Router, RouterError, RouterOptionsError, query::QueryCondition,
+    radix::RadixError, readonly::ReadOnlyError, request::HeaderCondition,
+};
+use std::collections::HashMap;
+
+fn with_headers(conditions: Vec<HeaderCondition>) -> RouteOptions {
+    RouteOptions::builder()
+        .headers(conditions)
+        .build()
+        .expect("options should build")
+}
+
+#[test]
+fn router_when_routes_differ_by_header_then_dispatches_on_header() {
+    let router = Router::new(None);
+    let json = router
+        .add_with_options(
+            "/reports/:id",
+            with_headers(vec![HeaderCondition::matches(
+                "Accept",
+                "application/json.*",
+            )]),
+        )
+        .expect("route should register")[0];
+    let csv = router
+        .add_with_options(
+            "/reports/:id",
+            with_headers(vec![HeaderCondition::equals("Accept", "text/csv")]),
+        )
+        .expect("variant should register")[0];
+    router.seal();
+
+    let (key, params) = router
+        .find_request(
+            HttpMethod::Get,
+            "/reports/7",
+            &[("accept", "application/json; charset=utf-8")],
+        )
+        .expect("json variant should match");
+    assert_eq!(key, json);
+    assert_eq!(params.get("id").map(|s| s.as_str()), Some("7"));
+
+    let (key, _) = router
+        .find_request(HttpMethod::Get, "/reports/7", &[("ACCEPT", "text/csv")])
+        .expect("csv variant should match");
+    assert_eq!(key, csv);
+
+    match router
+        .find_request(HttpMethod::Get, "/reports/7", &[("Accept", "text/html")])
+        .expect_err("no variant accepts the header")
+    {
+        RouterError::ReadOnly(ReadOnlyError::RouteNotFound { .. }) => {}
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn router_when_unconditional_route_shares_path_then_acts_as_fallback() {
+    let router = Router::new(None);
+    let fallback = router
+        .add(HttpMethod::Get, "/dashboard")
+        .expect("route should register");
+    let tenant = router
+        .add_with_options(
+            "/dashboard",
+            with_headers(vec![HeaderCondition::present("X-Tenant")]),
+        )
+        .expect("variant should register")[0];
+    router.seal();
+
+    let headers = HashMap::from([("X-Tenant".to_string(), "acme".to_string())]);
+    let (key, _) = router
+        .find_request(HttpMethod::Get, "/dashboard", &headers)
+        .expect("tenant variant should match");
+    assert_eq!(key, tenant);
+
+    let (key, _) = router
+        .find_request(HttpMethod::Get, "/dashboard", &HashMap::new())
+        .expect("fallback should match");
+    assert_eq!(key, fallback);
+
+    let (key, _) = router
+        .find(HttpMethod::Get, "/dashboard")
+        .expect("plain lookup should use the fallback");
+    assert_eq!(key, fallback);
+}
+
+#[test]
+fn router_when_header_and_query_conditions_combine_then_most_specific_wins() {
+    let router = Router::new(None);
+    let upload = router
+        .add_with_options(
+            "/files",
+            with_headers(vec![HeaderCondition::equals(
+                "Content-Type",
+                "application/octet-stream",
+            )]),
+        )
+        .expect("route should register")[0];
+    let chunked = router
+        .add_with_options(
+            "/files",
+            RouteOptions::builder()
+                .headers(vec![HeaderCondition::equals(
+                    "Content-Type",
+                    "application/octet-stream",
+                )])
+                .query(vec![QueryCondition::present("chunk")])
+                .build()
+                .expect("options should build"),
+        )
+        .expect("variant should register")[0];
+    router.seal();
+
+    let headers = vec![("Content-Type", "application/octet-stream")];
+    let (key, _) = router
+        .find_request(HttpMethod::Get, "/files?chunk=3", &headers)
+        .expect("chunked variant should match");
+    assert_eq!(key, chunked);
+    let (key, _) = router
+        .find_request(HttpMethod::Get, "/files", &headers)
+        .expect("upload variant should match");
+    assert_eq!(key, upload);
+}
+
+#[test]
+fn router_when_header_conditions_are_indistinguishable_or_invalid_then_returns_error() {
+    let router = Router::new(None);
+    router
+        .add_with_options(
+            "/api",
+            with_headers(vec![HeaderCondition::present("X-Tenant")]),
+        )
+        .expect("route should register");
+    match router
+        .add_with_options(
+            "/api",
+            with_headers(vec![HeaderCondition::present("x-tenant")]),
+        )
+        .expect_err("identical conditions should conflict")
+    {
+        RouterError::Radix(RadixError::DuplicateRoute { .. }) => {}
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    match RouteOptions::builder()
+        .headers(vec![HeaderCondition::matches("Accept", "[")])
+        .build()
+        .expect_err("invalid pattern should be rejected")
+    {
+        RouterOptionsError::HeaderConditionInvalid { name, .. } => assert_eq!(name, "accept"),
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
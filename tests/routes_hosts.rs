@@ -0,0 +1,139 @@
+use bunner_router_rs::{
+    HttpMethod, Router, RouterError, RouterReadOnly,
+    host::{HOST_WILDCARD_PARAM, HostError},
+};
+
+#[test]
+fn router_when_static_host_registered_then_matches_only_that_host() {
+    let router = Router::new(None);
+    let api_key = router
+        .add_host("api.example.com", HttpMethod::Get, "/status")
+        .expect("host route should register");
+    let default_key = router
+        .add(HttpMethod::Get, "/status")
+        .expect("default route should register");
+    router.seal();
+
+    let (matched_key, _) = router
+        .find_host(HttpMethod::Get, "API.example.com:8443", "/status")
+        .expect("host route should match");
+    assert_eq!(matched_key, api_key);
+
+    let (fallback_key, _) = router
+        .find_host(HttpMethod::Get, "www.example.com", "/status")
+        .expect("fallback route should match");
+    assert_eq!(fallback_key, default_key);
+    assert_ne!(api_key, default_key);
+}
+
+#[test]
+fn router_when_host_param_registered_then_merges_host_and_path_params() {
+    let router = Router::new(None);
+    let key = router
+        .add_host(":tenant.example.com", HttpMethod::Get, "/users/:id")
+        .expect("host route should register");
+    router.seal();
+
+    let (matched_key, params) = router
+        .find_host(HttpMethod::Get, "acme.example.com", "/users/7")
+        .expect("host route should match");
+
+    assert_eq!(matched_key, key);
+    assert_eq!(params.get("tenant").map(|s| s.as_str()), Some("acme"));
+    assert_eq!(params.get("id").map(|s| s.as_str()), Some("7"));
+}
+
+#[test]
+fn router_when_wildcard_host_registered_then_captures_leading_labels() {
+    let router = Router::new(None);
+    let wildcard_key = router
+        .add_host("*.example.com", HttpMethod::Get, "/")
+        .expect("wildcard host route should register");
+    let exact_key = router
+        .add_host("www.example.com", HttpMethod::Get, "/")
+        .expect("exact host route should register");
+    router.seal();
+
+    let (matched_key, params) = router
+        .find_host(HttpMethod::Get, "shop.eu.example.com", "/")
+        .expect("wildcard host should match");
+    assert_eq!(matched_key, wildcard_key);
+    assert_eq!(
+        params.get(HOST_WILDCARD_PARAM).map(|s| s.as_str()),
+        Some("shop.eu")
+    );
+
+    let (matched_key, _) = router
+        .find_host(HttpMethod::Get, "www.example.com", "/")
+        .expect("exact host should win over wildcard");
+    assert_eq!(matched_key, exact_key);
+}
+
+#[test]
+fn router_when_best_host_scope_misses_path_then_tries_less_specific_hosts() {
+    let router = Router::new(None);
+    let tenant_key = router
+        .add_host(":tenant.example.com", HttpMethod::Get, "/users/:id")
+        .expect("param host route should register");
+    let wildcard_key = router
+        .add_host("*.example.com", HttpMethod::Get, "/status")
+        .expect("wildcard host route should register");
+    let default_key = router
+        .add(HttpMethod::Get, "/health")
+        .expect("default route should register");
+    router.seal();
+
+    let readonly = router.get_readonly().expect("router is sealed");
+    let loaded = RouterReadOnly::from_bytes(&readonly.to_bytes()).expect("image should load");
+    for snapshot in [readonly.as_ref(), &loaded] {
+        let (matched_key, params) = snapshot
+            .find_host(HttpMethod::Get, "acme.example.com", "/users/7")
+            .expect("param host should match");
+        assert_eq!(matched_key, tenant_key);
+        assert_eq!(params.get("tenant").map(|s| s.as_str()), Some("acme"));
+
+        let (matched_key, params) = snapshot
+            .find_host(HttpMethod::Get, "acme.example.com", "/status")
+            .expect("wildcard host should match after the param host misses");
+        assert_eq!(matched_key, wildcard_key);
+        assert_eq!(params.get("tenant"), None);
+        assert_eq!(
+            params.get(HOST_WILDCARD_PARAM).map(|s| s.as_str()),
+            Some("acme")
+        );
+
+        let (matched_key, _) = snapshot
+            .find_host(HttpMethod::Get, "acme.example.com", "/health")
+            .expect("host-agnostic route should match last");
+        assert_eq!(matched_key, default_key);
+    }
+}
+
+#[test]
+fn router_when_host_and_path_declare_same_param_then_returns_error() {
+    let router = Router::new(None);
+    let err = router.add_host(":id.example.com", HttpMethod::Get, "/items/:id");
+
+    match err.expect_err("expected host param conflict") {
+        RouterError::Host(HostError::ParamConflict { param, .. }) => {
+            assert_eq!(param, "id");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn router_when_host_routes_registered_then_keys_do_not_collide() {
+    let router = Router::new(None);
+    let first = router
+        .add(HttpMethod::Get, "/a")
+        .expect("route should register");
+    let second = router
+        .add_host("a.example.com", HttpMethod::Get, "/a")
+        .expect("host route should register");
+    let third = router
+        .add_host("b.example.com", HttpMethod::Get, "/a")
+        .expect("host route should register");
+
+    assert_eq!(vec![first, second, third], vec![0, 1, 2]);
+}
@@ -0,0 +1,75 @@
+use bunner_router_rs::{HttpMethod, Router};
+
+#[test]
+fn router_when_target_has_query_and_fragment_then_matches_path_only() {
+    let router = Router::new(None);
+    let key = router
+        .add(HttpMethod::Get, "/search")
+        .expect("route should register");
+    router.seal();
+
+    let (matched_key, params) = router
+        .find(HttpMethod::Get, "/search?q=a%2Fb#top")
+        .expect("query should be ignored for matching");
+    assert_eq!(matched_key, key);
+    assert!(params.is_empty());
+}
+
+#[test]
+fn router_when_param_is_last_segment_then_query_is_not_captured() {
+    let router = Router::new(None);
+    router
+        .add(HttpMethod::Get, "/users/:id")
+        .expect("route should register");
+    router.seal();
+
+    let found = router
+        .find_target(HttpMethod::Get, "/users/42?expand=posts#bio")
+        .expect("target should match");
+
+    assert_eq!(found.route.1.get("id").map(|s| s.as_str()), Some("42"));
+    assert_eq!(found.query(), Some("expand=posts"));
+    assert_eq!(found.query_params().get("expand"), Some("posts"));
+}
+
+#[test]
+fn router_when_query_parsed_then_values_are_percent_decoded_multimap() {
+    let router = Router::new(None);
+    router
+        .add(HttpMethod::Get, "/search")
+        .expect("route should register");
+    router.seal();
+
+    let found = router
+        .find_target(HttpMethod::Get, "/search?q=a%2Fb+c&tag=x&tag=y")
+        .expect("target should match");
+    let query = found.query_params();
+
+    assert_eq!(query.get("q"), Some("a/b c"));
+    assert_eq!(query.get_all("tag").collect::<Vec<_>>(), vec!["x", "y"]);
+
+    let bare = router
+        .find_target(HttpMethod::Get, "/search")
+        .expect("target should match");
+    assert_eq!(bare.query(), None);
+    assert!(bare.query_params().is_empty());
+}
+
+#[test]
+fn router_when_queries_differ_then_cache_key_is_shared() {
+    let router = Router::new(None);
+    router
+        .add(HttpMethod::Get, "/items/:id")
+        .expect("route should register");
+    router.seal();
+    let readonly = router.get_readonly().expect("snapshot should exist");
+
+    router
+        .find(HttpMethod::Get, "/items/1?page=1")
+        .expect("first lookup should match");
+    router
+        .find(HttpMethod::Get, "/items/1?page=2")
+        .expect("second lookup should match");
+
+    assert_eq!(readonly.cache_metrics(), Some((1, 1)));
+}
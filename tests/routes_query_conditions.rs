@@ -0,0 +1,128 @@
+use bunner_router_rs::{
+    HttpMethod, RouteOptions, Router, RouterError, RouterOptionsError, query::QueryCondition,
+    radix::RadixError, readonly::ReadOnlyError,
+};
+
+fn with_query(conditions: Vec<QueryCondition>) -> RouteOptions {
+    RouteOptions::builder()
+        .query(conditions)
+        .build()
+        .expect("options should build")
+}
+
+#[test]
+fn router_when_routes_differ_by_query_value_then_dispatches_on_value() {
+    let router = Router::new(None);
+    let list = router
+        .add_with_options(
+            "/api",
+            with_query(vec![QueryCondition::equals("action", "list")]),
+        )
+        .expect("route should register")[0];
+    let delete = router
+        .add_with_options(
+            "/api",
+            with_query(vec![QueryCondition::equals("action", "delete")]),
+        )
+        .expect("variant should register")[0];
+    router.seal();
+
+    assert_ne!(list, delete);
+    let (key, _) = router
+        .find(HttpMethod::Get, "/api?action=list")
+        .expect("list variant should match");
+    assert_eq!(key, list);
+    let (key, _) = router
+        .find(HttpMethod::Get, "/api?page=2&action=delete")
+        .expect("delete variant should match");
+    assert_eq!(key, delete);
+
+    match router
+        .find(HttpMethod::Get, "/api?action=purge")
+        .expect_err("no variant accepts the query")
+    {
+        RouterError::ReadOnly(ReadOnlyError::RouteNotFound { .. }) => {}
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn router_when_several_conditions_match_then_most_specific_wins() {
+    let router = Router::new(None);
+    let present = router
+        .add_with_options("/search", with_query(vec![QueryCondition::present("q")]))
+        .expect("route should register")[0];
+    let pattern = router
+        .add_with_options(
+            "/search",
+            with_query(vec![QueryCondition::matches("q", "[0-9]+")]),
+        )
+        .expect("variant should register")[0];
+    let exact = router
+        .add_with_options(
+            "/search",
+            with_query(vec![QueryCondition::equals("q", "42")]),
+        )
+        .expect("variant should register")[0];
+    router.seal();
+
+    let key_for = |target: &str| {
+        router
+            .find(HttpMethod::Get, target)
+            .expect("should match")
+            .0
+    };
+    assert_eq!(key_for("/search?q=42"), exact);
+    assert_eq!(key_for("/search?q=7"), pattern);
+    assert_eq!(key_for("/search?q=rust"), present);
+}
+
+#[test]
+fn router_when_unconditional_route_shares_path_then_acts_as_fallback() {
+    let router = Router::new(None);
+    let fallback = router
+        .add(HttpMethod::Get, "/files/:id")
+        .expect("route should register");
+    let download = router
+        .add_with_options(
+            "/files/:id",
+            with_query(vec![QueryCondition::equals("download", "1")]),
+        )
+        .expect("variant should register")[0];
+    router.seal();
+
+    let (key, params) = router
+        .find(HttpMethod::Get, "/files/9?download=1")
+        .expect("variant should match");
+    assert_eq!(key, download);
+    assert_eq!(params.get("id").map(|s| s.as_str()), Some("9"));
+
+    let (key, _) = router
+        .find(HttpMethod::Get, "/files/9")
+        .expect("fallback should match");
+    assert_eq!(key, fallback);
+}
+
+#[test]
+fn router_when_conditions_are_indistinguishable_or_invalid_then_returns_error() {
+    let router = Router::new(None);
+    router
+        .add_with_options("/api", with_query(vec![QueryCondition::present("v")]))
+        .expect("route should register");
+    match router
+        .add_with_options("/api", with_query(vec![QueryCondition::present("v")]))
+        .expect_err("identical conditions should conflict")
+    {
+        RouterError::Radix(RadixError::DuplicateRoute { .. }) => {}
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    match RouteOptions::builder()
+        .query(vec![QueryCondition::matches("v", "(")])
+        .build()
+        .expect_err("invalid pattern should be rejected")
+    {
+        RouterOptionsError::QueryConditionInvalid { name, .. } => assert_eq!(name, "v"),
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
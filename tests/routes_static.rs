@@ -185,3 +185,24 @@ fn router_when_duplicate_static_route_registered_then_returns_error() {
         other => panic!("unexpected error: {other:?}"),
     }
 }
+
+#[test]
+fn router_when_static_full_map_enabled_then_returns_registered_keys() {
+    let router = Router::new(None);
+    let mut keys = Vec::new();
+    for i in 0..64 {
+        let key = router
+            .add(HttpMethod::Get, &format!("/static/{i}"))
+            .expect("route should register");
+        keys.push(key);
+    }
+    router.seal();
+
+    for (i, key) in keys.iter().enumerate() {
+        let (matched_key, params) = router
+            .find(HttpMethod::Get, &format!("/static/{i}"))
+            .expect("static route should match");
+        assert_eq!(matched_key, *key);
+        assert!(params.is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+use bunner_router_rs::{
+    HttpMethod, RouteOptions, Router, RouterError, RouterOptions, readonly::ReadOnlyError,
+    version::VersioningOptions,
+};
+
+fn versioned_router(options: Option<RouterOptions>) -> (Router, [u16; 3]) {
+    let router = Router::new(options);
+    let list_v1 = router
+        .add_versioned(1, HttpMethod::Get, "/users")
+        .expect("route should register");
+    let show_v1 = router
+        .add_versioned(1, HttpMethod::Get, "/users/:id")
+        .expect("route should register");
+    let show_v3 = router
+        .add_versioned(3, HttpMethod::Get, "/users/:id")
+        .expect("override should register");
+    router.seal();
+    (router, [list_v1, show_v1, show_v3])
+}
+
+#[test]
+fn router_when_version_in_path_then_resolves_highest_version_not_above_it() {
+    let (router, [list_v1, show_v1, show_v3]) = versioned_router(None);
+
+    let found = router
+        .find_versioned(HttpMethod::Get, "/v2/users/7", &())
+        .expect("v1 route should serve v2");
+    assert_eq!(found.key(), show_v1);
+    assert_eq!(found.version, Some(1));
+    assert_eq!(found.requested, Some(2));
+    assert_eq!(found.route.1.get("id").map(|s| s.as_str()), Some("7"));
+
+    let found = router
+        .find_versioned(HttpMethod::Get, "/v5/users/7", &())
+        .expect("v3 override should serve v5");
+    assert_eq!(found.key(), show_v3);
+    assert_eq!(found.version, Some(3));
+
+    let found = router
+        .find_versioned(HttpMethod::Get, "/v5/users", &())
+        .expect("v1 list should serve v5");
+    assert_eq!(found.key(), list_v1);
+    assert_eq!(found.version, Some(1));
+}
+
+#[test]
+fn router_when_version_in_header_or_media_type_then_uses_it() {
+    let (router, [_, show_v1, show_v3]) = versioned_router(None);
+
+    let found = router
+        .find_versioned(HttpMethod::Get, "/users/7", &[("Api-Version", "v4")])
+        .expect("header version should resolve");
+    assert_eq!(found.key(), show_v3);
+    assert_eq!(found.requested, Some(4));
+
+    let found = router
+        .find_versioned(
+            HttpMethod::Get,
+            "/users/7",
+            &[("Accept", "application/json; version=2")],
+        )
+        .expect("media type version should resolve");
+    assert_eq!(found.key(), show_v1);
+
+    let found = router
+        .find_versioned(HttpMethod::Get, "/users/7", &())
+        .expect("missing version should resolve to the newest");
+    assert_eq!(found.key(), show_v3);
+    assert_eq!(found.requested, Some(3));
+}
+
+#[test]
+fn router_when_default_version_configured_then_unversioned_requests_use_it() {
+    let options = RouterOptions::builder()
+        .versioning(VersioningOptions {
+            default_version: Some(1),
+            ..VersioningOptions::default()
+        })
+        .build()
+        .expect("options should build");
+    let (router, [_, show_v1, _]) = versioned_router(Some(options));
+
+    let found = router
+        .find_versioned(HttpMethod::Get, "/users/7", &())
+        .expect("default version should resolve");
+    assert_eq!(found.key(), show_v1);
+}
+
+#[test]
+fn router_when_no_version_matches_then_falls_back_to_unversioned_routes() {
+    let router = Router::new(None);
+    let health = router
+        .add(HttpMethod::Get, "/health")
+        .expect("route should register");
+    let keys = router
+        .group(
+            "/orders",
+            RouteOptions::builder()
+                .version(2)
+                .build()
+                .expect("options should build"),
+            |g| g.add(HttpMethod::Get, "/:id"),
+        )
+        .expect("group route should register");
+    router.seal();
+
+    let found = router
+        .find_versioned(HttpMethod::Get, "/v2/orders/1", &())
+        .expect("grouped route should resolve");
+    assert_eq!(found.key(), keys);
+    assert_eq!(found.version, Some(2));
+
+    let found = router
+        .find_versioned(HttpMethod::Get, "/v9/health", &())
+        .expect("unversioned route should resolve");
+    assert_eq!(found.key(), health);
+    assert_eq!(found.version, None);
+
+    match router
+        .find_versioned(HttpMethod::Get, "/v1/orders/1", &())
+        .expect_err("no version up to 1 has the route")
+    {
+        RouterError::ReadOnly(ReadOnlyError::RouteNotFound { .. }) => {}
+        other => panic!("unexpected error: {other:?}"),
+    }
+}